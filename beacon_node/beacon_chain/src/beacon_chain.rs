@@ -2,10 +2,11 @@ use crate::attestation_verification::{
     Error as AttestationError, SignatureVerifiedAttestation, VerifiedAggregatedAttestation,
     VerifiedUnaggregatedAttestation,
 };
+use crate::block_validation_cache::BlockValidationCache;
 use crate::block_verification::{
     check_block_is_finalized_descendant, check_block_relevancy, get_block_root,
     signature_verify_chain_segment, BlockError, FullyVerifiedBlock, GossipVerifiedBlock,
-    IntoFullyVerifiedBlock,
+    IntoFullyVerifiedBlock, SignatureVerifiedBlock,
 };
 use crate::chain_config::ChainConfig;
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
@@ -16,7 +17,9 @@ use crate::metrics;
 use crate::migrate::Migrate;
 use crate::naive_aggregation_pool::{Error as NaiveAggregationError, NaiveAggregationPool};
 use crate::observed_attestations::{Error as AttestationObservationError, ObservedAttestations};
-use crate::observed_attesters::{ObservedAggregators, ObservedAttesters};
+use crate::observed_attesters::{
+    Error as ObservedAttestersError, ObservedAggregators, ObservedAttesters,
+};
 use crate::observed_block_producers::ObservedBlockProducers;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
@@ -137,6 +140,9 @@ pub enum StateSkipConfig {
     WithoutStateRoots,
 }
 
+/// A cheaply-cloneable, immutable snapshot of the canonical head.
+pub type HeadSnapshot<E> = Arc<BeaconSnapshot<E>>;
+
 #[derive(Debug, PartialEq)]
 pub struct HeadInfo {
     pub slot: Slot,
@@ -187,6 +193,9 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     /// Maintains a record of which validators have been seen to create `SignedAggregateAndProofs`
     /// in recent epochs.
     pub observed_aggregators: ObservedAggregators<T::EthSpec>,
+    /// Maintains a record of which validators have been seen to attest in blocks during recent
+    /// epochs.
+    pub observed_block_attesters: ObservedAttesters<T::EthSpec>,
     /// Maintains a record of which validators have proposed blocks for each slot.
     pub observed_block_producers: ObservedBlockProducers<T::EthSpec>,
     /// Maintains a record of which validators have submitted voluntary exits.
@@ -198,7 +207,9 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     /// Provides information from the Ethereum 1 (PoW) chain.
     pub eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec>>,
     /// Stores a "snapshot" of the chain at the time the head-of-the-chain block was received.
-    pub(crate) canonical_head: TimeoutRwLock<BeaconSnapshot<T::EthSpec>>,
+    ///
+    /// The snapshot is replaced, rather than mutated, when the head changes.
+    pub(crate) canonical_head: TimeoutRwLock<HeadSnapshot<T::EthSpec>>,
     /// The root of the genesis block.
     pub genesis_block_root: Hash256,
     /// The root of the list of genesis validators, used during syncing.
@@ -218,6 +229,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub(crate) snapshot_cache: TimeoutRwLock<SnapshotCache<T::EthSpec>>,
     /// Caches the shuffling for a given epoch and state root.
     pub(crate) shuffling_cache: TimeoutRwLock<ShufflingCache>,
+    /// Caches the results of `Self::validate_block`, keyed by block root.
+    pub(crate) block_validation_cache: TimeoutRwLock<BlockValidationCache>,
     /// Caches a map of `validator_index -> validator_pubkey`.
     pub(crate) validator_pubkey_cache: TimeoutRwLock<ValidatorPubkeyCache>,
     /// A list of any hard-coded forks that have been disabled.
@@ -524,9 +537,21 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Returns a snapshot of the canonical head.
+    ///
+    /// Unlike `Self::head`, this does not clone the head `BeaconState`. The snapshot is never
+    /// mutated; a new one is created each time the head changes. Callers which read the head
+    /// several times should use a single snapshot, so that all reads are consistent.
+    pub fn head_snapshot(&self) -> Result<HeadSnapshot<T::EthSpec>, Error> {
+        self.canonical_head
+            .try_read_for(HEAD_LOCK_TIMEOUT)
+            .map(|head| head.clone())
+            .ok_or_else(|| Error::CanonicalHeadLockTimeout)
+    }
+
     /// Returns the current heads of the `BeaconChain`. For the canonical head, see `Self::head`.
     ///
-    /// Returns `(block_root, block_slot)`.
+    /// Returns `(block_root, block_slot)`, ordered by descending slot and then by ascending root.
     pub fn heads(&self) -> Vec<(Hash256, Slot)> {
         self.head_tracker.heads()
     }
@@ -678,6 +703,25 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(pubkey_cache.get(validator_index).cloned())
     }
 
+    /// Returns `true` if the validator with `validator_index` has been observed to attest (on
+    /// gossip or in a block), aggregate or propose a block during `epoch`.
+    ///
+    /// Observations are only retained for recent epochs. At least the current and previous epochs
+    /// are always available, earlier epochs will generally return `false`.
+    pub fn validator_seen_at_epoch(&self, validator_index: usize, epoch: Epoch) -> bool {
+        self.observed_attesters
+            .index_seen_at_epoch(validator_index, epoch)
+            || self
+                .observed_aggregators
+                .index_seen_at_epoch(validator_index, epoch)
+            || self
+                .observed_block_attesters
+                .index_seen_at_epoch(validator_index, epoch)
+            || self
+                .observed_block_producers
+                .index_seen_at_epoch(validator_index as u64, epoch)
+    }
+
     /// Returns the block canonical root of the current canonical chain at a given slot.
     ///
     /// Returns None if a block doesn't exist at the slot.
@@ -962,13 +1006,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let attestation = unaggregated_attestation.attestation();
 
         match self.naive_aggregation_pool.write().insert(attestation) {
-            Ok(outcome) => trace!(
-                self.log,
-                "Stored unaggregated attestation";
-                "outcome" => format!("{:?}", outcome),
-                "index" => attestation.data.index,
-                "slot" => attestation.data.slot.as_u64(),
-            ),
+            Ok(outcome) => {
+                trace!(
+                    self.log,
+                    "Stored unaggregated attestation";
+                    "outcome" => format!("{:?}", outcome),
+                    "index" => attestation.data.index,
+                    "slot" => attestation.data.slot.as_u64(),
+                );
+
+                // Only clone the attestation if someone is listening for it.
+                if self.event_handler.has_subscribers() {
+                    let _ = self
+                        .event_handler
+                        .register(EventKind::BeaconAttestationImported {
+                            attestation: Box::new(attestation.clone()),
+                        });
+                }
+            }
             Err(NaiveAggregationError::SlotTooLow {
                 slot,
                 lowest_permissible_slot,
@@ -1025,6 +1080,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .map_err(Error::from)?;
         }
 
+        if self.event_handler.has_subscribers() {
+            let _ = self
+                .event_handler
+                .register(EventKind::BeaconAttestationImported {
+                    attestation: Box::new(signed_aggregate.attestation().clone()),
+                });
+        }
+
         Ok(signed_aggregate)
     }
 
@@ -1119,6 +1182,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Remove any voluntary exits from the op pool for validators that have already initiated an
+    /// exit at the head state.
+    pub fn prune_voluntary_exits_at_head(&self) -> Result<(), Error> {
+        self.with_head(|head| {
+            self.op_pool
+                .prune_voluntary_exits_at_head(&head.beacon_state, &self.spec);
+            Ok(())
+        })
+    }
+
     /// Verify a proposer slashing before allowing it to propagate on the gossip network.
     pub fn verify_proposer_slashing_for_gossip(
         &self,
@@ -1352,6 +1425,54 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Fully verifies the given `block` against the chain, without importing it.
+    ///
+    /// Returns `Ok(Ok(()))` if the block is valid, or `Ok(Err(reason))` if it is invalid. Invalid
+    /// results are cached by block root so that repeatedly validating the same invalid block is
+    /// cheap. Results which may change over time (a valid block may later be finalized against,
+    /// or a parent may become known) are not cached.
+    ///
+    /// The parent of the block is cloned from the snapshot cache rather than removed from it, so
+    /// validation doesn't slow down the import of the next block.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `Err` if an error was encountered whilst trying to verify the block.
+    pub fn validate_block(
+        &self,
+        block: SignedBeaconBlock<T::EthSpec>,
+    ) -> Result<Result<(), String>, Error> {
+        let block_root = get_block_root(&block);
+
+        if let Some(result) = self
+            .block_validation_cache
+            .try_write_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
+            .ok_or_else(|| Error::BlockValidationCacheLockTimeout)?
+            .get(&block_root)
+        {
+            return Ok(result.clone());
+        }
+
+        let result = SignatureVerifiedBlock::new_without_import(block, self)
+            .and_then(|block| block.into_fully_verified_block(self));
+
+        let reason = match result {
+            Ok(_) => return Ok(Ok(())),
+            Err(BlockError::BeaconChainError(e)) => return Err(e),
+            Err(e @ BlockError::ParentUnknown(_))
+            | Err(e @ BlockError::FutureSlot { .. })
+            | Err(e @ BlockError::BlockIsAlreadyKnown) => return Ok(Err(e.to_string())),
+            Err(e) => e.to_string(),
+        };
+
+        self.block_validation_cache
+            .try_write_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
+            .ok_or_else(|| Error::BlockValidationCacheLockTimeout)?
+            .insert(block_root, Err(reason.clone()));
+
+        Ok(Err(reason))
+    }
+
     /// Returns `Ok(block_root)` if the given `unverified_block` was successfully verified and
     /// imported into the chain.
     ///
@@ -1523,6 +1644,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .map_err(|e| BlockError::BeaconChainError(e.into()))?;
         }
 
+        let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+
+        // The block is fully verified, so record its proposer. This ensures that proposers are
+        // known even if the block was never seen on gossip (e.g., it was published via the HTTP
+        // API). Liveness bookkeeping must not fail the import, so errors are only logged.
+        if let Err(e) = self.observed_block_producers.observe_proposer(block) {
+            error!(
+                self.log,
+                "Failed to observe block proposer";
+                "error" => format!("{:?}", e),
+                "block_root" => format!("{:?}", block_root)
+            );
+        }
+
         // Register each attestation in the block with the fork choice service.
         for attestation in &block.body.attestations[..] {
             let _fork_choice_attestation_timer =
@@ -1533,6 +1668,26 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             let indexed_attestation = get_indexed_attestation(committee.committee, attestation)
                 .map_err(|e| BlockError::BeaconChainError(e.into()))?;
 
+            // Record the attesters of recent attestations so that their liveness is known, even
+            // if the attestation was never seen on gossip.
+            if attestation.data.target.epoch + 1 >= current_epoch {
+                for &validator_index in &indexed_attestation.attesting_indices {
+                    match self.observed_block_attesters.observe_validator_at_epoch(
+                        attestation.data.target.epoch,
+                        validator_index as usize,
+                    ) {
+                        Ok(_) | Err(ObservedAttestersError::EpochTooLow { .. }) => {}
+                        Err(e) => error!(
+                            self.log,
+                            "Failed to observe block attester";
+                            "error" => format!("{:?}", e),
+                            "validator_index" => validator_index,
+                            "block_root" => format!("{:?}", block_root)
+                        ),
+                    }
+                }
+            }
+
             match fork_choice.on_attestation(current_slot, &indexed_attestation) {
                 Ok(()) => Ok(()),
                 // Ignore invalid attestations whilst importing attestations from a block. The
@@ -1823,8 +1978,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .map(|root| *root)
                 .unwrap_or_else(|_| Hash256::random());
 
+        let mut reorg_event = None;
         if is_reorg {
             metrics::inc_counter(&metrics::FORK_CHOICE_REORG_COUNT);
+
+            // The depth is only informational, so failing to find it must not prevent the head
+            // from being updated.
+            let depth =
+                match self.common_ancestor_slot(current_head.block_root, &new_head.beacon_state) {
+                    Ok(common_ancestor_slot) => common_ancestor_slot
+                        .map(|slot| current_head.slot.saturating_sub(slot).as_u64()),
+                    Err(e) => {
+                        error!(
+                            self.log,
+                            "Unable to find re-org common ancestor";
+                            "error" => format!("{:?}", e),
+                            "previous_head" => format!("{}", current_head.block_root),
+                        );
+                        None
+                    }
+                };
+
             warn!(
                 self.log,
                 "Beacon chain re-org";
@@ -1832,8 +2006,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "previous_slot" => current_head.slot,
                 "new_head_parent" => format!("{}", new_head.beacon_block.parent_root()),
                 "new_head" => format!("{}", beacon_block_root),
-                "new_slot" => new_head.beacon_block.slot()
+                "new_slot" => new_head.beacon_block.slot(),
+                "depth" => depth.map_or_else(|| "unknown".to_string(), |depth| depth.to_string()),
             );
+
+            // A depth can't be given if the common ancestor is beyond the reach of the new head's
+            // `block_roots` or couldn't be found, in which case only the `BeaconHeadChanged`
+            // event is emitted.
+            if let Some(depth) = depth {
+                metrics::set_gauge(&metrics::FORK_CHOICE_REORG_DEPTH, depth as i64);
+
+                reorg_event = Some(EventKind::BeaconChainReorg {
+                    slot: new_head.beacon_block.slot(),
+                    depth,
+                    old_head_block_root: current_head.block_root,
+                    new_head_block_root: beacon_block_root,
+                    old_head_state_root: current_head.state_root,
+                    new_head_state_root: new_head.beacon_state_root,
+                    epoch: new_head
+                        .beacon_block
+                        .slot()
+                        .epoch(T::EthSpec::slots_per_epoch()),
+                });
+            }
         } else {
             debug!(
                 self.log,
@@ -1893,7 +2088,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         *self
             .canonical_head
             .try_write_for(HEAD_LOCK_TIMEOUT)
-            .ok_or_else(|| Error::CanonicalHeadLockTimeout)? = new_head;
+            .ok_or_else(|| Error::CanonicalHeadLockTimeout)? = Arc::new(new_head);
 
         metrics::stop_timer(update_head_timer);
 
@@ -1911,6 +2106,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 );
             });
 
+        // Op pool housekeeping must not abort a head update which has already been applied.
+        if let Err(e) = self.prune_voluntary_exits_at_head() {
+            error!(
+                self.log,
+                "Failed to prune voluntary exits";
+                "error" => format!("{:?}", e),
+                "task" => "update head"
+            );
+        }
+
         if new_finalized_checkpoint.epoch != old_finalized_checkpoint.epoch {
             self.after_finalization(
                 old_finalized_checkpoint,
@@ -1925,9 +2130,43 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             current_head_beacon_block_root: beacon_block_root,
         });
 
+        if let Some(reorg_event) = reorg_event {
+            let _ = self.event_handler.register(reorg_event);
+        }
+
         Ok(())
     }
 
+    /// Returns the slot of the most recent block which is an ancestor of both the block at
+    /// `old_head_block_root` and the block at the head of `new_head_state`.
+    ///
+    /// Returns `None` if the common ancestor is older than the block roots stored in
+    /// `new_head_state`.
+    fn common_ancestor_slot(
+        &self,
+        old_head_block_root: Hash256,
+        new_head_state: &BeaconState<T::EthSpec>,
+    ) -> Result<Option<Slot>, Error> {
+        for result in ParentRootBlockIterator::new(&*self.store, old_head_block_root) {
+            let (block_root, block) = result?;
+            let slot = block.slot();
+
+            // The state's own block root is not yet known, so only strictly older blocks can be
+            // compared.
+            if slot >= new_head_state.slot {
+                continue;
+            }
+
+            match new_head_state.get_block_root(slot) {
+                Ok(root) if *root == block_root => return Ok(Some(slot)),
+                Ok(_) => continue,
+                Err(_) => return Ok(None),
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Called by the timer on every slot.
     ///
     /// Performs slot-based pruning.
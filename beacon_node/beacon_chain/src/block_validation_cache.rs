@@ -0,0 +1,40 @@
+use crate::metrics;
+use lru::LruCache;
+use types::Hash256;
+
+/// The number of block validation results stored in the cache.
+const CACHE_SIZE: usize = 64;
+
+/// Provides an LRU cache of the results of validating (but not importing) blocks, keyed by block
+/// root.
+///
+/// A result is either `Ok(())` for a valid block or `Err(reason)` for an invalid block. Only
+/// invalid results are inserted by `BeaconChain::validate_block`, since the validity of a valid
+/// block may change (e.g., once it conflicts with finalization).
+pub struct BlockValidationCache {
+    cache: LruCache<Hash256, Result<(), String>>,
+}
+
+impl BlockValidationCache {
+    pub fn new() -> Self {
+        Self {
+            cache: LruCache::new(CACHE_SIZE),
+        }
+    }
+
+    pub fn get(&mut self, block_root: &Hash256) -> Option<&Result<(), String>> {
+        let opt = self.cache.get(block_root);
+
+        if opt.is_some() {
+            metrics::inc_counter(&metrics::BLOCK_VALIDATION_CACHE_HITS);
+        } else {
+            metrics::inc_counter(&metrics::BLOCK_VALIDATION_CACHE_MISSES);
+        }
+
+        opt
+    }
+
+    pub fn insert(&mut self, block_root: Hash256, result: Result<(), String>) {
+        self.cache.put(block_root, result);
+    }
+}
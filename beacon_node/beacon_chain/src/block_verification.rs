@@ -519,8 +519,26 @@ impl<T: BeaconChainTypes> SignatureVerifiedBlock<T> {
         block: SignedBeaconBlock<T::EthSpec>,
         chain: &BeaconChain<T>,
     ) -> Result<Self, BlockError<T::EthSpec>> {
-        let (mut parent, block) = load_parent(block, chain)?;
+        let (parent, block) = load_parent(block, chain)?;
+        Self::from_parent(block, parent, chain)
+    }
+
+    /// As per `new`, except that the parent is cloned from the snapshot cache rather than removed
+    /// from it. Use this when the block will not be imported, so that the cache is left intact.
+    pub fn new_without_import(
+        block: SignedBeaconBlock<T::EthSpec>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, BlockError<T::EthSpec>> {
+        let (parent, block) = load_parent_cloned(block, chain)?;
+        Self::from_parent(block, parent, chain)
+    }
 
+    /// Verifies the signatures of `block`, given its `parent`.
+    fn from_parent(
+        block: SignedBeaconBlock<T::EthSpec>,
+        mut parent: BeaconSnapshot<T::EthSpec>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, BlockError<T::EthSpec>> {
         // Reject any block that exceeds our limit on skipped slots.
         check_block_skip_slots(chain, &parent.beacon_block.message, &block.message)?;
 
@@ -945,6 +963,25 @@ pub fn get_block_root<E: EthSpec>(block: &SignedBeaconBlock<E>) -> Hash256 {
 fn load_parent<T: BeaconChainTypes>(
     block: SignedBeaconBlock<T::EthSpec>,
     chain: &BeaconChain<T>,
+) -> Result<(BeaconSnapshot<T::EthSpec>, SignedBeaconBlock<T::EthSpec>), BlockError<T::EthSpec>> {
+    load_parent_from_cache(block, chain, true)
+}
+
+/// As per `load_parent`, except that a parent found in the snapshot cache is cloned rather than
+/// removed from the cache.
+fn load_parent_cloned<T: BeaconChainTypes>(
+    block: SignedBeaconBlock<T::EthSpec>,
+    chain: &BeaconChain<T>,
+) -> Result<(BeaconSnapshot<T::EthSpec>, SignedBeaconBlock<T::EthSpec>), BlockError<T::EthSpec>> {
+    load_parent_from_cache(block, chain, false)
+}
+
+/// Loads the parent of `block`, removing it from the snapshot cache if `remove_from_cache` is
+/// `true`, or cloning it otherwise.
+fn load_parent_from_cache<T: BeaconChainTypes>(
+    block: SignedBeaconBlock<T::EthSpec>,
+    chain: &BeaconChain<T>,
+    remove_from_cache: bool,
 ) -> Result<(BeaconSnapshot<T::EthSpec>, SignedBeaconBlock<T::EthSpec>), BlockError<T::EthSpec>> {
     // Reject any block if its parent is not known to fork choice.
     //
@@ -966,11 +1003,19 @@ fn load_parent<T: BeaconChainTypes>(
 
     let db_read_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_DB_READ);
 
-    let result = if let Some(snapshot) = chain
-        .snapshot_cache
-        .try_write_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
-        .and_then(|mut snapshot_cache| snapshot_cache.try_remove(block.parent_root()))
-    {
+    let cached_parent = if remove_from_cache {
+        chain
+            .snapshot_cache
+            .try_write_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
+            .and_then(|mut snapshot_cache| snapshot_cache.try_remove(block.parent_root()))
+    } else {
+        chain
+            .snapshot_cache
+            .try_read_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
+            .and_then(|snapshot_cache| snapshot_cache.get_cloned(block.parent_root()))
+    };
+
+    let result = if let Some(snapshot) = cached_parent {
         Ok((snapshot, block))
     } else {
         // Load the blocks parent block from the database, returning invalid if that block is not
@@ -1,10 +1,12 @@
 use crate::beacon_chain::{
     BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, FORK_CHOICE_DB_KEY, OP_POOL_DB_KEY,
 };
+use crate::block_validation_cache::BlockValidationCache;
 use crate::eth1_chain::{CachingEth1Backend, SszEth1};
 use crate::events::NullEventHandler;
 use crate::head_tracker::HeadTracker;
 use crate::migrate::Migrate;
+use crate::naive_aggregation_pool::NaiveAggregationPool;
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::shuffling_cache::ShufflingCache;
@@ -505,6 +507,12 @@ where
                 .map_err(|e| format!("Unable to build initialize ForkChoice: {:?}", e))?
         };
 
+        let naive_aggregation_pool = NaiveAggregationPool::new(
+            self.chain_config.naive_aggregation_pool_slots_retained,
+            self.chain_config
+                .naive_aggregation_pool_max_attestations_per_slot,
+        );
+
         let beacon_chain = BeaconChain {
             spec: self.spec,
             config: self.chain_config,
@@ -517,7 +525,7 @@ where
                 .op_pool
                 .ok_or_else(|| "Cannot build without op pool".to_string())?,
             // TODO: allow for persisting and loading the pool from disk.
-            naive_aggregation_pool: <_>::default(),
+            naive_aggregation_pool: RwLock::new(naive_aggregation_pool),
             // TODO: allow for persisting and loading the pool from disk.
             observed_attestations: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
@@ -525,6 +533,8 @@ where
             // TODO: allow for persisting and loading the pool from disk.
             observed_aggregators: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
+            observed_block_attesters: <_>::default(),
+            // TODO: allow for persisting and loading the pool from disk.
             observed_block_producers: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_voluntary_exits: <_>::default(),
@@ -532,7 +542,7 @@ where
             observed_attester_slashings: <_>::default(),
             eth1_chain: self.eth1_chain,
             genesis_validators_root: canonical_head.beacon_state.genesis_validators_root,
-            canonical_head: TimeoutRwLock::new(canonical_head.clone()),
+            canonical_head: TimeoutRwLock::new(Arc::new(canonical_head.clone())),
             genesis_block_root: self
                 .genesis_block_root
                 .ok_or_else(|| "Cannot build without a genesis block root".to_string())?,
@@ -546,6 +556,7 @@ where
                 canonical_head,
             )),
             shuffling_cache: TimeoutRwLock::new(ShufflingCache::new()),
+            block_validation_cache: TimeoutRwLock::new(BlockValidationCache::new()),
             validator_pubkey_cache: TimeoutRwLock::new(validator_pubkey_cache),
             disabled_forks: self.disabled_forks,
             log: log.clone(),
@@ -1,3 +1,4 @@
+use crate::naive_aggregation_pool::{MAX_ATTESTATIONS_PER_SLOT, SLOTS_RETAINED};
 use serde_derive::{Deserialize, Serialize};
 
 /// There is a 693 block skip in the current canonical Medalla chain, we use 700 to be safe.
@@ -10,12 +11,20 @@ pub struct ChainConfig {
     ///
     /// If `None`, there is no limit.
     pub import_max_skip_slots: Option<u64>,
+    /// The number of slots for which unaggregated attestations are stored in the naive
+    /// aggregation pool. Attestations older than this are evicted when the pool is pruned.
+    pub naive_aggregation_pool_slots_retained: usize,
+    /// The maximum number of distinct `AttestationData` stored in the naive aggregation pool for
+    /// each slot.
+    pub naive_aggregation_pool_max_attestations_per_slot: usize,
 }
 
 impl Default for ChainConfig {
     fn default() -> Self {
         Self {
             import_max_skip_slots: Some(DEFAULT_IMPORT_BLOCK_MAX_SKIP_SLOTS),
+            naive_aggregation_pool_slots_retained: SLOTS_RETAINED,
+            naive_aggregation_pool_max_attestations_per_slot: MAX_ATTESTATIONS_PER_SLOT,
         }
     }
 }
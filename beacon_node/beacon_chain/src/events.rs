@@ -1,14 +1,25 @@
-use bus::Bus;
 use parking_lot::Mutex;
 use serde_derive::{Deserialize, Serialize};
-use slog::{error, Logger};
+use slog::{warn, Logger};
 use std::marker::PhantomData;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
 use std::sync::Arc;
-use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedBeaconBlockHash};
+use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 pub use websocket_server::WebSocketSender;
 
+/// The maximum number of events which may be queued for each SSE consumer.
+///
+/// Events are dropped for a consumer, rather than buffered, whilst it is this far behind.
+pub const SSE_EVENT_QUEUE_LENGTH: usize = 1_024;
+
 pub trait EventHandler<T: EthSpec>: Sized + Send + Sync {
     fn register(&self, kind: EventKind<T>) -> Result<(), String>;
+
+    /// Returns `false` if any event given to `Self::register` would be discarded, so that callers
+    /// may avoid building events which are expensive to build.
+    fn has_subscribers(&self) -> bool {
+        true
+    }
 }
 
 pub struct NullEventHandler<T: EthSpec>(PhantomData<T>);
@@ -20,52 +31,93 @@ impl<T: EthSpec> EventHandler<T> for WebSocketSender<T> {
                 .map_err(|e| format!("Unable to serialize event: {:?}", e))?,
         )
     }
+
+    fn has_subscribers(&self) -> bool {
+        self.is_enabled()
+    }
+}
+
+/// Broadcasts events to any number of consumers, each with its own bounded queue.
+///
+/// A consumer which falls behind only misses events itself, it never delays or drops the events of
+/// other consumers.
+pub struct EventBus<T: EthSpec> {
+    subscribers: Mutex<Vec<SyncSender<EventKind<T>>>>,
+}
+
+impl<T: EthSpec> EventBus<T> {
+    /// Returns a receiver of every event broadcast from now on.
+    ///
+    /// The consumer is removed from the bus once the receiver is dropped.
+    pub fn subscribe(&self) -> Receiver<EventKind<T>> {
+        let (sender, receiver) = sync_channel(SSE_EVENT_QUEUE_LENGTH);
+        self.subscribers.lock().push(sender);
+        receiver
+    }
+
+    /// Returns `true` if there is at least one consumer.
+    pub fn has_subscribers(&self) -> bool {
+        !self.subscribers.lock().is_empty()
+    }
+
+    /// Queues `kind` for each consumer, returning the number of consumers whose queue was full.
+    fn broadcast(&self, kind: EventKind<T>) -> usize {
+        let mut dropped = 0;
+        self.subscribers
+            .lock()
+            .retain(|sender| match sender.try_send(kind.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    dropped += 1;
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            });
+        dropped
+    }
+}
+
+impl<T: EthSpec> Default for EventBus<T> {
+    fn default() -> Self {
+        Self {
+            subscribers: Mutex::new(vec![]),
+        }
+    }
 }
 
 pub struct ServerSentEvents<T: EthSpec> {
-    // Bus<> is itself Sync + Send.  We use Mutex<> here only because of the surrounding code does
-    // not enforce mutability statically (i.e. relies on interior mutability).
-    head_changed_queue: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    event_bus: Arc<EventBus<T>>,
     log: Logger,
-    _phantom: PhantomData<T>,
 }
 
 impl<T: EthSpec> ServerSentEvents<T> {
-    pub fn new(log: Logger) -> (Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>) {
-        let bus = Bus::new(T::slots_per_epoch() as usize);
-        let mutex = Mutex::new(bus);
-        let arc = Arc::new(mutex);
+    pub fn new(log: Logger) -> (Self, Arc<EventBus<T>>) {
+        let event_bus = Arc::new(EventBus::default());
         let this = Self {
-            head_changed_queue: arc.clone(),
+            event_bus: event_bus.clone(),
             log,
-            _phantom: PhantomData,
         };
-        (this, arc)
+        (this, event_bus)
     }
 }
 
 impl<T: EthSpec> EventHandler<T> for ServerSentEvents<T> {
     fn register(&self, kind: EventKind<T>) -> Result<(), String> {
-        match kind {
-            EventKind::BeaconHeadChanged {
-                current_head_beacon_block_root,
-                ..
-            } => {
-                let mut guard = self.head_changed_queue.lock();
-                if guard
-                    .try_broadcast(current_head_beacon_block_root.into())
-                    .is_err()
-                {
-                    error!(
-                        self.log,
-                        "Head change streaming queue full";
-                        "dropped_change" => format!("{}", current_head_beacon_block_root),
-                    );
-                }
-                Ok(())
-            }
-            _ => Ok(()),
+        let name = kind.name();
+        let dropped = self.event_bus.broadcast(kind);
+        if dropped > 0 {
+            warn!(
+                self.log,
+                "Event streaming queue full";
+                "dropped_event" => name,
+                "consumers" => dropped,
+            );
         }
+        Ok(())
+    }
+
+    fn has_subscribers(&self) -> bool {
+        self.event_bus.has_subscribers()
     }
 }
 
@@ -78,11 +130,10 @@ pub struct TeeEventHandler<E: EthSpec> {
 }
 
 impl<E: EthSpec> TeeEventHandler<E> {
-    #[allow(clippy::type_complexity)]
     pub fn new(
         log: Logger,
         websockets_handler: WebSocketSender<E>,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
+    ) -> Result<(Self, Arc<EventBus<E>>), String> {
         let (sse_handler, bus) = ServerSentEvents::new(log);
         let result = Self {
             websockets_handler,
@@ -98,12 +149,20 @@ impl<E: EthSpec> EventHandler<E> for TeeEventHandler<E> {
         self.sse_handler.register(kind)?;
         Ok(())
     }
+
+    fn has_subscribers(&self) -> bool {
+        self.websockets_handler.has_subscribers() || self.sse_handler.has_subscribers()
+    }
 }
 
 impl<T: EthSpec> EventHandler<T> for NullEventHandler<T> {
     fn register(&self, _kind: EventKind<T>) -> Result<(), String> {
         Ok(())
     }
+
+    fn has_subscribers(&self) -> bool {
+        false
+    }
 }
 
 impl<T: EthSpec> Default for NullEventHandler<T> {
@@ -125,6 +184,18 @@ pub enum EventKind<T: EthSpec> {
         current_head_beacon_block_root: Hash256,
         previous_head_beacon_block_root: Hash256,
     },
+    /// The head has moved to a block which does not descend from the previous head.
+    BeaconChainReorg {
+        /// The slot of the new head.
+        slot: Slot,
+        /// The number of slots between the previous head and its common ancestor with the new head.
+        depth: u64,
+        old_head_block_root: Hash256,
+        new_head_block_root: Hash256,
+        old_head_state_root: Hash256,
+        new_head_state_root: Hash256,
+        epoch: Epoch,
+    },
     BeaconFinalization {
         epoch: Epoch,
         root: Hash256,
@@ -145,3 +216,75 @@ pub enum EventKind<T: EthSpec> {
         attestation: Box<Attestation<T>>,
     },
 }
+
+impl<T: EthSpec> EventKind<T> {
+    /// Returns the name of the event, as used in its serialized form.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::BeaconHeadChanged { .. } => "beacon_head_changed",
+            EventKind::BeaconChainReorg { .. } => "beacon_chain_reorg",
+            EventKind::BeaconFinalization { .. } => "beacon_finalization",
+            EventKind::BeaconBlockImported { .. } => "beacon_block_imported",
+            EventKind::BeaconBlockRejected { .. } => "beacon_block_rejected",
+            EventKind::BeaconAttestationImported { .. } => "beacon_attestation_imported",
+            EventKind::BeaconAttestationRejected { .. } => "beacon_attestation_rejected",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    fn finalization(epoch: u64) -> EventKind<E> {
+        EventKind::BeaconFinalization {
+            epoch: Epoch::new(epoch),
+            root: Hash256::zero(),
+        }
+    }
+
+    #[test]
+    fn full_consumer_does_not_affect_others() {
+        let bus = EventBus::<E>::default();
+        assert!(!bus.has_subscribers());
+
+        let slow = bus.subscribe();
+        let fast = bus.subscribe();
+        assert!(bus.has_subscribers());
+
+        for epoch in 0..SSE_EVENT_QUEUE_LENGTH as u64 {
+            assert_eq!(bus.broadcast(finalization(epoch)), 0);
+            assert!(fast.try_recv().is_ok());
+        }
+
+        assert_eq!(
+            bus.broadcast(finalization(SSE_EVENT_QUEUE_LENGTH as u64)),
+            1,
+            "the event should only be dropped for the full consumer"
+        );
+        match fast.try_recv() {
+            Ok(EventKind::BeaconFinalization { epoch, .. }) => {
+                assert_eq!(epoch, Epoch::new(SSE_EVENT_QUEUE_LENGTH as u64))
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert_eq!(
+            slow.iter().take(SSE_EVENT_QUEUE_LENGTH).count(),
+            SSE_EVENT_QUEUE_LENGTH
+        );
+    }
+
+    #[test]
+    fn dropped_consumer_is_removed() {
+        let bus = EventBus::<E>::default();
+        let receiver = bus.subscribe();
+        drop(receiver);
+
+        assert!(bus.has_subscribers());
+        bus.broadcast(finalization(0));
+        assert!(!bus.has_subscribers());
+    }
+}
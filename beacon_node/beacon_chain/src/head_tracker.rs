@@ -41,13 +41,19 @@ impl HeadTracker {
         self.0.read().contains_key(&block_root)
     }
 
-    /// Returns the list of heads in the chain.
+    /// Returns the list of heads in the chain, ordered by descending slot and then by ascending
+    /// block root.
     pub fn heads(&self) -> Vec<(Hash256, Slot)> {
-        self.0
+        let mut heads = self
+            .0
             .read()
             .iter()
             .map(|(root, slot)| (*root, *slot))
-            .collect()
+            .collect::<Vec<_>>();
+        heads.sort_by(|(root_a, slot_a), (root_b, slot_b)| {
+            slot_b.cmp(slot_a).then_with(|| root_a.cmp(root_b))
+        });
+        heads
     }
 
     /// Returns a `SszHeadTracker`, which contains all necessary information to restore the state
@@ -143,20 +149,20 @@ mod test {
         block.parent_root = Hash256::from_low_u64_be(14);
         head_tracker.register_block(block_root, block.parent_root, block.slot);
 
-        let heads = head_tracker.heads();
+        let mut block: BeaconBlock<E> = BeaconBlock::empty(spec);
+        let block_root = Hash256::from_low_u64_be(7);
+        block.slot = Slot::new(14);
+        block.parent_root = Hash256::from_low_u64_be(13);
+        head_tracker.register_block(block_root, block.parent_root, block.slot);
 
-        assert_eq!(heads.len(), 2, "should only have two heads");
-        assert!(
-            heads
-                .iter()
-                .any(|(root, slot)| *root == Hash256::from_low_u64_be(15) && *slot == Slot::new(15)),
-            "should contain first head"
-        );
-        assert!(
-            heads
-                .iter()
-                .any(|(root, slot)| *root == Hash256::from_low_u64_be(42) && *slot == Slot::new(15)),
-            "should contain second head"
+        assert_eq!(
+            head_tracker.heads(),
+            vec![
+                (Hash256::from_low_u64_be(15), Slot::new(15)),
+                (Hash256::from_low_u64_be(42), Slot::new(15)),
+                (Hash256::from_low_u64_be(7), Slot::new(14)),
+            ],
+            "should order heads by descending slot then ascending root"
         );
     }
 
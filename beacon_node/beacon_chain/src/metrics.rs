@@ -180,6 +180,14 @@ lazy_static! {
     pub static ref SHUFFLING_CACHE_MISSES: Result<IntCounter> =
         try_create_int_counter("beacon_shuffling_cache_misses_total", "Count of times shuffling cache fulfils request");
 
+    /*
+     * Block Validation Cache
+     */
+    pub static ref BLOCK_VALIDATION_CACHE_HITS: Result<IntCounter> =
+        try_create_int_counter("beacon_block_validation_cache_hits_total", "Count of times the block validation cache fulfils request");
+    pub static ref BLOCK_VALIDATION_CACHE_MISSES: Result<IntCounter> =
+        try_create_int_counter("beacon_block_validation_cache_misses_total", "Count of times the block validation cache does not fulfil request");
+
     /*
      * Attestation Production
      */
@@ -218,6 +226,10 @@ lazy_static! {
         "beacon_fork_choice_reorg_total",
         "Count of occasions fork choice has switched to a different chain"
     );
+    pub static ref FORK_CHOICE_REORG_DEPTH: Result<IntGauge> = try_create_int_gauge(
+        "beacon_fork_choice_reorg_depth",
+        "Number of slots between the previous head and the common ancestor in the latest re-org"
+    );
     pub static ref FORK_CHOICE_TIMES: Result<Histogram> =
         try_create_histogram("beacon_fork_choice_seconds", "Full runtime of fork choice");
     pub static ref FORK_CHOICE_FIND_HEAD_TIMES: Result<Histogram> =
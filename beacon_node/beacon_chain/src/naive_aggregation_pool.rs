@@ -2,17 +2,17 @@ use crate::metrics;
 use std::collections::HashMap;
 use types::{Attestation, AttestationData, EthSpec, Slot};
 
-/// The number of slots that will be stored in the pool.
+/// The default number of slots that will be stored in the pool.
 ///
 /// For example, if `SLOTS_RETAINED == 3` and the pool is pruned at slot `6`, then all attestations
 /// at slots less than `4` will be dropped and any future attestation with a slot less than `4`
 /// will be refused.
-const SLOTS_RETAINED: usize = 3;
+pub const SLOTS_RETAINED: usize = 3;
 
-/// The maximum number of distinct `AttestationData` that will be stored in each slot.
+/// The default maximum number of distinct `AttestationData` that will be stored in each slot.
 ///
 /// This is a DoS protection measure.
-const MAX_ATTESTATIONS_PER_SLOT: usize = 16_384;
+pub const MAX_ATTESTATIONS_PER_SLOT: usize = 16_384;
 
 /// Returned upon successfully inserting an attestation into the pool.
 #[derive(Debug, PartialEq)]
@@ -66,8 +66,13 @@ impl<E: EthSpec> AggregatedAttestationMap<E> {
 
     /// Insert an attestation into `self`, aggregating it into the pool.
     ///
-    /// The given attestation (`a`) must only have one signature.
-    pub fn insert(&mut self, a: &Attestation<E>) -> Result<InsertOutcome, Error> {
+    /// The given attestation (`a`) must only have one signature. No more than
+    /// `max_attestations` distinct `AttestationData` will be stored.
+    pub fn insert(
+        &mut self,
+        a: &Attestation<E>,
+        max_attestations: usize,
+    ) -> Result<InsertOutcome, Error> {
         let _timer = metrics::start_timer(&metrics::ATTESTATION_PROCESSING_AGG_POOL_CORE_INSERT);
 
         let set_bits = a
@@ -101,10 +106,8 @@ impl<E: EthSpec> AggregatedAttestationMap<E> {
                 Ok(InsertOutcome::SignatureAggregated { committee_index })
             }
         } else {
-            if self.map.len() >= MAX_ATTESTATIONS_PER_SLOT {
-                return Err(Error::ReachedMaxAttestationsPerSlot(
-                    MAX_ATTESTATIONS_PER_SLOT,
-                ));
+            if self.map.len() >= max_attestations {
+                return Err(Error::ReachedMaxAttestationsPerSlot(max_attestations));
             }
 
             self.map.insert(a.data.clone(), a.clone());
@@ -144,27 +147,42 @@ impl<E: EthSpec> AggregatedAttestationMap<E> {
 /// signature, there should only ever be a single aggregated `Attestation` for any given
 /// `AttestationData`.
 ///
-/// The pool has a capacity for `SLOTS_RETAINED` slots, when a new `attestation.data.slot` is
+/// The pool has a capacity for `slots_retained` slots, when a new `attestation.data.slot` is
 /// provided, the oldest slot is dropped and replaced with the new slot. The pool can also be
 /// pruned by supplying a `current_slot`; all existing attestations with a slot lower than
-/// `current_slot - SLOTS_RETAINED` will be removed and any future attestation with a slot lower
+/// `current_slot - slots_retained` will be removed and any future attestation with a slot lower
 /// than that will also be refused. Pruning is done automatically based upon the attestations it
 /// receives and it can be triggered manually.
+///
+/// Each slot stores at most `max_attestations_per_slot` distinct `AttestationData`.
 pub struct NaiveAggregationPool<E: EthSpec> {
     lowest_permissible_slot: Slot,
+    slots_retained: usize,
+    max_attestations_per_slot: usize,
     maps: HashMap<Slot, AggregatedAttestationMap<E>>,
 }
 
 impl<E: EthSpec> Default for NaiveAggregationPool<E> {
     fn default() -> Self {
+        Self::new(SLOTS_RETAINED, MAX_ATTESTATIONS_PER_SLOT)
+    }
+}
+
+impl<E: EthSpec> NaiveAggregationPool<E> {
+    /// Create an empty pool which retains attestations from `slots_retained` slots and stores at
+    /// most `max_attestations_per_slot` distinct `AttestationData` per slot.
+    ///
+    /// A `slots_retained` of zero is treated as one, since the pool must be able to store
+    /// attestations for at least the current slot.
+    pub fn new(slots_retained: usize, max_attestations_per_slot: usize) -> Self {
         Self {
             lowest_permissible_slot: Slot::new(0),
+            slots_retained: std::cmp::max(slots_retained, 1),
+            max_attestations_per_slot,
             maps: HashMap::new(),
         }
     }
-}
 
-impl<E: EthSpec> NaiveAggregationPool<E> {
     /// Insert an attestation into `self`, aggregating it into the pool.
     ///
     /// The given attestation (`a`) must only have one signature and have an
@@ -189,8 +207,9 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
             metrics::start_timer(&metrics::ATTESTATION_PROCESSING_AGG_POOL_MAPS_WRITE_LOCK);
         drop(lock_timer);
 
+        let max_attestations = self.max_attestations_per_slot;
         let outcome = if let Some(map) = self.maps.get_mut(&slot) {
-            map.insert(attestation)
+            map.insert(attestation, max_attestations)
         } else {
             let _timer = metrics::start_timer(&metrics::ATTESTATION_PROCESSING_AGG_POOL_CREATE_MAP);
             // To avoid re-allocations, try and determine a rough initial capacity for the new item
@@ -208,7 +227,7 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
             let initial_capacity = sum.checked_div(count).unwrap_or(128);
 
             let mut item = AggregatedAttestationMap::new(initial_capacity);
-            let outcome = item.insert(attestation);
+            let outcome = item.insert(attestation, max_attestations);
             self.maps.insert(slot, item);
 
             outcome
@@ -233,18 +252,29 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
         self.maps.iter().map(|(_slot, map)| map.iter()).flatten()
     }
 
+    /// Returns the number of distinct `AttestationData` stored across all slots of `self`.
+    pub fn num_attestations(&self) -> usize {
+        self.maps.values().map(AggregatedAttestationMap::len).sum()
+    }
+
+    /// Returns the lowest slot for which `self` will accept attestations.
+    pub fn lowest_permissible_slot(&self) -> Slot {
+        self.lowest_permissible_slot
+    }
+
     /// Removes any attestations with a slot lower than `current_slot` and bars any future
-    /// attestations with a slot lower than `current_slot - SLOTS_RETAINED`.
+    /// attestations with a slot lower than `current_slot - slots_retained`.
     pub fn prune(&mut self, current_slot: Slot) {
         let _timer = metrics::start_timer(&metrics::ATTESTATION_PROCESSING_AGG_POOL_PRUNE);
+        let slots_retained = self.slots_retained;
 
         // Taking advantage of saturating subtraction on `Slot`.
-        let lowest_permissible_slot = current_slot - Slot::from(SLOTS_RETAINED);
+        let lowest_permissible_slot = current_slot - Slot::from(slots_retained);
 
         // No need to prune if the lowest permissible slot has not changed and the queue length is
         // less than the maximum
         if self.lowest_permissible_slot == lowest_permissible_slot
-            && self.maps.len() <= SLOTS_RETAINED
+            && self.maps.len() <= slots_retained
         {
             return;
         }
@@ -256,19 +286,19 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
             .retain(|slot, _map| *slot >= lowest_permissible_slot);
 
         // If we have too many maps, remove the lowest amount to ensure we only have
-        // `SLOTS_RETAINED` left.
-        if self.maps.len() > SLOTS_RETAINED {
+        // `slots_retained` left.
+        if self.maps.len() > slots_retained {
             let mut slots = self
                 .maps
                 .iter()
                 .map(|(slot, _map)| *slot)
                 .collect::<Vec<_>>();
-            // Sort is generally pretty slow, however `SLOTS_RETAINED` is quite low so it should be
+            // Sort is generally pretty slow, however `slots_retained` is quite low so it should be
             // negligible.
             slots.sort_unstable();
             slots
                 .into_iter()
-                .take(self.maps.len().saturating_sub(SLOTS_RETAINED))
+                .take(self.maps.len().saturating_sub(slots_retained))
                 .for_each(|slot| {
                     self.maps.remove(&slot);
                 })
@@ -491,4 +521,54 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn custom_capacity() {
+        let slots_retained = 2;
+        let max_attestations_per_slot = 4;
+
+        let mut base = get_attestation(Slot::new(0));
+        sign(&mut base, 0, Hash256::random());
+
+        let mut pool = NaiveAggregationPool::new(slots_retained, max_attestations_per_slot);
+
+        for i in 0..slots_retained * 4 {
+            let slot = Slot::from(i);
+
+            for j in 0..=max_attestations_per_slot {
+                let mut a = base.clone();
+                a.data.slot = slot;
+                a.data.beacon_block_root = Hash256::from_low_u64_be(j as u64);
+
+                if j < max_attestations_per_slot {
+                    assert_eq!(
+                        pool.insert(&a),
+                        Ok(InsertOutcome::NewAttestationData { committee_index: 0 }),
+                        "should accept attestation below limit"
+                    );
+                } else {
+                    assert_eq!(
+                        pool.insert(&a),
+                        Err(Error::ReachedMaxAttestationsPerSlot(
+                            max_attestations_per_slot
+                        )),
+                        "should not accept attestation above the configured limit"
+                    );
+                }
+            }
+
+            let expected_slots = std::cmp::min(i + 1, slots_retained);
+            assert_eq!(
+                pool.maps.len(),
+                expected_slots,
+                "the pool should have {} slots",
+                expected_slots
+            );
+            assert_eq!(
+                pool.num_attestations(),
+                expected_slots * max_attestations_per_slot,
+                "the pool should be bounded by the configured capacity"
+            );
+        }
+    }
 }
@@ -176,9 +176,22 @@ impl<T: Item, E: EthSpec> AutoPruningContainer<T, E> {
         a: &Attestation<E>,
         validator_index: usize,
     ) -> Result<bool, Error> {
-        self.sanitize_request(a, validator_index)?;
+        self.observe_validator_at_epoch(a.data.target.epoch, validator_index)
+    }
 
-        let epoch = a.data.target.epoch;
+    /// Observe that `validator_index` has attested during `epoch`. Returns `Ok(true)` if
+    /// `validator_index` has previously been observed during `epoch`.
+    ///
+    /// ## Errors
+    ///
+    /// - `validator_index` is higher than `VALIDATOR_REGISTRY_LIMIT`.
+    /// - `epoch` is earlier than `self.lowest_permissible_epoch`.
+    pub fn observe_validator_at_epoch(
+        &self,
+        epoch: Epoch,
+        validator_index: usize,
+    ) -> Result<bool, Error> {
+        self.sanitize_request(epoch, validator_index)?;
 
         self.prune(epoch);
 
@@ -219,7 +232,7 @@ impl<T: Item, E: EthSpec> AutoPruningContainer<T, E> {
         a: &Attestation<E>,
         validator_index: usize,
     ) -> Result<bool, Error> {
-        self.sanitize_request(a, validator_index)?;
+        self.sanitize_request(a.data.target.epoch, validator_index)?;
 
         let exists = self
             .items
@@ -230,6 +243,16 @@ impl<T: Item, E: EthSpec> AutoPruningContainer<T, E> {
         Ok(exists)
     }
 
+    /// Returns `true` if `validator_index` has been observed during `epoch`.
+    ///
+    /// Always returns `false` for epochs which have been pruned from `self`.
+    pub fn index_seen_at_epoch(&self, validator_index: usize, epoch: Epoch) -> bool {
+        self.items
+            .read()
+            .get(&epoch)
+            .map_or(false, |item| item.contains(validator_index))
+    }
+
     /// Returns the number of validators that have been observed at the given `epoch`. Returns
     /// `None` if `self` does not have a cache for that epoch.
     pub fn observed_validator_count(&self, epoch: Epoch) -> Option<usize> {
@@ -239,12 +262,11 @@ impl<T: Item, E: EthSpec> AutoPruningContainer<T, E> {
             .map(|item| item.validator_count())
     }
 
-    fn sanitize_request(&self, a: &Attestation<E>, validator_index: usize) -> Result<(), Error> {
+    fn sanitize_request(&self, epoch: Epoch, validator_index: usize) -> Result<(), Error> {
         if validator_index > E::ValidatorRegistryLimit::to_usize() {
             return Err(Error::ValidatorIndexTooHigh(validator_index));
         }
 
-        let epoch = a.data.target.epoch;
         let lowest_permissible_epoch: Epoch = *self.lowest_permissible_epoch.read();
         if epoch < lowest_permissible_epoch {
             return Err(Error::EpochTooLow {
@@ -345,6 +367,36 @@ mod tests {
                     );
                 }
 
+                #[test]
+                fn index_seen_at_epoch() {
+                    let store = $type::default();
+
+                    assert_eq!(
+                        store.observe_validator_at_epoch(Epoch::new(1), 3),
+                        Ok(false)
+                    );
+                    assert_eq!(
+                        store.observe_validator_at_epoch(Epoch::new(2), 4),
+                        Ok(false)
+                    );
+
+                    assert!(store.index_seen_at_epoch(3, Epoch::new(1)));
+                    assert!(store.index_seen_at_epoch(4, Epoch::new(2)));
+                    assert!(!store.index_seen_at_epoch(3, Epoch::new(2)));
+                    assert!(!store.index_seen_at_epoch(4, Epoch::new(1)));
+
+                    // Observing epoch 3 prunes epoch 1.
+                    assert_eq!(
+                        store.observe_validator_at_epoch(Epoch::new(3), 5),
+                        Ok(false)
+                    );
+                    assert!(
+                        !store.index_seen_at_epoch(3, Epoch::new(1)),
+                        "pruned epochs should not be seen"
+                    );
+                    assert!(store.index_seen_at_epoch(4, Epoch::new(2)));
+                }
+
                 #[test]
                 fn mulitple_contiguous_epochs() {
                     let store = $type::default();
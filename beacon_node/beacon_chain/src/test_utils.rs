@@ -7,10 +7,11 @@ use crate::slog::Drain;
 use crate::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::CachingEth1Backend,
-    events::NullEventHandler,
+    events::{EventHandler, EventKind, NullEventHandler},
     BeaconChain, BeaconChainTypes, StateSkipConfig,
 };
 use genesis::interop_genesis_state;
+use parking_lot::Mutex;
 use rand::rngs::StdRng;
 use rand::Rng;
 use rand_core::SeedableRng;
@@ -38,18 +39,26 @@ pub const HARNESS_GENESIS_TIME: u64 = 1_567_552_690;
 // This parameter is required by a builder but not used because we use the `TestingSlotClock`.
 pub const HARNESS_SLOT_TIME: Duration = Duration::from_secs(1);
 
-pub type BaseHarnessType<TStoreMigrator, TEthSpec, THotStore, TColdStore> = Witness<
+pub type BaseHarnessType<
+    TStoreMigrator,
+    TEthSpec,
+    THotStore,
+    TColdStore,
+    TEventHandler = NullEventHandler<TEthSpec>,
+> = Witness<
     TStoreMigrator,
     TestingSlotClock,
     CachingEth1Backend<TEthSpec>,
     TEthSpec,
-    NullEventHandler<TEthSpec>,
+    TEventHandler,
     THotStore,
     TColdStore,
 >;
 
 pub type NullMigratorEphemeralHarnessType<E> =
     BaseHarnessType<NullMigrator, E, MemoryStore<E>, MemoryStore<E>>;
+pub type RecordingEventsEphemeralHarnessType<E> =
+    BaseHarnessType<NullMigrator, E, MemoryStore<E>, MemoryStore<E>, RecordingEventHandler<E>>;
 pub type BlockingMigratorDiskHarnessType<E> =
     BaseHarnessType<BlockingMigrator<E, LevelDB<E>, LevelDB<E>>, E, LevelDB<E>, LevelDB<E>>;
 pub type BlockingMigratorEphemeralHarnessType<E> = BaseHarnessType<
@@ -66,6 +75,33 @@ pub type AddBlocksResult<E> = (
     BeaconState<E>,
 );
 
+/// An `EventHandler` which keeps every event registered with it, for inspection by tests.
+pub struct RecordingEventHandler<E: EthSpec> {
+    events: Mutex<Vec<EventKind<E>>>,
+}
+
+impl<E: EthSpec> RecordingEventHandler<E> {
+    /// Returns all events registered so far, in the order they were registered.
+    pub fn events(&self) -> Vec<EventKind<E>> {
+        self.events.lock().clone()
+    }
+}
+
+impl<E: EthSpec> Default for RecordingEventHandler<E> {
+    fn default() -> Self {
+        Self {
+            events: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl<E: EthSpec> EventHandler<E> for RecordingEventHandler<E> {
+    fn register(&self, kind: EventKind<E>) -> Result<(), String> {
+        self.events.lock().push(kind);
+        Ok(())
+    }
+}
+
 /// Deprecated: Indicates how the `BeaconChainHarness` should produce blocks.
 #[derive(Clone, Copy, Debug)]
 pub enum BlockStrategy {
@@ -185,6 +221,38 @@ impl<E: EthSpec> BeaconChainHarness<NullMigratorEphemeralHarnessType<E>> {
         validators_keypairs: Vec<Keypair>,
         target_aggregators_per_committee: u64,
         config: StoreConfig,
+    ) -> Self {
+        Self::new_ephemeral(
+            eth_spec_instance,
+            validators_keypairs,
+            target_aggregators_per_committee,
+            config,
+        )
+    }
+}
+
+impl<E: EthSpec> BeaconChainHarness<RecordingEventsEphemeralHarnessType<E>> {
+    /// As per `new_with_store_config`, but keeps every event registered with the chain for
+    /// inspection via `RecordingEventHandler::events`.
+    pub fn new_recording_events(
+        eth_spec_instance: E,
+        validators_keypairs: Vec<Keypair>,
+        config: StoreConfig,
+    ) -> Self {
+        Self::new_ephemeral(eth_spec_instance, validators_keypairs, 1 << 32, config)
+    }
+}
+
+impl<E, EH> BeaconChainHarness<BaseHarnessType<NullMigrator, E, MemoryStore<E>, MemoryStore<E>, EH>>
+where
+    E: EthSpec,
+    EH: EventHandler<E> + Default,
+{
+    fn new_ephemeral(
+        eth_spec_instance: E,
+        validators_keypairs: Vec<Keypair>,
+        target_aggregators_per_committee: u64,
+        config: StoreConfig,
     ) -> Self {
         let data_dir = tempdir().expect("should create temporary data_dir");
         let mut spec = E::default_spec();
@@ -210,7 +278,7 @@ impl<E: EthSpec> BeaconChainHarness<NullMigratorEphemeralHarnessType<E>> {
             .expect("should build state using recent genesis")
             .dummy_eth1_backend()
             .expect("should build dummy backend")
-            .null_event_handler()
+            .event_handler(EH::default())
             .testing_slot_clock(HARNESS_SLOT_TIME)
             .expect("should configure testing slot clock")
             .build()
@@ -313,12 +381,13 @@ impl<E: EthSpec> BeaconChainHarness<BlockingMigratorDiskHarnessType<E>> {
     }
 }
 
-impl<M, E, Hot, Cold> BeaconChainHarness<BaseHarnessType<M, E, Hot, Cold>>
+impl<M, E, Hot, Cold, EH> BeaconChainHarness<BaseHarnessType<M, E, Hot, Cold, EH>>
 where
     M: Migrate<E, Hot, Cold>,
     E: EthSpec,
     Hot: ItemStore<E>,
     Cold: ItemStore<E>,
+    EH: EventHandler<E>,
 {
     pub fn get_all_validators(&self) -> Vec<usize> {
         (0..self.validators_keypairs.len()).collect()
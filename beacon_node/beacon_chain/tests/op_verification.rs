@@ -117,6 +117,73 @@ fn voluntary_exit() {
     ));
 }
 
+#[test]
+fn voluntary_exit_pruned_after_inclusion() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let mut harness = get_harness(store.clone(), VALIDATOR_COUNT);
+    let spec = &harness.chain.spec.clone();
+
+    harness.extend_chain(
+        (E::slots_per_epoch() * (spec.shard_committee_period + 1)) as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head_info = harness.chain.head_info().unwrap();
+
+    let validator_index = VALIDATOR_COUNT - 1;
+    let exit = TestingVoluntaryExitBuilder::new(
+        Epoch::new(spec.shard_committee_period),
+        validator_index as u64,
+    )
+    .build(
+        &KEYPAIRS[validator_index].sk,
+        &head_info.fork,
+        head_info.genesis_validators_root,
+        spec,
+    );
+
+    let verified_exit = match harness
+        .chain
+        .verify_voluntary_exit_for_gossip(exit.clone())
+        .unwrap()
+    {
+        ObservationOutcome::New(verified_exit) => verified_exit,
+        ObservationOutcome::AlreadyKnown => panic!("exit should be new"),
+    };
+    harness.chain.import_voluntary_exit(verified_exit);
+
+    assert_eq!(
+        harness.chain.op_pool.get_all_voluntary_exits(),
+        vec![exit],
+        "the exit should be pooled"
+    );
+
+    // The next block should include the exit.
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head = harness.chain.head().unwrap();
+    assert_eq!(
+        head.beacon_block.message.body.voluntary_exits.len(),
+        1,
+        "the exit should be included in the head block"
+    );
+    assert_ne!(
+        head.beacon_state.validators[validator_index].exit_epoch, spec.far_future_epoch,
+        "the validator should have initiated an exit"
+    );
+
+    assert!(
+        harness.chain.op_pool.get_all_voluntary_exits().is_empty(),
+        "the exit should have been pruned"
+    );
+}
+
 #[test]
 fn proposer_slashing() {
     let db_path = tempdir().unwrap();
@@ -5,6 +5,7 @@ extern crate lazy_static;
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    events::EventKind,
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, NullMigratorEphemeralHarnessType,
         OP_POOL_DB_KEY,
@@ -14,6 +15,7 @@ use operation_pool::PersistedOperationPool;
 use state_processing::{
     per_slot_processing, per_slot_processing::Error as SlotProcessingError, EpochProcessingError,
 };
+use std::sync::Arc;
 use store::config::StoreConfig;
 use types::{BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot};
 
@@ -140,6 +142,74 @@ fn iterators() {
     );
 }
 
+#[test]
+fn head_snapshot_is_replaced_on_head_change() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    let old_snapshot = harness
+        .chain
+        .head_snapshot()
+        .expect("should get head snapshot");
+    assert!(
+        Arc::ptr_eq(
+            &old_snapshot,
+            &harness
+                .chain
+                .head_snapshot()
+                .expect("should get head snapshot")
+        ),
+        "reads should share a snapshot whilst the head is unchanged"
+    );
+    assert_eq!(
+        *old_snapshot,
+        harness.chain.head().expect("should get head"),
+        "the snapshot should match the head"
+    );
+
+    // Hold the old snapshot on another thread whilst the head changes.
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let reader = {
+        let snapshot = old_snapshot.clone();
+        std::thread::spawn(move || {
+            ready_rx.recv().expect("should be notified of head change");
+            (
+                snapshot.beacon_block_root,
+                snapshot.beacon_block.state_root() == snapshot.beacon_state_root
+                    && snapshot.beacon_block.slot() == snapshot.beacon_state.slot,
+            )
+        })
+    };
+
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    ready_tx.send(()).expect("should notify reader");
+
+    let new_snapshot = harness
+        .chain
+        .head_snapshot()
+        .expect("should get head snapshot");
+    let head_info = harness.chain.head_info().expect("should get head info");
+    assert_eq!(
+        new_snapshot.beacon_block_root, head_info.block_root,
+        "the snapshot should be replaced on head change"
+    );
+    assert_eq!(new_snapshot.beacon_state_root, head_info.state_root);
+    assert_ne!(
+        new_snapshot.beacon_block_root, old_snapshot.beacon_block_root,
+        "the head should have changed"
+    );
+
+    let (root_seen_by_reader, consistent) = reader.join().expect("reader should not panic");
+    assert_eq!(
+        root_seen_by_reader, old_snapshot.beacon_block_root,
+        "the reader should not observe the head change"
+    );
+    assert!(consistent, "the reader should see a consistent snapshot");
+}
+
 #[test]
 fn chooses_fork() {
     let mut harness = get_harness(VALIDATOR_COUNT);
@@ -189,6 +259,73 @@ fn chooses_fork() {
     );
 }
 
+#[test]
+fn reorg_emits_event() {
+    let mut harness = BeaconChainHarness::new_recording_events(
+        MinimalEthSpec,
+        KEYPAIRS[0..VALIDATOR_COUNT].to_vec(),
+        StoreConfig::default(),
+    );
+    harness.advance_slot();
+
+    let one_third = VALIDATOR_COUNT / 3;
+    let delay = MinimalEthSpec::default_spec().min_attestation_inclusion_delay as usize;
+
+    let minority_validators: Vec<usize> = (0..one_third).collect();
+    let majority_validators: Vec<usize> = (one_third..VALIDATOR_COUNT).collect();
+
+    let initial_blocks = delay + 1;
+    let minority_fork_blocks = delay + 1;
+    let majority_fork_blocks = delay + 1;
+
+    harness.extend_chain(
+        initial_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // The minority fork is built first and becomes the head, until the majority fork is built
+    // upon the last block on which all validators agreed.
+    let (minority_head, majority_head) = harness.generate_two_forks_by_skipping_a_block(
+        &minority_validators,
+        &majority_validators,
+        minority_fork_blocks,
+        majority_fork_blocks,
+    );
+
+    assert_eq!(
+        harness
+            .chain
+            .head()
+            .expect("should get head")
+            .beacon_block_root,
+        majority_head,
+        "the majority chain should be the canonical chain"
+    );
+
+    let reorgs = harness
+        .chain
+        .event_handler
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            EventKind::BeaconChainReorg {
+                depth,
+                old_head_block_root,
+                ..
+            } => Some((depth, old_head_block_root)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // The minority fork was built from the slot after the common ancestor.
+    assert_eq!(
+        reorgs,
+        vec![(minority_fork_blocks as u64, minority_head)],
+        "should emit a single re-org from the minority head"
+    );
+}
+
 #[test]
 fn finalizes_with_full_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
@@ -485,6 +622,69 @@ fn attestations_with_increasing_slots() {
     }
 }
 
+#[test]
+fn naive_aggregation_pool_is_bounded() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+    let slots_retained = harness.chain.config.naive_aggregation_pool_slots_retained;
+
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let earliest_attestation = harness
+        .chain
+        .naive_aggregation_pool
+        .read()
+        .iter()
+        .next()
+        .cloned()
+        .expect("should have pooled an attestation");
+
+    let mut max_pool_size = 0;
+    for _ in 0..MinimalEthSpec::slots_per_epoch() * 3 {
+        harness.extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+
+        let pool = harness.chain.naive_aggregation_pool.read();
+        let head_slot = harness.chain.head_info().expect("should get head").slot;
+
+        assert!(
+            pool.iter()
+                .all(|a| a.data.slot + slots_retained as u64 >= head_slot),
+            "the pool should only contain attestations from recent slots"
+        );
+
+        let pool_size = pool.num_attestations();
+        if pool_size > max_pool_size {
+            max_pool_size = pool_size;
+        }
+    }
+
+    // Each slot has a single committee with these parameters, so there can be at most one
+    // distinct `AttestationData` per retained slot.
+    assert!(
+        max_pool_size <= slots_retained,
+        "the pool size should stay bounded"
+    );
+
+    let pool = harness.chain.naive_aggregation_pool.read();
+    assert!(
+        pool.get(&earliest_attestation.data)
+            .expect("should not error while getting attestation")
+            .is_none(),
+        "the earliest attestation should have been evicted"
+    );
+    assert!(
+        pool.lowest_permissible_slot() > earliest_attestation.data.slot,
+        "the pool should refuse attestations from the evicted slot"
+    );
+}
+
 #[test]
 fn unaggregated_attestations_added_to_fork_choice_all_updated() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 2 - 1;
@@ -1,7 +1,7 @@
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
-use beacon_chain::events::TeeEventHandler;
+use beacon_chain::events::{EventBus, TeeEventHandler};
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::{CachingEth1Backend, Eth1Chain},
@@ -10,14 +10,13 @@ use beacon_chain::{
     store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, EventHandler,
 };
-use bus::Bus;
 use environment::RuntimeContext;
 use eth1::{Config as Eth1Config, Service as Eth1Service};
 use eth2_config::Eth2Config;
 use eth2_libp2p::NetworkGlobals;
 use genesis::{interop_genesis_state, Eth1GenesisService};
 use network::{NetworkConfig, NetworkMessage, NetworkService};
-use parking_lot::Mutex;
+use rest_api::NetworkMessageBuffer;
 use slog::info;
 use ssz::Decode;
 use std::net::SocketAddr;
@@ -26,10 +25,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use timer::spawn_timer;
 use tokio::sync::mpsc::UnboundedSender;
-use types::{
-    test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec,
-    SignedBeaconBlockHash,
-};
+use types::{test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec};
 use websocket_server::{Config as WebSocketConfig, WebSocketSender};
 
 /// Interval between polling the eth1 node for genesis information.
@@ -62,6 +58,8 @@ pub struct ClientBuilder<T: BeaconChainTypes> {
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     network_send: Option<UnboundedSender<NetworkMessage<T::EthSpec>>>,
     http_listen_addr: Option<SocketAddr>,
+    http_network_messages: Option<NetworkMessageBuffer<T::EthSpec>>,
+    http_api_context: Option<Arc<rest_api::Context<T>>>,
     websocket_listen_addr: Option<SocketAddr>,
     eth_spec_instance: T::EthSpec,
 }
@@ -104,6 +102,8 @@ where
             network_globals: None,
             network_send: None,
             http_listen_addr: None,
+            http_network_messages: None,
+            http_api_context: None,
             websocket_listen_addr: None,
             eth_spec_instance,
         }
@@ -285,7 +285,7 @@ where
         mut self,
         client_config: &ClientConfig,
         eth2_config: &Eth2Config,
-        events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+        events: Arc<EventBus<TEthSpec>>,
     ) -> Result<Self, String> {
         let beacon_chain = self
             .beacon_chain
@@ -305,12 +305,19 @@ where
             .clone()
             .ok_or_else(|| "http_server requires a libp2p network sender")?;
 
+        let network_message_buffer = if client_config.rest_api.record_network_messages {
+            Some(NetworkMessageBuffer::default())
+        } else {
+            None
+        };
+
         let network_info = rest_api::NetworkInfo {
             network_globals,
             network_chan: network_send,
+            network_message_buffer: network_message_buffer.clone(),
         };
 
-        let listening_addr = rest_api::start_server(
+        let (listening_addr, http_api_context) = rest_api::start_server(
             context.executor,
             &client_config.rest_api,
             beacon_chain,
@@ -324,9 +331,11 @@ where
             eth2_config.clone(),
             events,
         )
-        .map_err(|e| format!("Failed to start HTTP API: {:?}", e))?;
+        .map_err(|e| format!("Failed to start HTTP API: {}", e))?;
 
         self.http_listen_addr = Some(listening_addr);
+        self.http_network_messages = network_message_buffer;
+        self.http_api_context = Some(http_api_context);
 
         Ok(self)
     }
@@ -384,6 +393,8 @@ where
             beacon_chain: self.beacon_chain,
             network_globals: self.network_globals,
             http_listen_addr: self.http_listen_addr,
+            http_network_messages: self.http_network_messages,
+            http_api_context: self.http_api_context,
             websocket_listen_addr: self.websocket_listen_addr,
         }
     }
@@ -456,12 +467,11 @@ where
     THotStore: ItemStore<TEthSpec> + 'static,
     TColdStore: ItemStore<TEthSpec> + 'static,
 {
-    #[allow(clippy::type_complexity)]
     /// Specifies that the `BeaconChain` should publish events using the WebSocket server.
     pub fn tee_event_handler(
         mut self,
         config: WebSocketConfig,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
+    ) -> Result<(Self, Arc<EventBus<TEthSpec>>), String> {
         let context = self
             .runtime_context
             .as_ref()
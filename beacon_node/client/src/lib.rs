@@ -9,6 +9,7 @@ pub mod error;
 
 use beacon_chain::BeaconChain;
 use eth2_libp2p::{Enr, Multiaddr, NetworkGlobals};
+use rest_api::NetworkMessageBuffer;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
@@ -24,6 +25,8 @@ pub struct Client<T: BeaconChainTypes> {
     beacon_chain: Option<Arc<BeaconChain<T>>>,
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     http_listen_addr: Option<SocketAddr>,
+    http_network_messages: Option<NetworkMessageBuffer<T::EthSpec>>,
+    http_api_context: Option<Arc<rest_api::Context<T>>>,
     websocket_listen_addr: Option<SocketAddr>,
 }
 
@@ -33,11 +36,28 @@ impl<T: BeaconChainTypes> Client<T> {
         self.beacon_chain.clone()
     }
 
+    /// Returns an `Arc` reference to the client's network globals, if the network was started.
+    pub fn network_globals(&self) -> Option<Arc<NetworkGlobals<T::EthSpec>>> {
+        self.network_globals.clone()
+    }
+
     /// Returns the address of the client's HTTP API server, if it was started.
     pub fn http_listen_addr(&self) -> Option<SocketAddr> {
         self.http_listen_addr
     }
 
+    /// Returns the messages published to the network by the client's HTTP API server, if it was
+    /// started with `record_network_messages` enabled.
+    pub fn http_network_messages(&self) -> Option<NetworkMessageBuffer<T::EthSpec>> {
+        self.http_network_messages.clone()
+    }
+
+    /// Returns the shared state of the client's HTTP API server (e.g., its caches), if it was
+    /// started.
+    pub fn http_api_context(&self) -> Option<Arc<rest_api::Context<T>>> {
+        self.http_api_context.clone()
+    }
+
     /// Returns the address of the client's WebSocket API server, if it was started.
     pub fn websocket_listen_addr(&self) -> Option<SocketAddr> {
         self.websocket_listen_addr
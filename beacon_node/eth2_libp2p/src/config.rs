@@ -66,6 +66,14 @@ pub struct Config {
 
     /// List of extra topics to initially subscribe to as strings.
     pub topics: Vec<GossipKind>,
+
+    /// The number of whole slots before an attestation duty at which to subscribe to its subnet,
+    /// in addition to the fraction of a slot that is always allowed.
+    pub subnet_subscription_lead_slots: u64,
+
+    /// The number of slots after an attestation duty during which to remain subscribed to its
+    /// subnet.
+    pub subnet_subscription_linger_slots: u64,
 }
 
 impl Default for Config {
@@ -146,6 +154,8 @@ impl Default for Config {
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             topics,
+            subnet_subscription_lead_slots: 0,
+            subnet_subscription_linger_slots: 0,
         }
     }
 }
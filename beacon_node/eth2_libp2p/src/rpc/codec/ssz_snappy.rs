@@ -3,7 +3,8 @@ use crate::rpc::{
     codec::base::OutboundCodec,
     protocol::{
         Encoding, Protocol, ProtocolId, RPCError, Version, BLOCKS_BY_ROOT_REQUEST_MAX,
-        BLOCKS_BY_ROOT_REQUEST_MIN, SIGNED_BEACON_BLOCK_MAX, SIGNED_BEACON_BLOCK_MIN,
+        BLOCKS_BY_ROOT_REQUEST_MIN, ERROR_TYPE_MAX, SIGNED_BEACON_BLOCK_MAX,
+        SIGNED_BEACON_BLOCK_MIN,
     },
 };
 use crate::rpc::{RPCCodedResponse, RPCRequest, RPCResponse};
@@ -109,17 +110,24 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyInboundCodec<TSpec> {
 
         let length = self.len.expect("length should be Some");
 
-        // Should not attempt to decode rpc chunks with length > max_packet_size
-        if length > self.max_packet_size {
+        // Should not attempt to decode rpc chunks with length > max_packet_size, nor requests
+        // larger than the protocol allows. This is checked before allocating the buffer or
+        // decompressing any bytes.
+        if length > self.max_packet_size || length > self.protocol.max_request_size() {
             return Err(RPCError::InvalidData);
         }
-        let mut reader = FrameDecoder::new(Cursor::new(&src));
+        // Read at most the worst case compressed length of `length` bytes, so that a peer can't
+        // have us buffer an unbounded stream (e.g. of snappy padding chunks) which never
+        // decompresses to `length` bytes. The decompressed output is bounded by `decoded_buffer`.
+        let max_compressed_len = snap::raw::max_compress_len(length) as u64;
+        let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
+        let mut reader = FrameDecoder::new(limit_reader);
         let mut decoded_buffer = vec![0; length];
 
         match reader.read_exact(&mut decoded_buffer) {
             Ok(()) => {
                 // `n` is how many bytes the reader read in the compressed stream
-                let n = reader.get_ref().position();
+                let n = reader.get_ref().get_ref().position();
                 self.len = None;
                 let _read_bytes = src.split_to(n as usize);
                 match self.protocol.message_name {
@@ -193,12 +201,7 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyInboundCodec<TSpec> {
                     },
                 }
             }
-            Err(e) => match e.kind() {
-                // Haven't received enough bytes to decode yet
-                // TODO: check if this is the only Error variant where we return `Ok(None)`
-                ErrorKind::UnexpectedEof => Ok(None),
-                _ => Err(e).map_err(RPCError::from),
-            },
+            Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
         }
     }
 }
@@ -287,16 +290,23 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyOutboundCodec<TSpec> {
 
         let length = self.len.expect("length should be Some");
 
-        // Should not attempt to decode rpc chunks with length > max_packet_size
-        if length > self.max_packet_size {
+        // Should not attempt to decode rpc chunks with length > max_packet_size, nor responses
+        // larger than the protocol allows. This is checked before allocating the buffer or
+        // decompressing any bytes.
+        if length > self.max_packet_size || length > self.protocol.max_response_size::<TSpec>() {
             return Err(RPCError::InvalidData);
         }
-        let mut reader = FrameDecoder::new(Cursor::new(&src));
+        // Read at most the worst case compressed length of `length` bytes, so that a peer can't
+        // have us buffer an unbounded stream (e.g. of snappy padding chunks) which never
+        // decompresses to `length` bytes. The decompressed output is bounded by `decoded_buffer`.
+        let max_compressed_len = snap::raw::max_compress_len(length) as u64;
+        let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
+        let mut reader = FrameDecoder::new(limit_reader);
         let mut decoded_buffer = vec![0; length];
         match reader.read_exact(&mut decoded_buffer) {
             Ok(()) => {
                 // `n` is how many bytes the reader read in the compressed stream
-                let n = reader.get_ref().position();
+                let n = reader.get_ref().get_ref().position();
                 self.len = None;
                 let _read_byts = src.split_to(n as usize);
                 match self.protocol.message_name {
@@ -363,12 +373,7 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyOutboundCodec<TSpec> {
                     },
                 }
             }
-            Err(e) => match e.kind() {
-                // Haven't received enough bytes to decode yet
-                // TODO: check if this is the only Error variant where we return `Ok(None)`
-                ErrorKind::UnexpectedEof => Ok(None),
-                _ => Err(e).map_err(RPCError::from),
-            },
+            Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
         }
     }
 }
@@ -392,28 +397,223 @@ impl<TSpec: EthSpec> OutboundCodec<RPCRequest<TSpec>> for SSZSnappyOutboundCodec
 
         let length = self.len.expect("length should be Some");
 
-        // Should not attempt to decode rpc chunks with length > max_packet_size
-        if length > self.max_packet_size {
+        // Should not attempt to decode rpc chunks with length > max_packet_size, nor error
+        // messages longer than the maximum error length.
+        if length > self.max_packet_size || length > *ERROR_TYPE_MAX {
             return Err(RPCError::InvalidData);
         }
-        let mut reader = FrameDecoder::new(Cursor::new(&src));
+        // Read at most the worst case compressed length of `length` bytes, so that a peer can't
+        // have us buffer an unbounded stream (e.g. of snappy padding chunks) which never
+        // decompresses to `length` bytes. The decompressed output is bounded by `decoded_buffer`.
+        let max_compressed_len = snap::raw::max_compress_len(length) as u64;
+        let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
+        let mut reader = FrameDecoder::new(limit_reader);
         let mut decoded_buffer = vec![0; length];
         match reader.read_exact(&mut decoded_buffer) {
             Ok(()) => {
                 // `n` is how many bytes the reader read in the compressed stream
-                let n = reader.get_ref().position();
+                let n = reader.get_ref().get_ref().position();
                 self.len = None;
                 let _read_bytes = src.split_to(n as usize);
                 Ok(Some(ErrorType(VariableList::from_ssz_bytes(
                     &decoded_buffer,
                 )?)))
             }
-            Err(e) => match e.kind() {
+            Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
+        }
+    }
+}
+
+/// Handles an error from reading the decompressed bytes of a chunk, having read `num_bytes` of at
+/// most `max_compressed_len` compressed bytes.
+fn handle_error<T>(
+    err: std::io::Error,
+    num_bytes: u64,
+    max_compressed_len: u64,
+) -> Result<Option<T>, RPCError> {
+    match err.kind() {
+        ErrorKind::UnexpectedEof => {
+            // Having read the maximum compressed length without filling the buffer, the chunk
+            // can't be valid.
+            if num_bytes >= max_compressed_len {
+                Err(RPCError::InvalidData)
+            } else {
                 // Haven't received enough bytes to decode yet
-                // TODO: check if this is the only Error variant where we return `Ok(None)`
-                ErrorKind::UnexpectedEof => Ok(None),
-                _ => Err(e).map_err(RPCError::from),
-            },
+                Ok(None)
+            }
+        }
+        _ => Err(err).map_err(RPCError::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Epoch, Hash256, Slot};
+
+    type Spec = types::MainnetEthSpec;
+
+    /// Returns a buffer containing only the length prefix `length`. Decoding it can only fail
+    /// before decompression, since there are no compressed bytes to read.
+    fn length_prefix(length: usize) -> BytesMut {
+        let mut buf = BytesMut::new();
+        Uvi::<usize>::default()
+            .encode(length, &mut buf)
+            .expect("should encode length prefix");
+        buf
+    }
+
+    fn protocol_id(protocol: Protocol) -> ProtocolId {
+        ProtocolId::new(protocol, Version::V1, Encoding::SSZSnappy)
+    }
+
+    #[test]
+    fn inbound_rejects_requests_above_protocol_max() {
+        for protocol in &[
+            Protocol::Status,
+            Protocol::Goodbye,
+            Protocol::BlocksByRange,
+            Protocol::BlocksByRoot,
+            Protocol::Ping,
+        ] {
+            let protocol = protocol_id(*protocol);
+            let max = protocol.max_request_size();
+
+            let mut codec = SSZSnappyInboundCodec::<Spec>::new(protocol.clone(), 1_048_576);
+            assert!(
+                matches!(codec.decode(&mut length_prefix(max)), Ok(None)),
+                "{:?} should wait for the bytes of a request of the max size",
+                protocol
+            );
+
+            let mut codec = SSZSnappyInboundCodec::<Spec>::new(protocol.clone(), 1_048_576);
+            assert!(
+                matches!(
+                    codec.decode(&mut length_prefix(max + 1)),
+                    Err(RPCError::InvalidData)
+                ),
+                "{:?} should reject a request above the max size",
+                protocol
+            );
+        }
+    }
+
+    #[test]
+    fn outbound_rejects_responses_above_protocol_max() {
+        for protocol in &[
+            Protocol::Status,
+            Protocol::BlocksByRange,
+            Protocol::BlocksByRoot,
+            Protocol::Ping,
+            Protocol::MetaData,
+        ] {
+            let protocol = protocol_id(*protocol);
+            let max = protocol.max_response_size::<Spec>();
+
+            let mut codec = SSZSnappyOutboundCodec::<Spec>::new(protocol.clone(), 1_048_576);
+            assert!(
+                matches!(codec.decode(&mut length_prefix(max)), Ok(None)),
+                "{:?} should wait for the bytes of a response of the max size",
+                protocol
+            );
+
+            let mut codec = SSZSnappyOutboundCodec::<Spec>::new(protocol.clone(), 1_048_576);
+            assert!(
+                matches!(
+                    codec.decode(&mut length_prefix(max + 1)),
+                    Err(RPCError::InvalidData)
+                ),
+                "{:?} should reject a response above the max size",
+                protocol
+            );
+        }
+    }
+
+    #[test]
+    fn outbound_rejects_error_messages_above_max() {
+        let mut codec =
+            SSZSnappyOutboundCodec::<Spec>::new(protocol_id(Protocol::Status), 1_048_576);
+        assert!(matches!(
+            codec.decode_error(&mut length_prefix(*ERROR_TYPE_MAX)),
+            Ok(None)
+        ));
+
+        let mut codec =
+            SSZSnappyOutboundCodec::<Spec>::new(protocol_id(Protocol::Status), 1_048_576);
+        assert!(matches!(
+            codec.decode_error(&mut length_prefix(*ERROR_TYPE_MAX + 1)),
+            Err(RPCError::InvalidData)
+        ));
+    }
+
+    /// Returns the snappy stream identifier followed by a padding chunk of `padding` bytes, which
+    /// decompresses to nothing.
+    fn snappy_padding(padding: usize) -> Vec<u8> {
+        let mut bytes = vec![0xff, 0x06, 0x00, 0x00, b's', b'N', b'a', b'P', b'p', b'Y'];
+        bytes.push(0xfe);
+        bytes.extend_from_slice(&(padding as u32).to_le_bytes()[0..3]);
+        bytes.extend(std::iter::repeat(0).take(padding));
+        bytes
+    }
+
+    #[test]
+    fn rejects_compressed_bytes_above_max_compressed_len() {
+        let protocol = protocol_id(Protocol::Status);
+        let length = protocol.max_request_size();
+        let max_compressed_len = snap::raw::max_compress_len(length);
+
+        // Padding within the maximum compressed length may yet be followed by the data.
+        let mut buf = length_prefix(length);
+        buf.extend_from_slice(&snappy_padding(max_compressed_len / 2));
+        let mut codec = SSZSnappyInboundCodec::<Spec>::new(protocol.clone(), 1_048_576);
+        assert!(matches!(codec.decode(&mut buf), Ok(None)));
+
+        // Beyond it, the data can't be valid and we should stop waiting for more bytes.
+        let mut buf = length_prefix(length);
+        buf.extend_from_slice(&snappy_padding(max_compressed_len));
+        let mut codec = SSZSnappyInboundCodec::<Spec>::new(protocol.clone(), 1_048_576);
+        assert!(matches!(codec.decode(&mut buf), Err(RPCError::InvalidData)));
+
+        let mut buf = length_prefix(length);
+        buf.extend_from_slice(&snappy_padding(max_compressed_len));
+        let mut codec = SSZSnappyOutboundCodec::<Spec>::new(protocol, 1_048_576);
+        assert!(matches!(codec.decode(&mut buf), Err(RPCError::InvalidData)));
+    }
+
+    #[test]
+    fn decodes_status_within_max_compressed_len() {
+        let protocol = protocol_id(Protocol::Status);
+        let status = StatusMessage {
+            fork_digest: [1; 4],
+            finalized_root: Hash256::repeat_byte(2),
+            finalized_epoch: Epoch::new(3),
+            head_root: Hash256::repeat_byte(4),
+            head_slot: Slot::new(5),
+        };
+
+        let mut buf = BytesMut::new();
+        SSZSnappyOutboundCodec::<Spec>::new(protocol.clone(), 1_048_576)
+            .encode(RPCRequest::Status(status.clone()), &mut buf)
+            .expect("should encode status");
+
+        let mut codec = SSZSnappyInboundCodec::<Spec>::new(protocol, 1_048_576);
+        assert_eq!(
+            codec.decode(&mut buf).expect("should decode status"),
+            Some(RPCRequest::Status(status))
+        );
+    }
+
+    #[test]
+    fn small_protocols_have_small_limits() {
+        for protocol in &[
+            Protocol::Status,
+            Protocol::Goodbye,
+            Protocol::Ping,
+            Protocol::MetaData,
+        ] {
+            let protocol = protocol_id(*protocol);
+            assert!(protocol.max_request_size() <= 1_024);
+            assert!(protocol.max_response_size::<Spec>() <= 1_024);
         }
     }
 }
@@ -51,6 +51,10 @@ lazy_static! {
         ])
     .as_ssz_bytes()
     .len();
+    pub static ref ERROR_TYPE_MAX: usize =
+        VariableList::<u8, MaxErrorLen>::from(vec![0_u8; MAX_ERROR_LEN as usize])
+    .as_ssz_bytes()
+    .len();
 }
 
 /// The maximum bytes that can be sent across the RPC.
@@ -178,6 +182,36 @@ impl ProtocolId {
             protocol_id,
         }
     }
+
+    /// Returns the maximum length of the ssz-encoded request on this protocol.
+    ///
+    /// A length prefix above this limit is invalid, so the request can be rejected before any
+    /// bytes are decompressed.
+    pub fn max_request_size(&self) -> usize {
+        let max = match self.message_name {
+            Protocol::Status => <StatusMessage as Encode>::ssz_fixed_len(),
+            Protocol::Goodbye => <GoodbyeReason as Encode>::ssz_fixed_len(),
+            Protocol::BlocksByRange => <BlocksByRangeRequest as Encode>::ssz_fixed_len(),
+            Protocol::BlocksByRoot => *BLOCKS_BY_ROOT_REQUEST_MAX,
+            Protocol::Ping => <Ping as Encode>::ssz_fixed_len(),
+            Protocol::MetaData => 0,
+        };
+        std::cmp::min(max, MAX_RPC_SIZE)
+    }
+
+    /// Returns the maximum length of a ssz-encoded successful response chunk on this protocol.
+    ///
+    /// Error response chunks are bounded by `ERROR_TYPE_MAX` instead.
+    pub fn max_response_size<T: EthSpec>(&self) -> usize {
+        let max = match self.message_name {
+            Protocol::Status => <StatusMessage as Encode>::ssz_fixed_len(),
+            Protocol::Goodbye => 0,
+            Protocol::BlocksByRange | Protocol::BlocksByRoot => *SIGNED_BEACON_BLOCK_MAX,
+            Protocol::Ping => <Ping as Encode>::ssz_fixed_len(),
+            Protocol::MetaData => <MetaData<T> as Encode>::ssz_fixed_len(),
+        };
+        std::cmp::min(max, MAX_RPC_SIZE)
+    }
 }
 
 impl ProtocolName for ProtocolId {
@@ -484,3 +518,21 @@ impl<TSpec: EthSpec> std::fmt::Display for RPCRequest<TSpec> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GOSSIP_MAX_SIZE;
+    use types::{MinimalEthSpec, SignedAggregateAndProof};
+
+    #[test]
+    fn signed_aggregate_and_proof_max_size_is_within_limits() {
+        for max_len in &[
+            SignedAggregateAndProof::<MainnetEthSpec>::max_ssz_len(),
+            SignedAggregateAndProof::<MinimalEthSpec>::max_ssz_len(),
+        ] {
+            assert!(*max_len <= MAX_RPC_SIZE, "{} > MAX_RPC_SIZE", max_len);
+            assert!(*max_len <= GOSSIP_MAX_SIZE, "{} > GOSSIP_MAX_SIZE", max_len);
+        }
+    }
+}
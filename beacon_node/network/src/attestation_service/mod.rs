@@ -3,6 +3,7 @@
 //! determines whether attestations should be aggregated and/or passed to the beacon node.
 
 use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -13,7 +14,7 @@ use rand::seq::SliceRandom;
 use slog::{crit, debug, error, o, trace, warn};
 
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2_libp2p::{types::GossipKind, NetworkGlobals, SubnetDiscovery};
+use eth2_libp2p::{types::GossipKind, NetworkConfig, NetworkGlobals, SubnetDiscovery};
 use hashset_delay::HashSetDelay;
 use rest_types::ValidatorSubscription;
 use slot_clock::SlotClock;
@@ -84,6 +85,12 @@ pub struct AttestationService<T: BeaconChainTypes> {
     /// A collection timeouts to track the existence of aggregate validator subscriptions at an `ExactSubnet`.
     aggregate_validators_on_subnet: HashSetDelay<ExactSubnet>,
 
+    /// The time before the advance subscription time at which to subscribe to a subnet.
+    subscription_lead_duration: Duration,
+
+    /// The time after a subscribed slot during which to remain subscribed to its subnet.
+    subscription_linger_duration: Duration,
+
     /// A collection of seen validators. These dictate how many random subnets we should be
     /// subscribed to. As these time out, we unsubscribe for the required random subnets and update
     /// our ENR.
@@ -103,6 +110,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     pub fn new(
         beacon_chain: Arc<BeaconChain<T>>,
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+        config: &NetworkConfig,
         log: &slog::Logger,
     ) -> Self {
         let log = log.new(o!("service" => "attestation_service"));
@@ -122,6 +130,16 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         let default_timeout = slot_duration
             .checked_mul(DEFAULT_EXPIRATION_TIMEOUT)
             .expect("DEFAULT_EXPIRATION_TIMEOUT must not be ridiculoustly large");
+        let subscription_lead_duration = slots_duration(
+            slot_duration,
+            config.subnet_subscription_lead_slots,
+            "subnet_subscription_lead_slots",
+        );
+        let subscription_linger_duration = slots_duration(
+            slot_duration,
+            config.subnet_subscription_linger_slots,
+            "subnet_subscription_linger_slots",
+        );
 
         AttestationService {
             events: VecDeque::with_capacity(10),
@@ -131,6 +149,8 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             subscriptions: HashSetDelay::new(default_timeout),
             unsubscriptions: HashSetDelay::new(default_timeout),
             aggregate_validators_on_subnet: HashSetDelay::new(default_timeout),
+            subscription_lead_duration,
+            subscription_linger_duration,
             known_validators: HashSetDelay::new(last_seen_val_timeout),
             waker: None,
             log,
@@ -167,12 +187,15 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             );
             self.add_known_validator(subscription.validator_index);
 
-            let subnet_id = match SubnetId::compute_subnet::<T::EthSpec>(
-                subscription.slot,
-                subscription.attestation_committee_index,
-                subscription.committee_count_at_slot,
-                &self.beacon_chain.spec,
-            ) {
+            // A subnet id provided with the subscription has already been verified.
+            let subnet_id = match subscription.subnet_id.map(Ok).unwrap_or_else(|| {
+                SubnetId::compute_subnet::<T::EthSpec>(
+                    subscription.slot,
+                    subscription.attestation_committee_index,
+                    subscription.committee_count_at_slot,
+                    &self.beacon_chain.spec,
+                )
+            }) {
                 Ok(subnet_id) => subnet_id,
                 Err(e) => {
                     warn!(self.log,
@@ -331,15 +354,17 @@ impl<T: BeaconChainTypes> AttestationService<T> {
                 .ok_or_else(|| "Unable to determine duration to next slot")?;
 
             if current_slot >= exact_subnet.slot {
-                (Duration::from_secs(0), duration_to_next_slot)
+                (
+                    Duration::from_secs(0),
+                    duration_to_next_slot + self.subscription_linger_duration,
+                )
             } else {
                 let slot_duration = self.beacon_chain.slot_clock.slot_duration();
                 let advance_subscription_duration = slot_duration
                     .checked_div(ADVANCE_SUBSCRIBE_TIME)
                     .expect("ADVANCE_SUBSCRIPTION_TIME cannot be too large");
 
-                // calculate the time to subscribe to the subnet
-                let duration_to_subscribe = self
+                let duration_to_advance_subscription = self
                     .beacon_chain
                     .slot_clock
                     .duration_to_slot(exact_subnet.slot)
@@ -347,11 +372,17 @@ impl<T: BeaconChainTypes> AttestationService<T> {
                     .checked_sub(advance_subscription_duration)
                     .unwrap_or_else(|| Duration::from_secs(0));
 
-                // the duration until we no longer need this subscription. We assume a single slot is
-                // sufficient.
-                let expected_end_subscription_duration = duration_to_subscribe
+                // calculate the time to subscribe to the subnet, including any configured lead
+                let duration_to_subscribe = duration_to_advance_subscription
+                    .checked_sub(self.subscription_lead_duration)
+                    .unwrap_or_else(|| Duration::from_secs(0));
+
+                // the duration until we no longer need this subscription. We assume a single slot,
+                // plus any configured lingering time, is sufficient.
+                let expected_end_subscription_duration = duration_to_advance_subscription
                     + slot_duration
-                    + std::cmp::min(advance_subscription_duration, duration_to_next_slot);
+                    + std::cmp::min(advance_subscription_duration, duration_to_next_slot)
+                    + self.subscription_linger_duration;
 
                 (duration_to_subscribe, expected_end_subscription_duration)
             }
@@ -488,8 +519,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
                 .checked_div(ADVANCE_SUBSCRIBE_TIME)
                 .expect("ADVANCE_SUBSCRIPTION_TIME cannot be too large");
             // we require the subnet subscription for at least a slot on top of the initial
-            // subscription time
-            let expected_end_subscription_duration = slot_duration + advance_subscription_duration;
+            // subscription time, plus any configured lead and lingering time
+            let expected_end_subscription_duration = self.subscription_lead_duration
+                + advance_subscription_duration
+                + slot_duration
+                + self.subscription_linger_duration;
 
             if expiry < &(Instant::now() + expected_end_subscription_duration) {
                 self.random_subnets
@@ -619,6 +653,14 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     }
 }
 
+/// Returns the duration of `slots` slots of `slot_duration`, panicking on overflow.
+fn slots_duration(slot_duration: Duration, slots: u64, name: &str) -> Duration {
+    u32::try_from(slots)
+        .ok()
+        .and_then(|slots| slot_duration.checked_mul(slots))
+        .unwrap_or_else(|| panic!("{} must not be ridiculously large", name))
+}
+
 impl<T: BeaconChainTypes> Stream for AttestationService<T> {
     type Item = AttServiceMessage;
 
@@ -94,16 +94,21 @@ mod tests {
     }
 
     fn get_attestation_service() -> AttestationService<TestBeaconChainType> {
+        get_attestation_service_with_config(NetworkConfig::default())
+    }
+
+    fn get_attestation_service_with_config(
+        config: NetworkConfig,
+    ) -> AttestationService<TestBeaconChainType> {
         let log = get_logger();
 
         let beacon_chain = CHAIN.chain.clone();
 
-        let config = NetworkConfig::default();
         let enr_key = CombinedKey::from_libp2p(&Keypair::generate_secp256k1()).unwrap();
         let enr = build_enr::<MinimalEthSpec>(&enr_key, &config, EnrForkId::default()).unwrap();
 
         let network_globals: NetworkGlobals<MinimalEthSpec> = NetworkGlobals::new(enr, 0, 0, &log);
-        AttestationService::new(beacon_chain, Arc::new(network_globals), &log)
+        AttestationService::new(beacon_chain, Arc::new(network_globals), &config, &log)
     }
 
     fn get_subscription(
@@ -119,6 +124,7 @@ mod tests {
             slot,
             committee_count_at_slot,
             is_aggregator,
+            subnet_id: None,
         }
     }
 
@@ -210,7 +216,11 @@ mod tests {
         let events = get_events(attestation_service, no_events_expected, 1).await;
         assert_matches!(
             events[..3],
-            [AttServiceMessage::DiscoverPeers(_), AttServiceMessage::Subscribe(_any1), AttServiceMessage::EnrAdd(_any3)]
+            [
+                AttServiceMessage::DiscoverPeers(_),
+                AttServiceMessage::Subscribe(_any1),
+                AttServiceMessage::EnrAdd(_any3)
+            ]
         );
         // if there are fewer events than expected, there's been a collision
         if events.len() == no_events_expected {
@@ -263,7 +273,11 @@ mod tests {
         let events = get_events(attestation_service, no_events_expected, 2).await;
         assert_matches!(
             events[..3],
-            [AttServiceMessage::DiscoverPeers(_), AttServiceMessage::Subscribe(_any1), AttServiceMessage::EnrAdd(_any3)]
+            [
+                AttServiceMessage::DiscoverPeers(_),
+                AttServiceMessage::Subscribe(_any1),
+                AttServiceMessage::EnrAdd(_any3)
+            ]
         );
         // if there are fewer events than expected, there's been a collision
         if events.len() == no_events_expected {
@@ -271,6 +285,89 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn subscription_window_is_configurable() {
+        // subscription config
+        let validator_index = 1;
+        let committee_index = 1;
+        let subscription_slot = 5;
+        let committee_count = 1;
+        let lead_slots = 2;
+        let linger_slots = 3;
+
+        // create the attestation service and subscriptions
+        let mut config = NetworkConfig::default();
+        config.subnet_subscription_lead_slots = lead_slots;
+        config.subnet_subscription_linger_slots = linger_slots;
+        let mut attestation_service = get_attestation_service_with_config(config);
+        let slot_clock = &attestation_service.beacon_chain.slot_clock;
+        let current_slot = slot_clock.now().expect("Could not get current slot");
+        let slot = current_slot + Slot::new(subscription_slot);
+
+        let subscriptions = vec![get_subscription(
+            validator_index,
+            committee_index,
+            slot,
+            committee_count,
+        )];
+
+        // the subscription slot starts at `slot_start`, and ends a slot later
+        let slot_duration = Duration::from_millis(SLOT_DURATION_MILLIS);
+        let advance_duration = slot_duration / ADVANCE_SUBSCRIBE_TIME;
+        let slot_start = Instant::now() + slot_clock.duration_to_slot(slot).unwrap();
+        let duration_to_next_slot = slot_clock.duration_to_next_slot().unwrap();
+
+        // submit the subscriptions
+        attestation_service
+            .validator_subscriptions(subscriptions)
+            .unwrap();
+
+        let exact_subnet = ExactSubnet {
+            subnet_id: SubnetId::compute_subnet::<MinimalEthSpec>(
+                slot,
+                committee_index,
+                committee_count,
+                &attestation_service.beacon_chain.spec,
+            )
+            .unwrap(),
+            slot,
+        };
+        let subscribe_at = *attestation_service
+            .subscriptions
+            .get(&exact_subnet)
+            .expect("should schedule a subscription");
+        let unsubscribe_at = *attestation_service
+            .unsubscriptions
+            .get(&exact_subnet)
+            .expect("should schedule an unsubscription");
+
+        let expected_subscribe_at = slot_start - advance_duration - slot_duration * 2;
+        let expected_unsubscribe_at = slot_start - advance_duration
+            + slot_duration * 4
+            + std::cmp::min(advance_duration, duration_to_next_slot);
+
+        // allow for the time elapsed whilst scheduling
+        let tolerance = Duration::from_millis(SLOT_DURATION_MILLIS / 4);
+        let within_tolerance = |actual: Instant, expected: Instant| {
+            let difference = if actual > expected {
+                actual - expected
+            } else {
+                expected - actual
+            };
+            difference <= tolerance
+        };
+        assert!(
+            within_tolerance(subscribe_at, expected_subscribe_at),
+            "should subscribe {} slots ahead of the advance subscription time",
+            lead_slots
+        );
+        assert!(
+            within_tolerance(unsubscribe_at, expected_unsubscribe_at),
+            "should remain subscribed for {} slots after the subscription slot",
+            linger_slots
+        );
+    }
+
     #[tokio::test]
     async fn subscribe_five_slots_ahead() {
         // subscription config
@@ -324,7 +421,11 @@ mod tests {
         let events = get_events(attestation_service, no_events_expected, 1).await;
         assert_matches!(
             events[..3],
-            [AttServiceMessage::DiscoverPeers(_), AttServiceMessage::Subscribe(_any2), AttServiceMessage::EnrAdd(_any3)]
+            [
+                AttServiceMessage::DiscoverPeers(_),
+                AttServiceMessage::Subscribe(_any2),
+                AttServiceMessage::EnrAdd(_any3)
+            ]
         );
         // if there are fewer events than expected, there's been a collision
         if events.len() == no_events_expected {
@@ -385,7 +486,11 @@ mod tests {
         let events = get_events(attestation_service, no_events_expected, 5).await;
         assert_matches!(
             events[..3],
-            [AttServiceMessage::DiscoverPeers(_), AttServiceMessage::Subscribe(_any2), AttServiceMessage::EnrAdd(_any3)]
+            [
+                AttServiceMessage::DiscoverPeers(_),
+                AttServiceMessage::Subscribe(_any2),
+                AttServiceMessage::EnrAdd(_any3)
+            ]
         );
         // if there are fewer events than expected, there's been a collision
         if events.len() == no_events_expected {
@@ -429,7 +534,11 @@ mod tests {
 
         assert_matches!(
             events[..3],
-            [AttServiceMessage::DiscoverPeers(_), AttServiceMessage::Subscribe(_any2), AttServiceMessage::EnrAdd(_any3)]
+            [
+                AttServiceMessage::DiscoverPeers(_),
+                AttServiceMessage::Subscribe(_any2),
+                AttServiceMessage::EnrAdd(_any3)
+            ]
         );
         // if there are fewer events than expected, there's been a collision
         if events.len() == no_events_expected {
@@ -494,7 +603,11 @@ mod tests {
 
         assert_matches!(
             events[..3],
-            [AttServiceMessage::DiscoverPeers(_), AttServiceMessage::Subscribe(_any2), AttServiceMessage::EnrAdd(_any3)]
+            [
+                AttServiceMessage::DiscoverPeers(_),
+                AttServiceMessage::Subscribe(_any2),
+                AttServiceMessage::EnrAdd(_any3)
+            ]
         );
         // if there are fewer events than expected, there's been a collision
         if events.len() == no_events_expected {
@@ -18,10 +18,11 @@ use state_processing::SigVerifiedOp;
 use std::collections::{hash_map, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use tree_hash::TreeHash;
 use types::{
     typenum::Unsigned, Attestation, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
     EthSpec, Fork, ForkVersion, Hash256, ProposerSlashing, RelativeEpoch, SignedVoluntaryExit,
-    Validator,
+    Slot, Validator,
 };
 
 #[derive(Default, Debug)]
@@ -95,6 +96,71 @@ impl<T: EthSpec> OperationPool<T> {
         self.attestations.read().values().map(Vec::len).sum()
     }
 
+    /// Returns all attestations in the pool with a slot in the inclusive range
+    /// `start_slot..=end_slot`, without aggregating them.
+    pub fn get_attestations_in_slot_range(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Vec<Attestation<T>> {
+        self.attestations
+            .read()
+            .values()
+            .flatten()
+            .filter(|attestation| {
+                attestation.data.slot >= start_slot && attestation.data.slot <= end_slot
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns all attestations in the pool, without aggregating them.
+    pub fn get_all_attestations(&self) -> Vec<Attestation<T>> {
+        self.attestations
+            .read()
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Aggregate all of the attestations in the pool for the `AttestationData` with the given
+    /// `slot` and `data_root` into a single attestation.
+    ///
+    /// Attestations are stored pre-aggregated where possible, however attestations with
+    /// overlapping signers are kept separately. This function starts from the attestation with
+    /// the most signers and greedily aggregates each other attestation that is disjoint from it,
+    /// returning `None` if there are no matching attestations.
+    pub fn get_aggregated_attestation(
+        &self,
+        slot: Slot,
+        data_root: Hash256,
+    ) -> Option<Attestation<T>> {
+        let reader = self.attestations.read();
+        let mut matching = reader
+            .values()
+            .flatten()
+            .filter(|attestation| {
+                attestation.data.slot == slot && attestation.data.tree_hash_root() == data_root
+            })
+            .collect::<Vec<_>>();
+
+        // Visit the attestations with the most signers first.
+        matching.sort_by_key(|attestation| {
+            std::cmp::Reverse(attestation.aggregation_bits.num_set_bits())
+        });
+
+        let mut iter = matching.into_iter();
+        let mut aggregate = iter.next()?.clone();
+        for attestation in iter {
+            if aggregate.signers_disjoint_from(attestation) {
+                aggregate.aggregate(attestation);
+            }
+        }
+
+        Some(aggregate)
+    }
+
     /// Get a list of attestations for inclusion in a block.
     ///
     /// The `validity_filter` is a closure that provides extra filtering of the attestations
@@ -320,6 +386,23 @@ impl<T: EthSpec> OperationPool<T> {
         );
     }
 
+    /// Prune exits for validators which have already initiated an exit at the given head state.
+    ///
+    /// Such exits can never be included in a block on the chain of `head_state`.
+    pub fn prune_voluntary_exits_at_head(&self, head_state: &BeaconState<T>, spec: &ChainSpec) {
+        prune_validator_hash_map(
+            &mut self.voluntary_exits.write(),
+            |validator| validator.exit_epoch != spec.far_future_epoch,
+            head_state,
+        );
+    }
+
+    /// Returns all known voluntary exits, regardless of whether they are valid for inclusion in a
+    /// block.
+    pub fn get_all_voluntary_exits(&self) -> Vec<SignedVoluntaryExit> {
+        self.voluntary_exits.read().values().cloned().collect()
+    }
+
     /// Prune all types of transactions given the latest finalized state and head fork.
     pub fn prune_all(&self, finalized_state: &BeaconState<T>, head_fork: Fork) {
         self.prune_attestations(finalized_state);
@@ -590,6 +673,41 @@ mod release_tests {
         assert_eq!(op_pool.num_attestations(), 0);
     }
 
+    /// Only attestations within the requested slot window should be returned.
+    #[test]
+    fn attestations_in_slot_range() {
+        let (ref mut state, ref keypairs, ref spec) = attestation_test_state::<MainnetEthSpec>(1);
+
+        let op_pool = OperationPool::new();
+
+        let epoch_start_slot = state
+            .current_epoch()
+            .start_slot(MainnetEthSpec::slots_per_epoch());
+        for slot in &[epoch_start_slot, epoch_start_slot + 31] {
+            let bc = state.get_beacon_committee(*slot, 0).unwrap().into_owned();
+            let att = signed_attestation(
+                &bc.committee,
+                bc.index,
+                keypairs,
+                ..,
+                *slot,
+                state,
+                spec,
+                None,
+            );
+            op_pool
+                .insert_attestation(att, &state.fork, state.genesis_validators_root, spec)
+                .unwrap();
+        }
+
+        assert_eq!(op_pool.num_attestations(), 2);
+
+        let attestations =
+            op_pool.get_attestations_in_slot_range(epoch_start_slot + 30, epoch_start_slot + 32);
+        assert_eq!(attestations.len(), 1);
+        assert_eq!(attestations[0].data.slot, epoch_start_slot + 31);
+    }
+
     /// Adding an attestation already in the pool should not increase the size of the pool.
     #[test]
     fn attestation_duplicate() {
@@ -1,37 +1,98 @@
 use crate::helpers::*;
 use crate::validator::get_state_for_epoch;
-use crate::Context;
+use crate::{
+    metrics, Context, MAX_BLOCK_ATTESTATIONS_SLOTS, MAX_VALIDATORS_PAGE_LIMIT, MAX_VALIDATOR_STATES,
+};
 use crate::{ApiError, UrlQuery};
 use beacon_chain::{
-    observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes, StateSkipConfig,
+    events::EventKind, observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes,
+    StateSkipConfig,
 };
 use futures::executor::block_on;
 use hyper::body::Bytes;
 use hyper::{Body, Request};
+use itertools::{process_results, Itertools};
 use rest_types::{
-    BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
-    ValidatorRequest, ValidatorResponse,
+    AttestationRewards, AttestationRewardsRequest, BlockAttestations, BlockResponse, BlockRewards,
+    BlockRootResponse, CanonicalHeadResponse, Committee, FinalityCheckpoints, HeadBeaconBlock,
+    PoolSummary, StateResponse, ValidatorBalance, ValidatorId, ValidatorRequest, ValidatorResponse,
+    ValidatorStatesRequest, ValidatorStatus, ValidatorsPage,
+};
+use state_processing::common::{get_attesting_indices, get_base_reward};
+use state_processing::per_block_processing::{
+    process_attester_slashings, process_proposer_slashings, VerifySignatures,
 };
+use state_processing::per_epoch_processing::{get_attestation_component_deltas, ValidatorStatuses};
+use state_processing::per_slot_processing;
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::io::Write;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use slog::error;
 use types::{
-    AttesterSlashing, BeaconState, EthSpec, Hash256, ProposerSlashing, PublicKeyBytes,
-    RelativeEpoch, SignedBeaconBlockHash, Slot,
+    Attestation, AttestationData, AttesterSlashing, BeaconState, BitList, ChainSpec, EthSpec, Fork,
+    Hash256, ProposerSlashing, PublicKeyBytes, RelativeEpoch, SignedBeaconBlock, Slot,
 };
 
+/// Returns the root of the state identified by `state_id`.
+///
+/// If the API only serves finalized data, states later than the finalized checkpoint are refused.
+fn requested_state_root<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    state_id: StateId,
+) -> Result<Hash256, ApiError> {
+    let state_root = state_root_for_state_id(&ctx.beacon_chain, state_id)?;
+    if ctx.config.finalized_only {
+        ensure_finalized_state(&ctx.beacon_chain, state_root)?;
+    }
+    Ok(state_root)
+}
+
+/// Returns the root of the state identified by `state_id_opt`, or `None` if the canonical head
+/// state should be read.
+///
+/// If the API only serves finalized data, the finalized state is read in place of the head state.
+fn requested_state_root_opt<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    state_id_opt: Option<StateId>,
+) -> Result<Option<Hash256>, ApiError> {
+    match state_id_opt {
+        Some(state_id) => requested_state_root(ctx, state_id).map(Some),
+        None if ctx.config.finalized_only => {
+            state_root_for_state_id(&ctx.beacon_chain, StateId::Finalized).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Returns an error if the API only serves finalized data, for requests which describe the
+/// unfinalized chain.
+fn ensure_unfinalized_data_served<T: BeaconChainTypes>(ctx: &Context<T>) -> Result<(), ApiError> {
+    if ctx.config.finalized_only {
+        Err(ApiError::BadRequest(
+            "Only finalized data is served, the heads of the chain are not finalized".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 /// Returns a summary of the head of the beacon chain.
 pub fn get_head<T: BeaconChainTypes>(
     ctx: Arc<Context<T>>,
 ) -> Result<CanonicalHeadResponse, ApiError> {
+    ensure_unfinalized_data_served(&ctx)?;
+
     let beacon_chain = &ctx.beacon_chain;
-    let chain_head = beacon_chain.head()?;
+    let chain_head = beacon_chain.head_snapshot()?;
 
     Ok(CanonicalHeadResponse {
         slot: chain_head.beacon_state.slot,
         block_root: chain_head.beacon_block_root,
         state_root: chain_head.beacon_state_root,
+        proposer_index: chain_head.beacon_block.message.proposer_index,
         finalized_slot: chain_head
             .beacon_state
             .finalized_checkpoint
@@ -54,29 +115,45 @@ pub fn get_head<T: BeaconChainTypes>(
 }
 
 /// Return the list of heads of the beacon chain.
-pub fn get_heads<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Vec<HeadBeaconBlock> {
-    ctx.beacon_chain
+pub fn get_heads<T: BeaconChainTypes>(
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<HeadBeaconBlock>, ApiError> {
+    ensure_unfinalized_data_served(&ctx)?;
+
+    Ok(ctx
+        .beacon_chain
         .heads()
         .into_iter()
         .map(|(beacon_block_root, beacon_block_slot)| HeadBeaconBlock {
             beacon_block_root,
             beacon_block_slot,
         })
-        .collect()
+        .collect())
 }
 
-/// HTTP handler to return a `BeaconBlock` at a given `root` or `slot`.
-pub fn get_block<T: BeaconChainTypes>(
-    req: Request<Vec<u8>>,
-    ctx: Arc<Context<T>>,
-) -> Result<BlockResponse<T::EthSpec>, ApiError> {
+/// Returns the root and the block identified by the `root` or `slot` query parameter of `req`.
+///
+/// If the API only serves finalized data, blocks later than the finalized checkpoint are refused
+/// without being loaded.
+fn requested_block<T: BeaconChainTypes>(
+    req: &Request<Vec<u8>>,
+    ctx: &Context<T>,
+) -> Result<(Hash256, SignedBeaconBlock<T::EthSpec>), ApiError> {
     let beacon_chain = &ctx.beacon_chain;
     let query_params = ["root", "slot"];
-    let (key, value) = UrlQuery::from_request(&req)?.first_of(&query_params)?;
+    let (key, value) = UrlQuery::from_request(req)?.first_of(&query_params)?;
+
+    metrics::inc_counter_vec(
+        &metrics::BEACON_HTTP_API_BLOCK_ID_RESOLUTIONS_TOTAL,
+        &[&key],
+    );
 
     let block_root = match (key.as_ref(), value) {
         ("slot", value) => {
             let target = parse_slot(&value)?;
+            if ctx.config.finalized_only {
+                ensure_finalized_slot(beacon_chain, target)?;
+            }
 
             block_root_at_slot(beacon_chain, target)?.ok_or_else(|| {
                 ApiError::NotFound(format!(
@@ -85,7 +162,13 @@ pub fn get_block<T: BeaconChainTypes>(
                 ))
             })?
         }
-        ("root", value) => parse_root(&value)?,
+        ("root", value) => {
+            let root = parse_root(&value)?;
+            if ctx.config.finalized_only {
+                ensure_finalized_block(beacon_chain, root)?;
+            }
+            root
+        }
         _ => return Err(ApiError::ServerError("Unexpected query parameter".into())),
     };
 
@@ -96,60 +179,525 @@ pub fn get_block<T: BeaconChainTypes>(
         ))
     })?;
 
+    // Blocks which have been pruned from fork choice can only be checked once loaded.
+    if ctx.config.finalized_only {
+        ensure_finalized_slot(beacon_chain, block.slot())?;
+    }
+
+    Ok((block_root, block))
+}
+
+/// HTTP handler to return a `BeaconBlock` at a given `root` or `slot`.
+pub fn get_block<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<BlockResponse<T::EthSpec>, ApiError> {
+    let (root, beacon_block) = requested_block(&req, &ctx)?;
+    let finalized = is_finalized_block(&ctx.beacon_chain, root, beacon_block.slot())?;
+
     Ok(BlockResponse {
-        root: block_root,
-        beacon_block: block,
+        root,
+        beacon_block,
+        finalized,
     })
 }
 
-/// HTTP handler to return a `SignedBeaconBlock` root at a given `slot`.
-pub fn get_block_root<T: BeaconChainTypes>(
+/// HTTP handler to return the block `distance` parents before the block at a given `root` or
+/// `slot`.
+///
+/// The walk follows parent roots, so it may be used to traverse non-canonical chains.
+pub fn get_block_ancestor<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<Hash256, ApiError> {
-    let slot_string = UrlQuery::from_request(&req)?.only_one("slot")?;
-    let target = parse_slot(&slot_string)?;
+) -> Result<BlockResponse<T::EthSpec>, ApiError> {
+    let (block_root, _) = requested_block(&req, &ctx)?;
+    let distance = UrlQuery::from_request(&req)?
+        .first_of(&["distance"])?
+        .1
+        .parse::<u64>()
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse distance: {:?}", e)))?;
+
+    let (root, beacon_block) = block_ancestor(&ctx.beacon_chain, block_root, distance)?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Block {:?} has fewer than {} ancestors",
+                block_root, distance
+            ))
+        })?;
+    let finalized = is_finalized_block(&ctx.beacon_chain, root, beacon_block.slot())?;
+
+    Ok(BlockResponse {
+        root,
+        beacon_block,
+        finalized,
+    })
+}
+
+/// HTTP handler to return the rewards of the proposer of the block at a given `root` or `slot`.
+///
+/// The rewards are computed by applying the operations of the block to the state of its parent,
+/// advanced to the slot of the block.
+pub fn get_block_rewards<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<BlockRewards, ApiError> {
+    let (block_root, block) = requested_block(&req, &ctx)?;
+    let spec = &ctx.beacon_chain.spec;
+
+    if block.slot() == spec.genesis_slot {
+        return Err(ApiError::BadRequest(
+            "The genesis block has no proposer rewards".to_string(),
+        ));
+    }
+
+    let parent_root = block.parent_root();
+    let parent = ctx
+        .beacon_chain
+        .store
+        .get_block(&parent_root)?
+        .ok_or_else(|| {
+            ApiError::ServerError(format!("Unable to find parent block {:?}", parent_root))
+        })?;
+    let mut state = ctx
+        .beacon_chain
+        .get_state(&parent.state_root(), Some(parent.slot()))?
+        .ok_or_else(|| {
+            ApiError::ServerError(format!(
+                "Unable to find parent state {:?}",
+                parent.state_root()
+            ))
+        })?;
+
+    while state.slot < block.slot() {
+        per_slot_processing(&mut state, None, spec)?;
+    }
+    state.build_all_committee_caches(spec)?;
+
+    let proposer_index = block.message.proposer_index as usize;
+    let processing_error =
+        |e| ApiError::ServerError(format!("Unable to process block operations: {:?}", e));
+
+    // Slashings are applied in the same order as during block processing, so each component is
+    // the change in the balance of the proposer caused by applying its operations.
+    let initial_balance = state.balances[proposer_index];
+    process_proposer_slashings(
+        &mut state,
+        &block.message.body.proposer_slashings,
+        VerifySignatures::False,
+        spec,
+    )
+    .map_err(processing_error)?;
+    let proposer_slashing_balance = state.balances[proposer_index];
+    process_attester_slashings(
+        &mut state,
+        &block.message.body.attester_slashings,
+        VerifySignatures::False,
+        spec,
+    )
+    .map_err(processing_error)?;
+    let attester_slashing_balance = state.balances[proposer_index];
+
+    let proposer_slashings = proposer_slashing_balance.saturating_sub(initial_balance);
+    let attester_slashings = attester_slashing_balance.saturating_sub(proposer_slashing_balance);
+    let attestations = block_attestation_rewards(&state, &block, spec)?;
+
+    Ok(BlockRewards {
+        block_root,
+        proposer_index: proposer_index as u64,
+        attestations,
+        proposer_slashings,
+        attester_slashings,
+        total: attestations + proposer_slashings + attester_slashings,
+    })
+}
+
+/// Returns the reward that the proposer of `block` is due for the attesters that the block
+/// includes for the first time.
+///
+/// `state` must be at the slot of the block, with its slashings applied.
+fn block_attestation_rewards<E: EthSpec>(
+    state: &BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+    spec: &ChainSpec,
+) -> Result<u64, ApiError> {
+    let attesting_indices = |data: &AttestationData,
+                             aggregation_bits: &BitList<E::MaxValidatorsPerCommittee>|
+     -> Result<_, ApiError> {
+        let committee = state.get_beacon_committee(data.slot, data.index)?;
+        Ok(get_attesting_indices::<E>(
+            committee.committee,
+            aggregation_bits,
+        )?)
+    };
+
+    // The proposer is rewarded once per attester and target epoch, so attesters which have already
+    // been included in a prior block for the same target epoch earn no further reward.
+    let mut rewarded = HashSet::new();
+    for pending in state
+        .previous_epoch_attestations
+        .iter()
+        .chain(state.current_epoch_attestations.iter())
+    {
+        let target_epoch = pending.data.target.epoch;
+        rewarded.extend(
+            attesting_indices(&pending.data, &pending.aggregation_bits)?
+                .into_iter()
+                .map(|index| (target_epoch, index)),
+        );
+    }
+
+    let total_active_balance = state.get_total_active_balance(spec)?;
+    let mut reward = 0;
+    for attestation in &block.message.body.attestations {
+        let target_epoch = attestation.data.target.epoch;
+        for index in attesting_indices(&attestation.data, &attestation.aggregation_bits)? {
+            if state.validators[index].slashed || !rewarded.insert((target_epoch, index)) {
+                continue;
+            }
+            reward += get_base_reward(state, index, total_active_balance, spec)?
+                / spec.proposer_reward_quotient;
+        }
+    }
+
+    Ok(reward)
+}
+
+/// HTTP handler to fully verify a `SignedBeaconBlock` against the chain, without importing or
+/// publishing it.
+pub fn validate_block<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<(), ApiError> {
+    let block: SignedBeaconBlock<T::EthSpec> =
+        serde_json::from_slice(&req.into_body()).map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into SignedBeaconBlock: {:?}",
+                e
+            ))
+        })?;
 
-    block_root_at_slot(&ctx.beacon_chain, target)?.ok_or_else(|| {
+    match ctx.beacon_chain.validate_block(block) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(reason)) => Err(ApiError::ProcessingError(format!(
+            "The SignedBeaconBlock is invalid: {}",
+            reason
+        ))),
+        Err(e) => Err(ApiError::ServerError(format!(
+            "Error while validating block: {:?}",
+            e
+        ))),
+    }
+}
+
+/// HTTP handler to return the attestations included in each canonical block with a slot in
+/// `start_slot..start_slot + count`, ordered by ascending slot.
+pub fn get_block_attestations<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<BlockAttestations<T::EthSpec>>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let start_slot = parse_slot(&query.first_of(&["start_slot"])?.1)?;
+    let count = query
+        .first_of(&["count"])?
+        .1
+        .parse::<u64>()
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse count: {:?}", e)))?;
+
+    if count > MAX_BLOCK_ATTESTATIONS_SLOTS {
+        return Err(ApiError::BadRequest(format!(
+            "The count must not exceed {}",
+            MAX_BLOCK_ATTESTATIONS_SLOTS
+        )));
+    }
+
+    let end_slot = start_slot + count;
+
+    // Skipped slots repeat the root of the prior block, so dedup the roots before loading blocks.
+    let block_roots = process_results(ctx.beacon_chain.rev_iter_block_roots()?, |iter| {
+        iter.take_while(|(_, slot)| *slot >= start_slot)
+            .filter(|(_, slot)| *slot < end_slot)
+            .map(|(root, _)| root)
+            .dedup()
+            .collect::<Vec<_>>()
+    })?;
+
+    let mut response = block_roots
+        .into_iter()
+        .map(|block_root| -> Result<_, ApiError> {
+            let block = ctx.beacon_chain.get_block(&block_root)?.ok_or_else(|| {
+                ApiError::ServerError(format!(
+                    "Unable to find canonical SignedBeaconBlock for root {:?}",
+                    block_root
+                ))
+            })?;
+            Ok((block_root, block))
+        })
+        // The last root may belong to a block prior to the `start_slot`.
+        .filter(|result| {
+            result
+                .as_ref()
+                .map_or(true, |(_, block)| block.slot() >= start_slot)
+        })
+        .map(|result| {
+            result.map(|(block_root, block)| BlockAttestations {
+                block_root,
+                slot: block.slot(),
+                attestations: block.message.body.attestations.to_vec(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    response.reverse();
+
+    Ok(response)
+}
+
+/// HTTP handler to return a `SignedBeaconBlock` root at a given `slot`, along with whether or
+/// not it is on the canonical chain.
+pub fn get_block_root<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<BlockRootResponse, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let target = query.slot()?;
+    if ctx.config.finalized_only {
+        ensure_finalized_slot(&ctx.beacon_chain, target)?;
+    }
+    let not_found = || {
         ApiError::NotFound(format!(
             "Unable to find SignedBeaconBlock for slot {:?}",
             target
         ))
-    })
+    };
+
+    // If a `head` is supplied, resolve the slot on the chain ending at that block instead of the
+    // canonical chain, so the result is unaffected by re-orgs.
+    if let Some((_key, value)) = query.first_of_opt(&["head"]) {
+        let root = block_root_at_slot_from_head(&ctx.beacon_chain, parse_root(&value)?, target)?
+            .ok_or_else(not_found)?;
+        let canonical = block_root_at_slot(&ctx.beacon_chain, target)? == Some(root);
+
+        Ok(BlockRootResponse { root, canonical })
+    } else {
+        let root = block_root_at_slot(&ctx.beacon_chain, target)?.ok_or_else(not_found)?;
+
+        Ok(BlockRootResponse {
+            root,
+            canonical: true,
+        })
+    }
+}
+
+/// The topics which may be subscribed to via `stream_events`.
+const EVENT_TOPICS: &[&str] = &[
+    "head",
+    "block",
+    "attestation",
+    "finalized_checkpoint",
+    "chain_reorg",
+];
+
+/// Returns the topics of `stream_events` that `event` belongs to.
+fn event_topics<E: EthSpec>(event: &EventKind<E>) -> &'static [&'static str] {
+    match event {
+        EventKind::BeaconHeadChanged { .. } => &["head"],
+        EventKind::BeaconChainReorg { .. } => &["chain_reorg"],
+        EventKind::BeaconBlockImported { .. } => &["block"],
+        EventKind::BeaconAttestationImported { .. } => &["attestation"],
+        EventKind::BeaconFinalization { .. } => &["finalized_checkpoint"],
+        EventKind::BeaconBlockRejected { .. } | EventKind::BeaconAttestationRejected { .. } => &[],
+    }
 }
 
-fn make_sse_response_chunk(new_head_hash: SignedBeaconBlockHash) -> std::io::Result<Bytes> {
+fn make_sse_response_chunk(event: Option<&str>, data: &str) -> std::io::Result<Bytes> {
     let mut buffer = Vec::new();
     {
         let mut sse_message = uhttp_sse::SseMessage::new(&mut buffer);
-        let untyped_hash: Hash256 = new_head_hash.into();
-        write!(sse_message.data()?, "{:?}", untyped_hash)?;
+        if let Some(event) = event {
+            write!(sse_message.event()?, "{}", event)?;
+        }
+        write!(sse_message.data()?, "{}", data)?;
     }
     let bytes: Bytes = buffer.into();
     Ok(bytes)
 }
 
-pub fn stream_forks<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Body, ApiError> {
-    let mut events = ctx.events.lock().add_rx();
+/// Returns a `Body` which streams the chunks produced by `make_chunks` for each event registered
+/// with the `BeaconChain`.
+///
+/// The subscription to the events is dropped once the client disconnects. Whilst the client is
+/// not reading the stream, events are queued in this client's own (bounded) queue of the event
+/// bus and, once it is full, dropped for this client only.
+fn stream_sse_chunks<T, F>(ctx: Arc<Context<T>>, make_chunks: F) -> Body
+where
+    T: BeaconChainTypes,
+    F: Fn(&EventKind<T::EthSpec>) -> std::io::Result<Vec<Bytes>> + Send + 'static,
+{
+    let events = ctx.events.subscribe();
     let (mut sender, body) = Body::channel();
     std::thread::spawn(move || {
-        while let Ok(new_head_hash) = events.recv() {
-            let chunk = match make_sse_response_chunk(new_head_hash) {
-                Ok(chunk) => chunk,
+        'events: while let Ok(event) = events.recv() {
+            let chunks = match make_chunks(&event) {
+                Ok(chunks) => chunks,
                 Err(e) => {
                     error!(ctx.log, "Failed to make SSE chunk"; "error" => e.to_string());
                     sender.abort();
                     break;
                 }
             };
-            match block_on(sender.send_data(chunk)) {
-                Err(e) if e.is_closed() => break,
-                Err(e) => error!(ctx.log, "Couldn't stream piece {:?}", e),
-                Ok(_) => (),
+            for chunk in chunks {
+                match block_on(sender.send_data(chunk)) {
+                    Err(e) if e.is_closed() => break 'events,
+                    Err(e) => error!(ctx.log, "Couldn't stream piece {:?}", e),
+                    Ok(_) => (),
+                }
             }
         }
     });
-    Ok(body)
+    body
+}
+
+pub fn stream_forks<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Body, ApiError> {
+    Ok(stream_sse_chunks(ctx, |event| match event {
+        EventKind::BeaconHeadChanged {
+            current_head_beacon_block_root,
+            ..
+        } => Ok(vec![make_sse_response_chunk(
+            None,
+            &format!("{:?}", current_head_beacon_block_root),
+        )?]),
+        _ => Ok(vec![]),
+    }))
+}
+
+/// HTTP handler to stream the events of the given `topics` as they occur.
+///
+/// Each event is sent as an SSE message, with the topic as the `event` field and the JSON
+/// encoded event as the `data` field.
+pub fn stream_events<T: BeaconChainTypes>(
+    req: Request<()>,
+    ctx: Arc<Context<T>>,
+) -> Result<Body, ApiError> {
+    let topics = UrlQuery::from_request(&req)?
+        .all_of("topics")?
+        .iter()
+        .flat_map(|topics| topics.split(','))
+        .map(|topic| {
+            EVENT_TOPICS
+                .iter()
+                .find(|known| **known == topic)
+                .copied()
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!(
+                        "Unknown topic {:?}, must be one of {:?}",
+                        topic, EVENT_TOPICS
+                    ))
+                })
+        })
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    if topics.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "At least one topic must be supplied, from {:?}",
+            EVENT_TOPICS
+        )));
+    }
+
+    Ok(stream_sse_chunks(ctx, move |event| {
+        event_topics(event)
+            .iter()
+            .filter(|topic| topics.contains(*topic))
+            .map(|topic| make_sse_response_chunk(Some(*topic), &serde_json::to_string(event)?))
+            .collect()
+    }))
+}
+
+/// HTTP handler to return the `Fork` of the state with the given `state_root`, or of the canonical
+/// head if no `state_root` is supplied.
+///
+/// A state's fork only changes at fork boundaries, so the fork is cached per state root to avoid
+/// loading the full state on repeated requests.
+pub fn get_fork<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Fork, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+
+    let state_root = if let Some(state_root) = requested_state_root_opt(&ctx, query.state_id()?)? {
+        state_root
+    } else {
+        return Ok(ctx.beacon_chain.head_info()?.fork);
+    };
+
+    if let Some(fork) = ctx.fork_cache.lock().get(&state_root) {
+        return Ok(*fork);
+    }
+
+    metrics::inc_counter(&metrics::BEACON_HTTP_API_FORK_STATE_LOADS_TOTAL);
+    ctx.fork_state_loads.fetch_add(1, Ordering::Relaxed);
+    let fork = get_state_from_root_opt(&ctx.beacon_chain, Some(state_root))?.fork;
+    ctx.fork_cache.lock().put(state_root, fork);
+
+    Ok(fork)
+}
+
+/// HTTP handler to return the `FinalityCheckpoints` of the state with the given `state_root`, or
+/// of the canonical head if no `state_root` is supplied.
+///
+/// The checkpoints are cached per state root to avoid loading the full state on repeated requests.
+pub fn get_finality_checkpoints<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<FinalityCheckpoints, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+
+    let state_root = if let Some(state_root) = requested_state_root_opt(&ctx, query.state_id()?)? {
+        state_root
+    } else {
+        ctx.beacon_chain.head_info()?.state_root
+    };
+
+    if let Some(checkpoints) = ctx.finality_checkpoints_cache.lock().get(&state_root) {
+        return Ok(checkpoints.clone());
+    }
+
+    metrics::inc_counter(&metrics::BEACON_HTTP_API_FINALITY_CHECKPOINTS_STATE_LOADS_TOTAL);
+    ctx.finality_checkpoints_state_loads
+        .fetch_add(1, Ordering::Relaxed);
+    let state = get_state_from_root_opt(&ctx.beacon_chain, Some(state_root))?;
+    let checkpoints = FinalityCheckpoints::from(&state);
+    ctx.finality_checkpoints_cache
+        .lock()
+        .put(state_root, checkpoints.clone());
+
+    Ok(checkpoints)
+}
+
+/// HTTP handler to return the randao mix of the given `epoch` from the state given by `state_id`.
+///
+/// The canonical head state is used if no state is supplied, and the current epoch of the state
+/// is used if no `epoch` is supplied. Returns a 400 for epochs whose mix is not stored in the
+/// state.
+pub fn get_randao_mix<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Hash256, ApiError> {
+    let state_root_opt = requested_state_root_opt(&ctx, UrlQuery::from_request(&req)?.state_id()?)?;
+    let epoch_opt = UrlQuery::from_request(&req)?
+        .first_of_opt(&["epoch"])
+        .map(|(_key, value)| parse_epoch(&value))
+        .transpose()?;
+
+    let state = get_state_from_root_opt(&ctx.beacon_chain, state_root_opt)?;
+    let epoch = epoch_opt.unwrap_or_else(|| state.current_epoch());
+
+    state.get_randao_mix(epoch).copied().map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to read the randao mix of epoch {} from a state at epoch {}: {:?}",
+            epoch,
+            state.current_epoch(),
+            e
+        ))
+    })
 }
 
 /// HTTP handler to which accepts a query string of a list of validator pubkeys and maps it to a
@@ -169,13 +717,9 @@ pub fn get_validators<T: BeaconChainTypes>(
         .map(|validator_pubkey_str| parse_pubkey_bytes(validator_pubkey_str))
         .collect::<Result<Vec<_>, _>>()?;
 
-    let state_root_opt = if let Some((_key, value)) = query.first_of_opt(&["state_root"]) {
-        Some(parse_root(&value)?)
-    } else {
-        None
-    };
+    let state_root_opt = requested_state_root_opt(&ctx, query.state_id()?)?;
 
-    validator_responses_by_pubkey(&ctx.beacon_chain, state_root_opt, validator_pubkeys)
+    validator_responses_by_pubkey(&ctx, state_root_opt, validator_pubkeys)
 }
 
 /// HTTP handler to return all validators, each as a `ValidatorResponse`.
@@ -185,19 +729,16 @@ pub fn get_all_validators<T: BeaconChainTypes>(
 ) -> Result<Vec<ValidatorResponse>, ApiError> {
     let query = UrlQuery::from_request(&req)?;
 
-    let state_root_opt = if let Some((_key, value)) = query.first_of_opt(&["state_root"]) {
-        Some(parse_root(&value)?)
-    } else {
-        None
-    };
+    let state_root_opt = requested_state_root_opt(&ctx, query.state_id()?)?;
 
     let mut state = get_state_from_root_opt(&ctx.beacon_chain, state_root_opt)?;
     state.update_pubkey_cache()?;
+    let finalized = state_is_finalized(&ctx.beacon_chain, state_root_opt, &state)?;
 
     state
         .validators
         .iter()
-        .map(|validator| validator_response_by_pubkey(&state, validator.pubkey.clone()))
+        .map(|validator| validator_response_by_pubkey(&state, validator.pubkey.clone(), finalized))
         .collect::<Result<Vec<_>, _>>()
 }
 
@@ -208,23 +749,198 @@ pub fn get_active_validators<T: BeaconChainTypes>(
 ) -> Result<Vec<ValidatorResponse>, ApiError> {
     let query = UrlQuery::from_request(&req)?;
 
-    let state_root_opt = if let Some((_key, value)) = query.first_of_opt(&["state_root"]) {
-        Some(parse_root(&value)?)
-    } else {
-        None
-    };
+    let state_root_opt = requested_state_root_opt(&ctx, query.state_id()?)?;
 
     let mut state = get_state_from_root_opt(&ctx.beacon_chain, state_root_opt)?;
     state.update_pubkey_cache()?;
+    let finalized = state_is_finalized(&ctx.beacon_chain, state_root_opt, &state)?;
 
     state
         .validators
         .iter()
         .filter(|validator| validator.is_active_at(state.current_epoch()))
-        .map(|validator| validator_response_by_pubkey(&state, validator.pubkey.clone()))
+        .map(|validator| validator_response_by_pubkey(&state, validator.pubkey.clone(), finalized))
         .collect::<Result<Vec<_>, _>>()
 }
 
+/// HTTP handler to return a page of at most `limit` validators, starting at index `offset`.
+///
+/// Validators may be filtered by the `id` and `status` query parameters, in which case only
+/// validators matching one of the given ids and one of the given statuses are returned. Filters are
+/// applied before paging, so `offset` and the reported total count only the matching validators.
+///
+/// The `limit` defaults to, and is capped at, `MAX_VALIDATORS_PAGE_LIMIT`. An `offset` beyond the
+/// last validator results in an empty page.
+pub fn get_validators_page<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<ValidatorsPage, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+
+    let offset = query.u64_opt("offset")?.unwrap_or(0);
+    let limit = query
+        .u64_opt("limit")?
+        .unwrap_or(MAX_VALIDATORS_PAGE_LIMIT)
+        .min(MAX_VALIDATORS_PAGE_LIMIT);
+
+    let validator_ids = query
+        .all_of("id")?
+        .iter()
+        .map(|validator_id_str| parse_validator_id(validator_id_str))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let statuses = query
+        .all_of("status")?
+        .iter()
+        .map(|status_str| parse_validator_status(status_str))
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    let state_root_opt = requested_state_root_opt(&ctx, query.state_id()?)?;
+
+    // Report the root of the state which was read, so that subsequent pages may be read from the
+    // same state even if the head changes.
+    //
+    // The head state is only cloned if its pubkey cache must be built.
+    let head;
+    let (state_root, mut state) = if let Some(state_root) = state_root_opt {
+        (
+            state_root,
+            Cow::Owned(get_state_from_root_opt(
+                &ctx.beacon_chain,
+                Some(state_root),
+            )?),
+        )
+    } else {
+        head = ctx.beacon_chain.head_snapshot()?;
+        (head.beacon_state_root, Cow::Borrowed(&head.beacon_state))
+    };
+
+    let indices_opt = if validator_ids.is_empty() {
+        None
+    } else {
+        if validator_ids
+            .iter()
+            .any(|id| matches!(id, ValidatorId::PublicKey(_)))
+        {
+            state.to_mut().update_pubkey_cache()?;
+        }
+
+        let mut indices = HashSet::new();
+        for validator_id in validator_ids {
+            if let Some(index) = validator_index_for_id(&state, validator_id)? {
+                indices.insert(index);
+            }
+        }
+        Some(indices)
+    };
+
+    let epoch = state.current_epoch();
+    let far_future_epoch = ctx.beacon_chain.spec.far_future_epoch;
+    let matching = state
+        .validators
+        .iter()
+        .zip(state.balances.iter())
+        .enumerate()
+        .filter(|(index, _)| indices_opt.as_ref().map_or(true, |i| i.contains(index)))
+        .filter(|(_, (validator, balance))| {
+            statuses.is_empty()
+                || statuses.contains(&ValidatorStatus::from_validator(
+                    validator,
+                    **balance,
+                    epoch,
+                    far_future_epoch,
+                ))
+        })
+        .map(|(index, (validator, _))| (index, validator.pubkey.clone()))
+        .collect::<Vec<_>>();
+
+    let total = matching.len() as u64;
+    let finalized = is_finalized_state(&ctx.beacon_chain, state_root, state.slot)?;
+    let validators = matching
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|(index, pubkey)| validator_response_at_index(&state, pubkey, Some(index), finalized))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ValidatorsPage {
+        state_root,
+        total,
+        validators,
+    })
+}
+
+/// HTTP handler to return the balances of the validators identified by the `id` query parameters,
+/// or of all validators if no `id` is supplied.
+///
+/// Each `id` may be a validator index or public key. Validators which are not in the state are
+/// omitted from the response.
+pub fn get_validator_balances<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<ValidatorBalance>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+
+    let validator_ids = query
+        .all_of("id")?
+        .iter()
+        .map(|validator_id_str| parse_validator_id(validator_id_str))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let state_root_opt = requested_state_root_opt(&ctx, query.state_id()?)?;
+
+    let mut state = get_state_from_root_opt(&ctx.beacon_chain, state_root_opt)?;
+
+    if validator_ids.is_empty() {
+        return Ok(state
+            .balances
+            .iter()
+            .enumerate()
+            .map(|(index, balance)| ValidatorBalance {
+                index: index as u64,
+                balance: *balance,
+            })
+            .collect());
+    }
+
+    if validator_ids
+        .iter()
+        .any(|id| matches!(id, ValidatorId::PublicKey(_)))
+    {
+        state.update_pubkey_cache()?;
+    }
+
+    let mut balances = Vec::with_capacity(validator_ids.len());
+    for validator_id in validator_ids {
+        if let Some((index, balance)) = validator_index_for_id(&state, validator_id)?
+            .and_then(|index| state.balances.get(index).map(|balance| (index, *balance)))
+        {
+            balances.push(ValidatorBalance {
+                index: index as u64,
+                balance,
+            });
+        }
+    }
+
+    Ok(balances)
+}
+
+/// Returns the index of the validator identified by `validator_id` in the given state, if any.
+///
+/// The index is not checked against the validator registry of the state. If `validator_id` is a
+/// public key, the provided `state` must have a fully up-to-date pubkey cache.
+fn validator_index_for_id<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_id: ValidatorId,
+) -> Result<Option<usize>, ApiError> {
+    match validator_id {
+        ValidatorId::Index(index) => Ok(Some(index as usize)),
+        ValidatorId::PublicKey(pubkey) => state
+            .get_validator_index(&pubkey)
+            .map_err(|e| ApiError::ServerError(format!("Unable to read pubkey cache: {:?}", e))),
+    }
+}
+
 /// HTTP handler to which accepts a `ValidatorRequest` and returns a `ValidatorResponse` for
 /// each of the given `pubkeys`. When `state_root` is `None`, the canonical head is used.
 ///
@@ -242,14 +958,47 @@ pub fn post_validators<T: BeaconChainTypes>(
             ))
         })
         .and_then(|bulk_request| {
-            validator_responses_by_pubkey(
-                &ctx.beacon_chain,
-                bulk_request.state_root,
-                bulk_request.pubkeys,
-            )
+            let state_root_opt =
+                requested_state_root_opt(&ctx, bulk_request.state_root.map(StateId::Root))?;
+            validator_responses_by_pubkey(&ctx, state_root_opt, bulk_request.pubkeys)
         })
 }
 
+/// HTTP handler which accepts a `ValidatorStatesRequest` and returns a `ValidatorResponse` for the
+/// given `pubkey` in each of the given `state_roots`, in the same order.
+pub fn post_validator_states<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<ValidatorResponse>, ApiError> {
+    let request =
+        serde_json::from_slice::<ValidatorStatesRequest>(&req.into_body()).map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into ValidatorStatesRequest: {:?}",
+                e
+            ))
+        })?;
+
+    // Each state root may require a state to be read from disk, so bound the work of a request.
+    if request.state_roots.len() > MAX_VALIDATOR_STATES {
+        return Err(ApiError::BadRequest(format!(
+            "The number of state_roots must not exceed {}",
+            MAX_VALIDATOR_STATES
+        )));
+    }
+
+    request
+        .state_roots
+        .into_iter()
+        .map(|state_root| {
+            let state_root = requested_state_root(&ctx, StateId::Root(state_root))?;
+            let mut state = get_state_from_root_opt(&ctx.beacon_chain, Some(state_root))?;
+            state.update_pubkey_cache()?;
+            let finalized = is_finalized_state(&ctx.beacon_chain, state_root, state.slot)?;
+            validator_response_by_pubkey(&state, request.pubkey.clone(), finalized)
+        })
+        .collect()
+}
+
 /// Returns either the state given by `state_root_opt`, or the canonical head state if it is
 /// `None`.
 fn get_state_from_root_opt<T: BeaconChainTypes>(
@@ -271,33 +1020,138 @@ fn get_state_from_root_opt<T: BeaconChainTypes>(
     }
 }
 
+/// Returns `true` if the `state` read by `get_state_from_root_opt` for the given `state_root_opt`
+/// is finalized.
+fn state_is_finalized<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_root_opt: Option<Hash256>,
+    state: &BeaconState<T::EthSpec>,
+) -> Result<bool, ApiError> {
+    match state_root_opt {
+        Some(state_root) => is_finalized_state(beacon_chain, state_root, state.slot),
+        // The head state is on the canonical chain by definition.
+        None => Ok(state.slot <= finalized_slot(beacon_chain)?),
+    }
+}
+
 /// Maps a vec of `validator_pubkey` to a vec of `ValidatorResponse`, using the state at the given
 /// `state_root`. If `state_root.is_none()`, uses the canonial head state.
+///
+/// Queries against the head state resolve validator indices using the `BeaconChain` pubkey cache,
+/// rather than building the pubkey cache of the state. Queries against other states remember the
+/// resolved indices in the `validator_index_cache` of the `Context`.
 fn validator_responses_by_pubkey<T: BeaconChainTypes>(
-    beacon_chain: &BeaconChain<T>,
+    ctx: &Context<T>,
     state_root_opt: Option<Hash256>,
     validator_pubkeys: Vec<PublicKeyBytes>,
 ) -> Result<Vec<ValidatorResponse>, ApiError> {
+    let beacon_chain = &ctx.beacon_chain;
     let mut state = get_state_from_root_opt(beacon_chain, state_root_opt)?;
-    state.update_pubkey_cache()?;
+    let finalized = state_is_finalized(beacon_chain, state_root_opt, &state)?;
 
-    validator_pubkeys
-        .into_iter()
-        .map(|validator_pubkey| validator_response_by_pubkey(&state, validator_pubkey))
-        .collect::<Result<Vec<_>, ApiError>>()
+    if let Some(state_root) = state_root_opt {
+        // Serve the indices from the cache if every requested validator was seen before at this
+        // state, avoiding the (expensive) build of the state's pubkey cache.
+        let cached_indices = {
+            let mut cache = ctx.validator_index_cache.lock();
+            validator_pubkeys
+                .iter()
+                .map(|pubkey| cache.get(&(state_root, pubkey.clone())).copied())
+                .collect::<Option<Vec<_>>>()
+        };
+
+        if let Some(indices) = cached_indices {
+            metrics::inc_counter(&metrics::BEACON_HTTP_API_VALIDATOR_INDEX_CACHE_HITS_TOTAL);
+            ctx.validator_index_cache_hits
+                .fetch_add(1, Ordering::Relaxed);
+
+            return validator_pubkeys
+                .into_iter()
+                .zip(indices)
+                .map(|(validator_pubkey, validator_index_opt)| {
+                    validator_response_at_index(
+                        &state,
+                        validator_pubkey,
+                        validator_index_opt,
+                        finalized,
+                    )
+                })
+                .collect::<Result<Vec<_>, ApiError>>();
+        }
+
+        metrics::inc_counter(&metrics::BEACON_HTTP_API_VALIDATOR_INDEX_CACHE_MISSES_TOTAL);
+        ctx.validator_index_cache_misses
+            .fetch_add(1, Ordering::Relaxed);
+        metrics::inc_counter(&metrics::BEACON_HTTP_API_VALIDATOR_PUBKEY_SCANS_TOTAL);
+        ctx.validator_pubkey_scans.fetch_add(1, Ordering::Relaxed);
+        state.update_pubkey_cache()?;
+
+        let responses = validator_pubkeys
+            .into_iter()
+            .map(|validator_pubkey| {
+                validator_response_by_pubkey(&state, validator_pubkey, finalized)
+            })
+            .collect::<Result<Vec<_>, ApiError>>()?;
+
+        let mut cache = ctx.validator_index_cache.lock();
+        for response in &responses {
+            cache.put(
+                (state_root, response.pubkey.clone()),
+                response.validator_index,
+            );
+        }
+
+        Ok(responses)
+    } else {
+        validator_pubkeys
+            .into_iter()
+            .map(|validator_pubkey| {
+                // The chain's pubkey cache contains all validators ever seen, so ensure the
+                // validator is actually present in the head state.
+                let validator_index_opt =
+                    beacon_chain
+                        .validator_index(&validator_pubkey)?
+                        .filter(|&i| {
+                            state
+                                .validators
+                                .get(i)
+                                .map_or(false, |v| v.pubkey == validator_pubkey)
+                        });
+                validator_response_at_index(
+                    &state,
+                    validator_pubkey,
+                    validator_index_opt,
+                    finalized,
+                )
+            })
+            .collect::<Result<Vec<_>, ApiError>>()
+    }
 }
 
-/// Maps a `validator_pubkey` to a `ValidatorResponse`, using the given state.
+/// Maps a `validator_pubkey` to a `ValidatorResponse`, using the given state, which is finalized
+/// if `finalized` is `true`.
 ///
 /// The provided `state` must have a fully up-to-date pubkey cache.
 fn validator_response_by_pubkey<E: EthSpec>(
     state: &BeaconState<E>,
     validator_pubkey: PublicKeyBytes,
+    finalized: bool,
 ) -> Result<ValidatorResponse, ApiError> {
     let validator_index_opt = state
         .get_validator_index(&validator_pubkey)
         .map_err(|e| ApiError::ServerError(format!("Unable to read pubkey cache: {:?}", e)))?;
 
+    validator_response_at_index(state, validator_pubkey, validator_index_opt, finalized)
+}
+
+/// Builds a `ValidatorResponse` for the validator with the given `validator_pubkey`, which is
+/// known to be at `validator_index_opt` in the given state (if at all).
+fn validator_response_at_index<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_pubkey: PublicKeyBytes,
+    validator_index_opt: Option<usize>,
+    finalized: bool,
+) -> Result<ValidatorResponse, ApiError> {
     if let Some(validator_index) = validator_index_opt {
         let balance = state.balances.get(validator_index).ok_or_else(|| {
             ApiError::ServerError(format!("Invalid balances index: {:?}", validator_index))
@@ -316,6 +1170,7 @@ fn validator_response_by_pubkey<E: EthSpec>(
             validator_index: Some(validator_index),
             balance: Some(*balance),
             validator: Some(validator),
+            finalized,
         })
     } else {
         Ok(ValidatorResponse {
@@ -323,11 +1178,14 @@ fn validator_response_by_pubkey<E: EthSpec>(
             validator_index: None,
             balance: None,
             validator: None,
+            finalized,
         })
     }
 }
 
-/// HTTP handler
+/// HTTP handler to return the committees of all slots of the requested `epoch`.
+///
+/// Returns a 400 for epochs beyond the next epoch, whose committees are not yet known.
 pub fn get_committees<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
@@ -335,6 +1193,21 @@ pub fn get_committees<T: BeaconChainTypes>(
     let query = UrlQuery::from_request(&req)?;
 
     let epoch = query.epoch()?;
+    if ctx.config.finalized_only {
+        ensure_finalized_slot(
+            &ctx.beacon_chain,
+            epoch.start_slot(T::EthSpec::slots_per_epoch()),
+        )?;
+    }
+
+    // The committees of epochs beyond the next epoch depend on randomness which is not yet known.
+    let next_epoch = ctx.beacon_chain.epoch()? + 1;
+    if epoch > next_epoch {
+        return Err(ApiError::BadRequest(format!(
+            "Unable to get committees for epoch {}: committees are only known up to epoch {}",
+            epoch, next_epoch
+        )));
+    }
 
     let mut state =
         get_state_for_epoch(&ctx.beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
@@ -359,7 +1232,7 @@ pub fn get_committees<T: BeaconChainTypes>(
         .collect::<Vec<_>>())
 }
 
-/// HTTP handler to return a `BeaconState` at a given `root` or `slot`.
+/// HTTP handler to return a `BeaconState` at a given `root`, `slot` or `state_id`.
 ///
 /// Will not return a state if the request slot is in the future. Will return states higher than
 /// the current head by skipping slots.
@@ -367,42 +1240,66 @@ pub fn get_state<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
 ) -> Result<StateResponse<T::EthSpec>, ApiError> {
-    let head_state = ctx.beacon_chain.head()?.beacon_state;
-
     let (key, value) = match UrlQuery::from_request(&req) {
         Ok(query) => {
             // We have *some* parameters, just check them.
-            let query_params = ["root", "slot"];
+            let query_params = ["root", "slot", "state_id"];
             query.first_of(&query_params)?
         }
+        Err(ApiError::BadRequest(_)) if ctx.config.finalized_only => {
+            // No parameters provided at all, use the finalized state.
+            (String::from("state_id"), String::from("finalized"))
+        }
         Err(ApiError::BadRequest(_)) => {
             // No parameters provided at all, use current slot.
-            (String::from("slot"), head_state.slot.to_string())
+            (
+                String::from("slot"),
+                ctx.beacon_chain.head_info()?.slot.to_string(),
+            )
         }
         Err(e) => {
             return Err(e);
         }
     };
 
-    let (root, state): (Hash256, BeaconState<T::EthSpec>) = match (key.as_ref(), value) {
-        ("slot", value) => state_at_slot(&ctx.beacon_chain, parse_slot(&value)?)?,
-        ("root", value) => {
-            let root = &parse_root(&value)?;
+    let state_at_root = |root: Hash256| -> Result<_, ApiError> {
+        let state = ctx
+            .beacon_chain
+            .store
+            .get_state(&root, None)?
+            .ok_or_else(|| ApiError::NotFound(format!("No state for root: {:?}", root)))?;
 
-            let state = ctx
-                .beacon_chain
-                .store
-                .get_state(root, None)?
-                .ok_or_else(|| ApiError::NotFound(format!("No state for root: {:?}", root)))?;
+        Ok((root, state))
+    };
 
-            (*root, state)
+    let (root, state): (Hash256, BeaconState<T::EthSpec>) = match (key.as_ref(), value) {
+        ("slot", value) => {
+            let slot = parse_slot(&value)?;
+            if ctx.config.finalized_only {
+                ensure_finalized_slot(&ctx.beacon_chain, slot)?;
+            }
+            state_at_slot(&ctx.beacon_chain, slot)?
+        }
+        ("root", value) => {
+            let root = parse_root(&value)?;
+            if ctx.config.finalized_only {
+                ensure_finalized_state(&ctx.beacon_chain, root)?;
+            }
+            state_at_root(root)?
+        }
+        ("state_id", value) => {
+            let state_id = parse_state_id(&value)?;
+            state_at_root(requested_state_root(&ctx, state_id)?)?
         }
         _ => return Err(ApiError::ServerError("Unexpected query parameter".into())),
     };
 
+    let finalized = is_finalized_state(&ctx.beacon_chain, root, state.slot)?;
+
     Ok(StateResponse {
         root,
         beacon_state: state,
+        finalized,
     })
 }
 
@@ -416,6 +1313,9 @@ pub fn get_state_root<T: BeaconChainTypes>(
 ) -> Result<Hash256, ApiError> {
     let slot_string = UrlQuery::from_request(&req)?.only_one("slot")?;
     let slot = parse_slot(&slot_string)?;
+    if ctx.config.finalized_only {
+        ensure_finalized_slot(&ctx.beacon_chain, slot)?;
+    }
 
     state_root_at_slot(&ctx.beacon_chain, slot, StateSkipConfig::WithStateRoots)
 }
@@ -430,6 +1330,62 @@ pub fn get_genesis_state<T: BeaconChainTypes>(
     state_at_slot(&ctx.beacon_chain, Slot::new(0)).map(|(_root, state)| state)
 }
 
+/// HTTP handler to return the rewards of the given validators for their attestations during the
+/// given epoch, replaying the reward computation of the following epoch transition.
+///
+/// Returns the rewards of all validators if no validator indices are given.
+pub fn post_attestation_rewards<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<AttestationRewards>, ApiError> {
+    let body = req.into_body();
+
+    let request = serde_json::from_slice::<AttestationRewardsRequest>(&body).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into AttestationRewardsRequest: {:?}",
+            e
+        ))
+    })?;
+
+    // Attestations during `epoch` are rewarded at the end of the following epoch, using the state
+    // at the last slot of that epoch.
+    let target_slot = (request.epoch + 2).start_slot(T::EthSpec::slots_per_epoch()) - 1;
+
+    if ctx.config.finalized_only {
+        ensure_finalized_slot(&ctx.beacon_chain, target_slot)?;
+    }
+
+    let (_root, mut state) = state_at_slot(&ctx.beacon_chain, target_slot)?;
+    let spec = &ctx.beacon_chain.spec;
+
+    // Processing the attestations of the previous epoch requires its committees.
+    state.build_all_committee_caches(spec)?;
+
+    let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
+    validator_statuses.process_attestations(&state, spec)?;
+
+    let deltas = get_attestation_component_deltas(&state, &validator_statuses, spec)
+        .map_err(|e| ApiError::ServerError(format!("Unable to compute rewards: {:?}", e)))?;
+
+    let validator_indices = if request.validator_indices.is_empty() {
+        (0..deltas.len() as u64).collect()
+    } else {
+        request.validator_indices
+    };
+
+    validator_indices
+        .into_iter()
+        .map(|validator_index| {
+            deltas
+                .get(validator_index as usize)
+                .map(|deltas| AttestationRewards::from_deltas(validator_index, deltas))
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!("Unknown validator index {}", validator_index))
+                })
+        })
+        .collect()
+}
+
 pub fn proposer_slashing<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
@@ -498,3 +1454,41 @@ pub fn attester_slashing<T: BeaconChainTypes>(
 
     Ok(true)
 }
+
+/// HTTP handler to return a single aggregate of all the attestations in the operation pool with
+/// the given `slot` and `data_root`.
+///
+/// Unlike `/validator/aggregate_attestation`, this only considers the operation pool, ignoring
+/// any pre-aggregated attestation from the naive aggregation pool.
+pub fn get_pool_attestations_aggregate<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Attestation<T::EthSpec>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let slot = query.slot()?;
+    let data_root = query
+        .first_of(&["data_root"])
+        .and_then(|(_key, value)| parse_root(&value))?;
+
+    ctx.beacon_chain
+        .op_pool
+        .get_aggregated_attestation(slot, data_root)
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No attestations with data root {:?} are known at slot {:?}",
+                data_root, slot
+            ))
+        })
+}
+
+/// Returns the number of each type of operation currently stored in the operation pool.
+pub fn get_pool_summary<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> PoolSummary {
+    let op_pool = &ctx.beacon_chain.op_pool;
+
+    PoolSummary {
+        attestations: op_pool.num_attestations() as u64,
+        attester_slashings: op_pool.num_attester_slashings() as u64,
+        proposer_slashings: op_pool.num_proposer_slashings() as u64,
+        voluntary_exits: op_pool.num_voluntary_exits() as u64,
+    }
+}
@@ -41,6 +41,27 @@ pub struct Config {
     /// If something else than "", a 'Access-Control-Allow-Origin' header will be present in
     /// responses.  Put *, to allow any origin.
     pub allow_origin: String,
+    /// If `true`, all messages published to the network by the REST API will also be stored in a
+    /// `NetworkMessageBuffer`. This is intended for testing only, the buffer is never pruned.
+    pub record_network_messages: bool,
+    /// If `Some`, block production and duties will be refused whilst the head is more than this
+    /// many slots behind the current slot.
+    pub sync_tolerance: Option<u64>,
+    /// If `true`, requests for states or blocks later than the finalized checkpoint will be
+    /// refused. Intended for replicas which only serve finalized data.
+    pub finalized_only: bool,
+    /// If `false`, requests to publish or import blocks will be refused.
+    pub enable_block_publishing: bool,
+    /// If `false`, requests to publish attestations or aggregates will be refused.
+    pub enable_attestation_publishing: bool,
+    /// If `false`, requests to publish proposer or attester slashings will be refused.
+    pub enable_slashing_publishing: bool,
+    /// If `true`, the genesis and spec endpoints are served before the chain has reached genesis,
+    /// even though the genesis data may not yet be final. Intended for bootstrapping devnets.
+    pub allow_insecure_genesis_sync: bool,
+    /// If `true`, requests to prune the operation pool will be served. Disabled by default since
+    /// the endpoint mutates the node and the API is unauthenticated.
+    pub enable_op_pool_pruning: bool,
 }
 
 impl Default for Config {
@@ -50,6 +71,14 @@ impl Default for Config {
             listen_address: Ipv4Addr::new(127, 0, 0, 1),
             port: 5052,
             allow_origin: "".to_string(),
+            record_network_messages: false,
+            sync_tolerance: None,
+            finalized_only: false,
+            enable_block_publishing: true,
+            enable_attestation_publishing: true,
+            enable_slashing_publishing: true,
+            allow_insecure_genesis_sync: false,
+            enable_op_pool_pruning: false,
         }
     }
 }
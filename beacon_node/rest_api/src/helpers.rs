@@ -1,9 +1,10 @@
-use crate::{ApiError, NetworkChannel};
-use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use crate::{metrics, ApiError, NetworkChannel};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, StateSkipConfig};
 use bls::PublicKeyBytes;
 use eth2_libp2p::PubsubMessage;
 use itertools::process_results;
 use network::NetworkMessage;
+use rest_types::{PeerDirection, PeerState, ValidatorId, ValidatorStatus};
 use ssz::Decode;
 use store::iter::AncestorIter;
 use types::{
@@ -76,6 +77,194 @@ pub fn parse_root(string: &str) -> Result<Hash256, ApiError> {
     }
 }
 
+/// Identifies a `BeaconState` in a request.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StateId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(Slot),
+    Root(Hash256),
+}
+
+impl StateId {
+    /// The name of the variant of `self`, as used to label metrics.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            StateId::Head => "head",
+            StateId::Genesis => "genesis",
+            StateId::Finalized => "finalized",
+            StateId::Justified => "justified",
+            StateId::Slot(_) => "slot",
+            StateId::Root(_) => "root",
+        }
+    }
+}
+
+/// Parse a `StateId` from a string.
+///
+/// E.g., `"head"`, `"genesis"`, `"finalized"`, `"justified"`, `"42"` or a `0x` prefixed state root.
+pub fn parse_state_id(string: &str) -> Result<StateId, ApiError> {
+    match string {
+        "head" => Ok(StateId::Head),
+        "genesis" => Ok(StateId::Genesis),
+        "finalized" => Ok(StateId::Finalized),
+        "justified" => Ok(StateId::Justified),
+        other if other.starts_with("0x") => parse_root(other).map(StateId::Root),
+        other => other
+            .parse::<u64>()
+            .map(|slot| StateId::Slot(slot.into()))
+            .map_err(|_| {
+                ApiError::BadRequest(format!(
+                    "Invalid state id {:?}, must be one of \"head\", \"genesis\", \"finalized\", \
+                 \"justified\", a slot or a 0x prefixed state root",
+                    other
+                ))
+            }),
+    }
+}
+
+/// Returns the root of the `BeaconState` identified by `state_id`.
+pub fn state_root_for_state_id<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_id: StateId,
+) -> Result<Hash256, ApiError> {
+    let checkpoint_state_root = |block_root: Hash256| -> Result<Hash256, ApiError> {
+        // The checkpoints of the genesis state have a zero root, which refers to the genesis
+        // block.
+        let block_root = if block_root == Hash256::zero() {
+            beacon_chain.genesis_block_root
+        } else {
+            block_root
+        };
+
+        beacon_chain
+            .get_block(&block_root)?
+            .map(|block| block.state_root())
+            .ok_or_else(|| {
+                ApiError::NotFound(format!("Unable to find checkpoint block {:?}", block_root))
+            })
+    };
+
+    metrics::inc_counter_vec(
+        &metrics::BEACON_HTTP_API_STATE_ID_RESOLUTIONS_TOTAL,
+        &[state_id.variant_name()],
+    );
+
+    match state_id {
+        StateId::Head => Ok(beacon_chain.head_info()?.state_root),
+        StateId::Genesis => {
+            state_root_at_slot(beacon_chain, Slot::new(0), StateSkipConfig::WithStateRoots)
+        }
+        StateId::Finalized => {
+            checkpoint_state_root(beacon_chain.head_info()?.finalized_checkpoint.root)
+        }
+        StateId::Justified => {
+            checkpoint_state_root(beacon_chain.head_info()?.current_justified_checkpoint.root)
+        }
+        StateId::Slot(slot) => {
+            state_root_at_slot(beacon_chain, slot, StateSkipConfig::WithStateRoots)
+        }
+        StateId::Root(root) => Ok(root),
+    }
+}
+
+/// Returns the start slot of the finalized epoch of the canonical chain.
+pub fn finalized_slot<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+) -> Result<Slot, ApiError> {
+    Ok(beacon_chain
+        .head_info()?
+        .finalized_checkpoint
+        .epoch
+        .start_slot(T::EthSpec::slots_per_epoch()))
+}
+
+/// Returns `true` if the block with the given `block_root` and `slot` is finalized, i.e., it is no
+/// later than the finalized slot and is on the canonical chain.
+pub fn is_finalized_block<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_root: Hash256,
+    slot: Slot,
+) -> Result<bool, ApiError> {
+    if slot > finalized_slot(beacon_chain)? {
+        return Ok(false);
+    }
+
+    Ok(block_root_at_slot(beacon_chain, slot)? == Some(block_root))
+}
+
+/// As per `is_finalized_block`, for the state with the given `state_root` and `slot`.
+pub fn is_finalized_state<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_root: Hash256,
+    slot: Slot,
+) -> Result<bool, ApiError> {
+    if slot > finalized_slot(beacon_chain)? {
+        return Ok(false);
+    }
+
+    let canonical_root = process_results(beacon_chain.rev_iter_state_roots()?, |iter| {
+        iter.take_while(|(_, state_slot)| *state_slot >= slot)
+            .find(|(_, state_slot)| *state_slot == slot)
+            .map(|(root, _)| root)
+    })?;
+
+    Ok(canonical_root == Some(state_root))
+}
+
+/// Returns an error if `slot` is later than the start slot of the finalized epoch.
+pub fn ensure_finalized_slot<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    slot: Slot,
+) -> Result<(), ApiError> {
+    let finalized_slot = finalized_slot(beacon_chain)?;
+
+    if slot > finalized_slot {
+        Err(ApiError::BadRequest(format!(
+            "Only finalized data is served, slot {} is later than the finalized slot {}",
+            slot, finalized_slot
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// As per `ensure_finalized_slot`, for the state with the given `state_root`.
+///
+/// Unknown states are permitted, since any attempt to read them will fail regardless.
+pub fn ensure_finalized_state<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_root: Hash256,
+) -> Result<(), ApiError> {
+    match beacon_chain.store.load_state_slot(&state_root)? {
+        Some(slot) => ensure_finalized_slot(beacon_chain, slot),
+        None => Ok(()),
+    }
+}
+
+/// As per `ensure_finalized_slot`, for the block with the given `block_root`.
+///
+/// Only blocks known to fork choice are checked, so the block does not need to be loaded. All
+/// other blocks are either unknown or have been pruned at finalization.
+pub fn ensure_finalized_block<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_root: Hash256,
+) -> Result<(), ApiError> {
+    let slot_opt = beacon_chain
+        .fork_choice
+        .read()
+        .proto_array()
+        .get_block(&block_root)
+        .map(|block| block.slot);
+
+    match slot_opt {
+        Some(slot) => ensure_finalized_slot(beacon_chain, slot),
+        None => Ok(()),
+    }
+}
+
 /// Parse a PublicKey from a `0x` prefixed hex string
 pub fn parse_pubkey_bytes(string: &str) -> Result<PublicKeyBytes, ApiError> {
     const PREFIX: &str = "0x";
@@ -93,6 +282,39 @@ pub fn parse_pubkey_bytes(string: &str) -> Result<PublicKeyBytes, ApiError> {
     }
 }
 
+/// Parse a `ValidatorId` from either a validator index or a `0x` prefixed public key.
+///
+/// E.g., `"42"` or `"0x9324...f74a"`.
+pub fn parse_validator_id(string: &str) -> Result<ValidatorId, ApiError> {
+    if string.starts_with("0x") {
+        parse_pubkey_bytes(string).map(ValidatorId::PublicKey)
+    } else {
+        string.parse::<u64>().map(ValidatorId::Index).map_err(|_| {
+            ApiError::BadRequest(format!(
+                "Invalid validator id {:?}, must be an index or a 0x prefixed public key",
+                string
+            ))
+        })
+    }
+}
+
+/// Parse a `ValidatorStatus`.
+///
+/// E.g., `"active_ongoing"`
+pub fn parse_validator_status(string: &str) -> Result<ValidatorStatus, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Parses a `PeerState` from its snake case string representation (e.g., `connected`).
+pub fn parse_peer_state(string: &str) -> Result<PeerState, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Parses a `PeerDirection` from its snake case string representation (e.g., `inbound`).
+pub fn parse_peer_direction(string: &str) -> Result<PeerDirection, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
 /// Returns the root of the `SignedBeaconBlock` in the canonical chain of `beacon_chain` at the given
 /// `slot`, if possible.
 ///
@@ -111,6 +333,64 @@ pub fn block_root_at_slot<T: BeaconChainTypes>(
     )?)
 }
 
+/// Like `block_root_at_slot`, but resolves the `target` slot on the chain ending at the block
+/// with root `head`, rather than the canonical chain.
+///
+/// Returns an `ApiError::NotFound` if the `head` block is unknown.
+pub fn block_root_at_slot_from_head<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    head: Hash256,
+    target: Slot,
+) -> Result<Option<Hash256>, ApiError> {
+    let iter = match beacon_chain.rev_iter_block_roots_from(head) {
+        Ok(iter) => iter,
+        Err(BeaconChainError::MissingBeaconBlock(_)) => {
+            return Err(ApiError::NotFound(format!(
+                "Unable to find SignedBeaconBlock with root {:?}",
+                head
+            )))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(process_results(iter, |iter| {
+        iter.take_while(|(_, slot)| *slot >= target)
+            .find(|(_, slot)| *slot == target)
+            .map(|(root, _)| root)
+    })?)
+}
+
+/// Returns the root and the block `distance` parents before the block with root `root`, following
+/// parent roots rather than the canonical chain. A `distance` of zero returns the block itself.
+///
+/// Returns `None` if the genesis block is reached before the walk is complete.
+pub fn block_ancestor<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    root: Hash256,
+    distance: u64,
+) -> Result<Option<(Hash256, SignedBeaconBlock<T::EthSpec>)>, ApiError> {
+    let get_block = |block_root: Hash256| {
+        beacon_chain.store.get_block(&block_root)?.ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Unable to find SignedBeaconBlock for root {:?}",
+                block_root
+            ))
+        })
+    };
+
+    let mut block_root = root;
+    let mut block = get_block(block_root)?;
+    for _ in 0..distance {
+        if block.slot() == beacon_chain.spec.genesis_slot {
+            return Ok(None);
+        }
+        block_root = block.parent_root();
+        block = get_block(block_root)?;
+    }
+
+    Ok(Some((block_root, block)))
+}
+
 /// Returns a `BeaconState` and it's root in the canonical chain of `beacon_chain` at the given
 /// `slot`, if possible.
 ///
@@ -146,7 +426,10 @@ pub fn state_root_at_slot<T: BeaconChainTypes>(
     slot: Slot,
     config: StateSkipConfig,
 ) -> Result<Hash256, ApiError> {
-    let head_state = &beacon_chain.head()?.beacon_state;
+    // Use a single snapshot of the head, so a concurrent change of head cannot be observed
+    // part-way through.
+    let head = beacon_chain.head_snapshot()?;
+    let head_state = &head.beacon_state;
     let current_slot = beacon_chain
         .slot()
         .map_err(|_| ApiError::ServerError("Unable to read slot clock".to_string()))?;
@@ -170,7 +453,7 @@ pub fn state_root_at_slot<T: BeaconChainTypes>(
         // 2. The request slot is the same as the best block (head) slot.
         //
         // The head state root is stored in memory, return a reference.
-        Ok(beacon_chain.head()?.beacon_state_root)
+        Ok(head.beacon_state_root)
     } else if head_state.slot > slot {
         // 3. The request slot is prior to the head slot.
         //
@@ -190,7 +473,7 @@ pub fn state_root_at_slot<T: BeaconChainTypes>(
         //
         // Use `per_slot_processing` to advance the head state to the present slot,
         // assuming that all slots do not contain a block (i.e., they are skipped slots).
-        let mut state = beacon_chain.head()?.beacon_state;
+        let mut state = head_state.clone();
         let spec = &T::EthSpec::default_spec();
 
         let skip_state_root = match config {
@@ -250,6 +533,60 @@ mod test {
         assert!(parse_root("0x00").is_err());
     }
 
+    #[test]
+    fn parse_state_id_works() {
+        assert_eq!(parse_state_id("head"), Ok(StateId::Head));
+        assert_eq!(parse_state_id("genesis"), Ok(StateId::Genesis));
+        assert_eq!(parse_state_id("finalized"), Ok(StateId::Finalized));
+        assert_eq!(parse_state_id("justified"), Ok(StateId::Justified));
+        assert_eq!(parse_state_id("42"), Ok(StateId::Slot(Slot::new(42))));
+        assert_eq!(
+            parse_state_id("0x000000000000000000000000000000000000000000000000000000000000002a"),
+            Ok(StateId::Root(Hash256::from_low_u64_be(42)))
+        );
+        assert!(parse_state_id("banana").is_err());
+        assert!(parse_state_id("0x00").is_err());
+        assert!(parse_state_id("").is_err());
+    }
+
+    #[test]
+    fn parse_validator_id_works() {
+        assert_eq!(parse_validator_id("0"), Ok(ValidatorId::Index(0)));
+        assert_eq!(parse_validator_id("42"), Ok(ValidatorId::Index(42)));
+        assert_eq!(
+            parse_validator_id(&format!("0x{}", "00".repeat(48))),
+            Ok(ValidatorId::PublicKey(PublicKeyBytes::empty()))
+        );
+        assert!(parse_validator_id("-1").is_err());
+        assert!(parse_validator_id("0x00").is_err());
+        assert!(parse_validator_id("").is_err());
+    }
+
+    #[test]
+    fn parse_validator_status_works() {
+        assert_eq!(
+            parse_validator_status("active_ongoing"),
+            Ok(ValidatorStatus::ActiveOngoing)
+        );
+        assert_eq!(
+            parse_validator_status("exited_slashed"),
+            Ok(ValidatorStatus::ExitedSlashed)
+        );
+        assert!(parse_validator_status("active").is_err());
+        assert!(parse_validator_status("").is_err());
+    }
+
+    #[test]
+    fn parse_peer_filters_works() {
+        assert_eq!(parse_peer_state("connecting"), Ok(PeerState::Connecting));
+        assert_eq!(
+            parse_peer_direction("outbound"),
+            Ok(PeerDirection::Outbound)
+        );
+        assert!(parse_peer_state("Connected").is_err());
+        assert!(parse_peer_direction("").is_err());
+    }
+
     #[test]
     fn parse_slot_works() {
         assert_eq!(parse_slot("0"), Ok(Slot::new(0)));
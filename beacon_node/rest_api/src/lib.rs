@@ -13,35 +13,105 @@ mod node;
 mod url_query;
 mod validator;
 
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use bus::Bus;
+use beacon_chain::{events::EventBus, BeaconChain, BeaconChainTypes};
 use client_network::NetworkMessage;
 pub use config::ApiEncodingFormat;
 use eth2_config::Eth2Config;
-use eth2_libp2p::NetworkGlobals;
+use eth2_libp2p::{NetworkGlobals, PubsubMessage};
 use futures::future::TryFutureExt;
 use hyper::server::conn::AddrStream;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Server};
+use lru::LruCache;
 use parking_lot::Mutex;
-use rest_types::ApiError;
+use rest_types::{ApiError, GenesisData};
 use slog::{info, warn};
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use types::SignedBeaconBlockHash;
+use types::EthSpec;
 use url_query::UrlQuery;
 
 pub use crate::helpers::parse_pubkey_bytes;
 pub use config::Config;
 pub use router::Context;
 
-pub type NetworkChannel<T> = mpsc::UnboundedSender<NetworkMessage<T>>;
+/// The number of state roots for which the `Fork` is cached.
+const FORK_CACHE_SIZE: usize = 64;
+/// The number of state roots for which the `FinalityCheckpoints` are cached.
+const FINALITY_CHECKPOINTS_CACHE_SIZE: usize = 64;
+/// The number of epochs for which validator duties are cached.
+const DUTIES_CACHE_SIZE: usize = 4;
+/// The number of `(state_root, pubkey)` pairs for which the validator index is cached.
+const VALIDATOR_INDEX_CACHE_SIZE: usize = 4_096;
+/// The maximum number of validators returned in a single page by `/beacon/validators/page`.
+pub const MAX_VALIDATORS_PAGE_LIMIT: u64 = 1_024;
+/// The maximum number of epochs which may be evaluated by `/lighthouse/validator/effectiveness`.
+pub const MAX_EFFECTIVENESS_EPOCHS: u64 = 64;
+/// The maximum number of blocks returned by `/lighthouse/recent_blocks`.
+pub const MAX_RECENT_BLOCKS: u64 = 1_024;
+/// The maximum number of epochs which may be requested from `/lighthouse/validator/duty_history`.
+pub const MAX_DUTY_HISTORY_EPOCHS: u64 = 64;
+/// The maximum number of state roots which may be requested from `/beacon/validators/states`.
+pub const MAX_VALIDATOR_STATES: usize = 16;
+/// The maximum number of slots which may be requested from `/beacon/blocks/attestations`.
+pub const MAX_BLOCK_ATTESTATIONS_SLOTS: u64 = 1_024;
+
+/// A channel to the network service which optionally records each published message in a
+/// `NetworkMessageBuffer`.
+#[derive(Clone)]
+pub struct NetworkChannel<T: EthSpec> {
+    sender: mpsc::UnboundedSender<NetworkMessage<T>>,
+    buffer: Option<NetworkMessageBuffer<T>>,
+}
+
+impl<T: EthSpec> NetworkChannel<T> {
+    pub fn new(
+        sender: mpsc::UnboundedSender<NetworkMessage<T>>,
+        buffer: Option<NetworkMessageBuffer<T>>,
+    ) -> Self {
+        Self { sender, buffer }
+    }
+
+    /// Send `message` to the network service, recording it first if `self` has a buffer.
+    pub fn send(
+        &self,
+        message: NetworkMessage<T>,
+    ) -> Result<(), mpsc::error::SendError<NetworkMessage<T>>> {
+        if let (Some(buffer), NetworkMessage::Publish { messages }) = (&self.buffer, &message) {
+            buffer.0.lock().extend(messages.iter().cloned());
+        }
+
+        self.sender.send(message)
+    }
+}
+
+/// An in-memory record of the messages published to the network by the REST API.
+///
+/// Allows tests to determine what was published without racing the network service for messages
+/// on the network channel.
+#[derive(Clone, Default)]
+pub struct NetworkMessageBuffer<T: EthSpec>(Arc<Mutex<Vec<PubsubMessage<T>>>>);
+
+impl<T: EthSpec> NetworkMessageBuffer<T> {
+    /// Returns all messages that have been published, in the order they were sent.
+    pub fn messages(&self) -> Vec<PubsubMessage<T>> {
+        self.0.lock().clone()
+    }
+
+    /// Remove all messages from the buffer.
+    pub fn clear(&self) {
+        self.0.lock().clear()
+    }
+}
 
 pub struct NetworkInfo<T: BeaconChainTypes> {
     pub network_globals: Arc<NetworkGlobals<T::EthSpec>>,
-    pub network_chan: NetworkChannel<T::EthSpec>,
+    pub network_chan: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
+    /// Must be `Some` if `Config::record_network_messages` is `true`.
+    pub network_message_buffer: Option<NetworkMessageBuffer<T::EthSpec>>,
 }
 
 // Allowing more than 7 arguments.
@@ -54,27 +124,52 @@ pub fn start_server<T: BeaconChainTypes>(
     db_path: PathBuf,
     freezer_db_path: PathBuf,
     eth2_config: Eth2Config,
-    events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
-) -> Result<SocketAddr, hyper::Error> {
+    events: Arc<EventBus<T::EthSpec>>,
+) -> Result<(SocketAddr, Arc<Context<T>>), String> {
     let log = executor.log();
     let eth2_config = Arc::new(eth2_config);
 
+    let head_info = beacon_chain
+        .head_info()
+        .map_err(|e| format!("Unable to read genesis from head: {:?}", e))?;
+    let genesis_data = GenesisData {
+        genesis_time: head_info.genesis_time,
+        genesis_validators_root: head_info.genesis_validators_root,
+        genesis_fork_version: beacon_chain.spec.genesis_fork_version,
+    };
+
     let context = Arc::new(Context {
         executor: executor.clone(),
         config: config.clone(),
         beacon_chain,
         network_globals: network_info.network_globals.clone(),
-        network_chan: network_info.network_chan,
+        network_chan: NetworkChannel::new(
+            network_info.network_chan,
+            network_info.network_message_buffer,
+        ),
         eth2_config,
         log: log.clone(),
         db_path,
         freezer_db_path,
         events,
+        genesis_data,
+        fork_cache: Mutex::new(LruCache::new(FORK_CACHE_SIZE)),
+        fork_state_loads: AtomicU64::new(0),
+        finality_checkpoints_cache: Mutex::new(LruCache::new(FINALITY_CHECKPOINTS_CACHE_SIZE)),
+        finality_checkpoints_state_loads: AtomicU64::new(0),
+        sync_observation: Mutex::new(None),
+        duties_cache: Mutex::new(LruCache::new(DUTIES_CACHE_SIZE)),
+        duties_cache_misses: AtomicU64::new(0),
+        validator_index_cache: Mutex::new(LruCache::new(VALIDATOR_INDEX_CACHE_SIZE)),
+        validator_index_cache_hits: AtomicU64::new(0),
+        validator_index_cache_misses: AtomicU64::new(0),
+        validator_pubkey_scans: AtomicU64::new(0),
     });
 
     // Define the function that will build the request handler.
+    let service_context = context.clone();
     let make_service = make_service_fn(move |_socket: &AddrStream| {
-        let ctx = context.clone();
+        let ctx = service_context.clone();
 
         async move {
             Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
@@ -123,5 +218,5 @@ pub fn start_server<T: BeaconChainTypes>(
 
     executor.spawn_without_exit(server_future, "http");
 
-    Ok(actual_listen_addr)
+    Ok((actual_listen_addr, context))
 }
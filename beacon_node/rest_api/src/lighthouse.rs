@@ -1,11 +1,26 @@
 //! This contains a collection of lighthouse specific HTTP endpoints.
 
-use crate::{ApiError, Context};
+use crate::beacon::get_pool_summary;
+use crate::helpers::{ensure_finalized_slot, parse_validator_id, state_at_slot};
+use crate::validator::return_validator_duties;
+use crate::{
+    ApiError, Context, UrlQuery, MAX_DUTY_HISTORY_EPOCHS, MAX_EFFECTIVENESS_EPOCHS,
+    MAX_RECENT_BLOCKS,
+};
 use beacon_chain::BeaconChainTypes;
 use eth2_libp2p::PeerInfo;
+use hyper::Request;
+use itertools::{process_results, Itertools};
+use rest_types::{
+    AttestationPoolKind, BlockImportResult, CurrentSlotResponse, EpochEffectiveness,
+    EpochValidatorDuty, ExportedAttestation, PoolSummary, RecentBlock, ValidatorEffectiveness,
+    ValidatorId,
+};
 use serde::Serialize;
+use ssz::Decode;
+use state_processing::per_epoch_processing::ValidatorStatuses;
 use std::sync::Arc;
-use types::EthSpec;
+use types::{ChainSpec, Epoch, EthSpec, SignedBeaconBlock};
 
 /// Returns all known peers and corresponding information
 pub fn peers<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Vec<Peer<T::EthSpec>>, ApiError> {
@@ -37,6 +52,333 @@ pub fn connected_peers<T: BeaconChainTypes>(
         .collect())
 }
 
+/// Returns the current slot of the node, as computed from the genesis time.
+pub fn current_slot<T: BeaconChainTypes>(
+    ctx: Arc<Context<T>>,
+) -> Result<CurrentSlotResponse, ApiError> {
+    Ok(CurrentSlotResponse {
+        slot: ctx.beacon_chain.slot()?,
+        genesis_time: ctx.beacon_chain.head_info()?.genesis_time,
+    })
+}
+
+/// Returns the root and slot of the last `count` blocks in the canonical chain, starting with the
+/// head block and in order of descending slot.
+///
+/// Fewer than `count` blocks are returned if the chain does not contain that many blocks.
+pub fn recent_blocks<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<RecentBlock>, ApiError> {
+    let count = UrlQuery::from_request(&req)?
+        .u64_opt("count")?
+        .ok_or_else(|| ApiError::BadRequest("The count query parameter is required".into()))?;
+
+    if count > MAX_RECENT_BLOCKS {
+        return Err(ApiError::BadRequest(format!(
+            "The count must not exceed {}",
+            MAX_RECENT_BLOCKS
+        )));
+    }
+
+    // Skipped slots repeat the root of the prior block, so coalesce each run of identical roots
+    // into the lowest slot of the run, which is the slot of the block itself.
+    process_results(ctx.beacon_chain.rev_iter_block_roots()?, |iter| {
+        iter.coalesce(|later, earlier| {
+            if later.0 == earlier.0 {
+                Ok(earlier)
+            } else {
+                Err((later, earlier))
+            }
+        })
+        .take(count as usize)
+        .map(|(root, slot)| RecentBlock { root, slot })
+        .collect::<Vec<_>>()
+    })
+    .map_err(Into::into)
+}
+
+/// Returns every attestation in the operation pool and the naive aggregation pool, annotated
+/// with the pool it was found in and some metadata useful for debugging.
+pub fn export_attestations<T: BeaconChainTypes>(
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<ExportedAttestation<T::EthSpec>>, ApiError> {
+    let chain = &ctx.beacon_chain;
+    let current_slot = chain.slot()?;
+
+    let mut exported = chain
+        .op_pool
+        .get_all_attestations()
+        .into_iter()
+        .map(|attestation| {
+            ExportedAttestation::new(AttestationPoolKind::OpPool, attestation, current_slot)
+        })
+        .collect::<Vec<_>>();
+
+    exported.extend(
+        chain
+            .naive_aggregation_pool
+            .read()
+            .iter()
+            .map(|attestation| {
+                ExportedAttestation::new(
+                    AttestationPoolKind::Naive,
+                    attestation.clone(),
+                    current_slot,
+                )
+            }),
+    );
+
+    Ok(exported)
+}
+
+/// Prunes the operation pool against the head state.
+///
+/// Returns the number of operations of each type that were removed from the pool.
+pub fn prune_op_pool<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<PoolSummary, ApiError> {
+    let chain = &ctx.beacon_chain;
+    let head = chain.head_snapshot()?;
+
+    let before = get_pool_summary(ctx.clone());
+    chain
+        .op_pool
+        .prune_all(&head.beacon_state, head.beacon_state.fork);
+    chain
+        .op_pool
+        .prune_voluntary_exits_at_head(&head.beacon_state, &chain.spec);
+    let after = get_pool_summary(ctx.clone());
+
+    Ok(PoolSummary {
+        attestations: before.attestations.saturating_sub(after.attestations),
+        attester_slashings: before
+            .attester_slashings
+            .saturating_sub(after.attester_slashings),
+        proposer_slashings: before
+            .proposer_slashings
+            .saturating_sub(after.proposer_slashings),
+        voluntary_exits: before.voluntary_exits.saturating_sub(after.voluntary_exits),
+    })
+}
+
+/// Imports an SSZ-encoded list of blocks, in order, returning the outcome for each block.
+///
+/// The blocks are fully verified but, unlike blocks from local validators, they are not required
+/// to pass gossip verification and are not published to the network.
+pub fn import_blocks<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<BlockImportResult>, ApiError> {
+    let blocks = Vec::<SignedBeaconBlock<T::EthSpec>>::from_ssz_bytes(req.body()).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse SSZ list of SignedBeaconBlock: {:?}",
+            e
+        ))
+    })?;
+
+    let results = blocks
+        .into_iter()
+        .map(|block| BlockImportResult {
+            block_root: block.canonical_root(),
+            slot: block.slot(),
+            error: ctx
+                .beacon_chain
+                .process_block(block)
+                .err()
+                .map(|e| format!("{:?}", e)),
+        })
+        .collect::<Vec<_>>();
+
+    if results.iter().any(BlockImportResult::is_imported) {
+        ctx.beacon_chain.fork_choice()?;
+    }
+
+    Ok(results)
+}
+
+/// Returns the effectiveness of the attestations of the validator identified by the `id` query
+/// parameter during the last `epochs` epochs for which attestation inclusion is final.
+///
+/// The attestations of some epoch may be included in blocks until the end of the following epoch,
+/// so the most recent epoch evaluated is the one prior to the last complete epoch of the head.
+pub fn validator_effectiveness<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<ValidatorEffectiveness, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let validator_id = query
+        .first_of(&["id"])
+        .and_then(|(_key, value)| parse_validator_id(&value))?;
+    let num_epochs = query
+        .u64_opt("epochs")?
+        .ok_or_else(|| ApiError::BadRequest("The epochs query parameter is required".into()))?;
+
+    if num_epochs == 0 || num_epochs > MAX_EFFECTIVENESS_EPOCHS {
+        return Err(ApiError::BadRequest(format!(
+            "The number of epochs must be between 1 and {}",
+            MAX_EFFECTIVENESS_EPOCHS
+        )));
+    }
+
+    let chain = &ctx.beacon_chain;
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+
+    let validator_index = match validator_id {
+        ValidatorId::Index(index) => index,
+        ValidatorId::PublicKey(pubkey) => chain
+            .validator_index(&pubkey)?
+            .ok_or_else(|| ApiError::NotFound(format!("Unknown validator {:?}", pubkey)))?
+            as u64,
+    };
+
+    let complete_epochs = (chain.head_info()?.slot + 1).epoch(slots_per_epoch);
+    if complete_epochs < 2 {
+        return Err(ApiError::ServiceUnavailable(
+            "No epochs can be evaluated until the head has completed two epochs".into(),
+        ));
+    }
+    let end_epoch = complete_epochs - 2;
+    let start_epoch = (end_epoch + 1).saturating_sub(num_epochs);
+
+    if ctx.config.finalized_only {
+        ensure_finalized_slot(chain, (end_epoch + 2).start_slot(slots_per_epoch) - 1)?;
+    }
+
+    let spec = &chain.spec;
+    let epochs = (start_epoch.as_u64()..=end_epoch.as_u64())
+        .map(Epoch::new)
+        .map(|epoch| {
+            // Attestations during `epoch` are final at the last slot of the following epoch.
+            let target_slot = (epoch + 2).start_slot(slots_per_epoch) - 1;
+            let (_root, mut state) = state_at_slot(chain, target_slot)?;
+
+            // Processing the attestations of the previous epoch requires its committees.
+            state.build_all_committee_caches(spec)?;
+
+            let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
+            validator_statuses.process_attestations(&state, spec)?;
+
+            let status = validator_statuses
+                .statuses
+                .get(validator_index as usize)
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!("Unknown validator index {}", validator_index))
+                })?;
+
+            Ok(EpochEffectiveness::from_validator_status(epoch, status))
+        })
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(ValidatorEffectiveness::new(validator_index, epochs))
+}
+
+/// Returns the duties of the validator identified by the `id` query parameter during each of the
+/// last `epochs` epochs, ending at the current epoch, in order of ascending epoch.
+///
+/// Epochs prior to genesis are omitted.
+pub fn validator_duty_history<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<EpochValidatorDuty>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let validator_id = query
+        .first_of(&["id"])
+        .and_then(|(_key, value)| parse_validator_id(&value))?;
+    let num_epochs = query
+        .u64_opt("epochs")?
+        .ok_or_else(|| ApiError::BadRequest("The epochs query parameter is required".into()))?;
+
+    if num_epochs == 0 || num_epochs > MAX_DUTY_HISTORY_EPOCHS {
+        return Err(ApiError::BadRequest(format!(
+            "The number of epochs must be between 1 and {}",
+            MAX_DUTY_HISTORY_EPOCHS
+        )));
+    }
+
+    let chain = &ctx.beacon_chain;
+    let validator_pubkey = match validator_id {
+        ValidatorId::PublicKey(pubkey) => pubkey,
+        ValidatorId::Index(index) => chain
+            .validator_pubkey(index as usize)?
+            .ok_or_else(|| ApiError::NotFound(format!("Unknown validator index {}", index)))?
+            .into(),
+    };
+
+    let end_epoch = chain.epoch()?;
+    let start_epoch = (end_epoch + 1).saturating_sub(num_epochs);
+
+    (start_epoch.as_u64()..=end_epoch.as_u64())
+        .map(Epoch::new)
+        .map(|epoch| {
+            let duty = return_validator_duties(&ctx, epoch, vec![validator_pubkey.clone()])?
+                .pop()
+                .ok_or_else(|| {
+                    ApiError::ServerError(format!("No duties were computed for epoch {}", epoch))
+                })?;
+
+            Ok(EpochValidatorDuty { epoch, duty })
+        })
+        .collect()
+}
+
+/// Checks the internal consistency of the `spec` in use by the node, returning a description of
+/// each invariant that is violated.
+///
+/// An empty list indicates that the spec is self-consistent.
+pub fn validate_spec<E: EthSpec>(spec: &ChainSpec) -> Vec<String> {
+    let mut violations = vec![];
+    let mut check = |ok: bool, description: &str| {
+        if !ok {
+            violations.push(description.to_string())
+        }
+    };
+
+    check(E::slots_per_epoch() > 0, "slots_per_epoch must be non-zero");
+    check(
+        E::slots_per_historical_root() as u64 % E::slots_per_epoch().max(1) == 0,
+        "slots_per_historical_root must be a multiple of slots_per_epoch",
+    );
+    check(
+        spec.milliseconds_per_slot > 0,
+        "milliseconds_per_slot must be non-zero",
+    );
+    check(
+        spec.max_committees_per_slot > 0,
+        "max_committees_per_slot must be non-zero",
+    );
+    check(
+        spec.target_committee_size > 0,
+        "target_committee_size must be non-zero",
+    );
+    check(
+        spec.shuffle_round_count > 0,
+        "shuffle_round_count must be non-zero",
+    );
+    check(
+        spec.churn_limit_quotient > 0,
+        "churn_limit_quotient must be non-zero",
+    );
+    check(
+        spec.min_seed_lookahead <= spec.max_seed_lookahead,
+        "min_seed_lookahead must not exceed max_seed_lookahead",
+    );
+    check(
+        spec.effective_balance_increment > 0
+            && spec.max_effective_balance % spec.effective_balance_increment.max(1) == 0,
+        "max_effective_balance must be a non-zero multiple of effective_balance_increment",
+    );
+    check(
+        spec.ejection_balance < spec.max_effective_balance,
+        "ejection_balance must be less than max_effective_balance",
+    );
+    check(
+        spec.attestation_subnet_count > 0,
+        "attestation_subnet_count must be non-zero",
+    );
+
+    violations
+}
+
 /// Information returned by `peers` and `connected_peers`.
 #[derive(Clone, Debug, Serialize)]
 #[serde(bound = "T: EthSpec")]
@@ -46,3 +388,36 @@ pub struct Peer<T: EthSpec> {
     /// The PeerInfo associated with the peer.
     peer_info: PeerInfo<T>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Epoch, MainnetEthSpec, MinimalEthSpec};
+
+    #[test]
+    fn default_specs_are_valid() {
+        assert_eq!(
+            validate_spec::<MainnetEthSpec>(&MainnetEthSpec::default_spec()),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            validate_spec::<MinimalEthSpec>(&MinimalEthSpec::default_spec()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn corrupted_spec_reports_violations() {
+        let mut spec = MainnetEthSpec::default_spec();
+        spec.milliseconds_per_slot = 0;
+        spec.min_seed_lookahead = spec.max_seed_lookahead + Epoch::new(1);
+
+        assert_eq!(
+            validate_spec::<MainnetEthSpec>(&spec),
+            vec![
+                "milliseconds_per_slot must be non-zero".to_string(),
+                "min_seed_lookahead must not exceed max_seed_lookahead".to_string(),
+            ]
+        );
+    }
+}
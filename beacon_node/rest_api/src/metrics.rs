@@ -29,6 +29,48 @@ lazy_static! {
         "Duration to process HTTP requests",
         &["endpoint"]
     );
+    pub static ref BEACON_HTTP_API_VALIDATOR_PUBKEY_SCANS_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_validator_pubkey_scans_total",
+            "Count of validator lookups which required building the pubkey cache of a state"
+        );
+    pub static ref BEACON_HTTP_API_FORK_STATE_LOADS_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_fork_state_loads_total",
+            "Count of states loaded to serve requests for the fork at a state root"
+        );
+    pub static ref BEACON_HTTP_API_FINALITY_CHECKPOINTS_STATE_LOADS_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_finality_checkpoints_state_loads_total",
+            "Count of states loaded to serve requests for the finality checkpoints of a state"
+        );
+    pub static ref BEACON_HTTP_API_DUTIES_CACHE_MISSES_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_duties_cache_misses_total",
+            "Count of validator duties requests which required computing duties from a state"
+        );
+    pub static ref BEACON_HTTP_API_VALIDATOR_INDEX_CACHE_HITS_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_validator_index_cache_hits_total",
+            "Count of validator lookups at a state which were served from the validator index cache"
+        );
+    pub static ref BEACON_HTTP_API_VALIDATOR_INDEX_CACHE_MISSES_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_validator_index_cache_misses_total",
+            "Count of validator lookups at a state which missed the validator index cache"
+        );
+    pub static ref BEACON_HTTP_API_STATE_ID_RESOLUTIONS_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_http_api_state_id_resolutions_total",
+            "Count of states resolved from a state id, by the kind of id",
+            &["state_id"]
+        );
+    pub static ref BEACON_HTTP_API_BLOCK_ID_RESOLUTIONS_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_http_api_block_id_resolutions_total",
+            "Count of blocks resolved from a slot or root, by the kind of id",
+            &["block_id"]
+        );
     pub static ref REQUEST_RESPONSE_TIME: Result<Histogram> = try_create_histogram(
         "http_server_request_duration_seconds",
         "Time taken to build a response to a HTTP request"
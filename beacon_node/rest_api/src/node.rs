@@ -1,9 +1,17 @@
-use crate::{ApiError, Context};
+use crate::helpers::{parse_peer_direction, parse_peer_state};
+use crate::{ApiError, Context, UrlQuery};
 use beacon_chain::BeaconChainTypes;
+use eth2_libp2p::multiaddr::Protocol;
 use eth2_libp2p::types::SyncState;
-use rest_types::{SyncingResponse, SyncingStatus};
+use eth2_libp2p::{EnrExt, PeerId, PeerInfo};
+use hyper::{Request, StatusCode};
+use rest_types::{
+    NodeIdentity, PeerCount, PeerData, PeerDirection, PeerState, SyncingResponse, SyncingStatus,
+    SYNC_TOLERANCE,
+};
+use std::collections::HashSet;
 use std::sync::Arc;
-use types::Slot;
+use types::{EthSpec, Slot};
 
 /// Returns a syncing status.
 pub fn syncing<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<SyncingResponse, ApiError> {
@@ -37,3 +45,147 @@ pub fn syncing<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<SyncingRespo
         sync_status,
     })
 }
+
+/// Returns the status code of a liveness probe:
+///
+/// - `200 OK` if the head is within `SYNC_TOLERANCE` slots of the current slot.
+/// - `206 Partial Content` if the head is further behind the current slot.
+/// - `503 Service Unavailable` if the head or current slot cannot be read.
+pub fn liveness<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> StatusCode {
+    let (current_slot, head_slot) = match (ctx.beacon_chain.slot(), ctx.beacon_chain.head_info()) {
+        (Ok(current_slot), Ok(head_info)) => (current_slot, head_info.slot),
+        _ => return StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    if current_slot.saturating_sub(head_slot) <= SYNC_TOLERANCE {
+        StatusCode::OK
+    } else {
+        StatusCode::PARTIAL_CONTENT
+    }
+}
+
+/// Returns the identity of the node on the network.
+///
+/// The discovery addresses are read from the local ENR, so they are empty rather than an error if
+/// the ENR does not advertise a UDP address.
+pub fn identity<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> NodeIdentity {
+    let globals = &ctx.network_globals;
+    let enr = globals.local_enr();
+
+    NodeIdentity {
+        peer_id: globals.local_peer_id().to_base58(),
+        enr: enr.to_base64(),
+        p2p_addresses: globals
+            .listen_multiaddrs()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        discovery_addresses: enr
+            .multiaddr()
+            .into_iter()
+            .filter(|addr| {
+                addr.iter()
+                    .any(|protocol| matches!(protocol, Protocol::Udp(_)))
+            })
+            .map(|addr| addr.to_string())
+            .collect(),
+    }
+}
+
+/// Returns the number of peers known to the node in each connection state.
+pub fn peer_count<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> PeerCount {
+    let mut count = PeerCount {
+        connected: 0,
+        connecting: 0,
+        disconnected: 0,
+    };
+
+    for (_peer_id, peer_info) in ctx.network_globals.peers.read().peers() {
+        match peer_state(peer_info) {
+            PeerState::Connected => count.connected += 1,
+            PeerState::Connecting => count.connecting += 1,
+            PeerState::Disconnected => count.disconnected += 1,
+        }
+    }
+
+    count
+}
+
+/// Returns all peers known to the node.
+///
+/// Peers may be filtered by the `state` and `direction` query parameters, in which case only peers
+/// matching one of the given states and one of the given directions are returned. Peers with an
+/// unknown direction never match a `direction` filter.
+pub fn peers<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<PeerData>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+
+    let states = query
+        .all_of("state")?
+        .iter()
+        .map(|state_str| parse_peer_state(state_str))
+        .collect::<Result<HashSet<_>, _>>()?;
+    let directions = query
+        .all_of("direction")?
+        .iter()
+        .map(|direction_str| parse_peer_direction(direction_str))
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    Ok(ctx
+        .network_globals
+        .peers
+        .read()
+        .peers()
+        .map(|(peer_id, peer_info)| peer_data(peer_id, peer_info))
+        .filter(|peer| states.is_empty() || states.contains(&peer.state))
+        .filter(|peer| {
+            directions.is_empty()
+                || peer
+                    .direction
+                    .map_or(false, |direction| directions.contains(&direction))
+        })
+        .collect())
+}
+
+fn peer_data<E: EthSpec>(peer_id: &PeerId, peer_info: &PeerInfo<E>) -> PeerData {
+    PeerData {
+        peer_id: peer_id.to_string(),
+        last_seen_p2p_address: peer_info
+            .listening_addresses
+            .last()
+            .map(|addr| addr.to_string()),
+        state: peer_state(peer_info),
+        direction: peer_direction(peer_info),
+    }
+}
+
+/// Banned peers are reported as `Disconnected`, as are peers whose status is not yet known.
+fn peer_state<E: EthSpec>(peer_info: &PeerInfo<E>) -> PeerState {
+    let status = &peer_info.connection_status;
+    if status.is_connected() {
+        PeerState::Connected
+    } else if status.is_dialing() {
+        PeerState::Connecting
+    } else {
+        PeerState::Disconnected
+    }
+}
+
+/// A peer which we are dialing, or to which we hold any outgoing connection, is `Outbound`.
+fn peer_direction<E: EthSpec>(peer_info: &PeerInfo<E>) -> Option<PeerDirection> {
+    let status = &peer_info.connection_status;
+    if status.is_dialing() {
+        Some(PeerDirection::Outbound)
+    } else if status.is_connected() {
+        let (_n_in, n_out) = status.connections();
+        if n_out > 0 {
+            Some(PeerDirection::Outbound)
+        } else {
+            Some(PeerDirection::Inbound)
+        }
+    } else {
+        None
+    }
+}
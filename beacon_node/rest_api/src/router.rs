@@ -1,22 +1,25 @@
 use crate::{
     beacon, config::Config, consensus, lighthouse, metrics, node, validator, NetworkChannel,
 };
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use bus::Bus;
+use beacon_chain::{events::EventBus, BeaconChain, BeaconChainTypes};
 use environment::TaskExecutor;
 use eth2_config::Eth2Config;
 use eth2_libp2p::{NetworkGlobals, PeerId};
 use hyper::header::HeaderValue;
 use hyper::{Body, Method, Request, Response};
 use lighthouse_version::version_with_platform;
+use lru::LruCache;
 use operation_pool::PersistedOperationPool;
 use parking_lot::Mutex;
-use rest_types::{ApiError, Handler, Health};
+use rest_types::{ApiError, FinalityCheckpoints, GenesisData, Handler, Health, ValidatorDutyBytes};
 use slog::debug;
+use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Instant;
-use types::{EthSpec, SignedBeaconBlockHash};
+use types::{Epoch, EthSpec, Fork, Hash256, PublicKeyBytes, Slot};
 
 pub struct Context<T: BeaconChainTypes> {
     pub executor: TaskExecutor,
@@ -28,7 +31,35 @@ pub struct Context<T: BeaconChainTypes> {
     pub log: slog::Logger,
     pub db_path: PathBuf,
     pub freezer_db_path: PathBuf,
-    pub events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    pub events: Arc<EventBus<T::EthSpec>>,
+    /// The genesis of the chain, read once at startup since it never changes.
+    pub genesis_data: GenesisData,
+    /// The `Fork` of recently requested states, keyed by state root.
+    pub fork_cache: Mutex<LruCache<Hash256, Fork>>,
+    /// The number of fork requests which could not be served from `fork_cache`.
+    pub fork_state_loads: AtomicU64,
+    /// The `FinalityCheckpoints` of recently requested states, keyed by state root.
+    pub finality_checkpoints_cache: Mutex<LruCache<Hash256, FinalityCheckpoints>>,
+    /// The number of finality checkpoints requests which could not be served from
+    /// `finality_checkpoints_cache`.
+    pub finality_checkpoints_state_loads: AtomicU64,
+    /// The time and head slot at which a request was last refused whilst syncing, used to estimate
+    /// the sync speed. Reset once the node is synced.
+    pub sync_observation: Mutex<Option<(Instant, Slot)>>,
+    /// The duties of validators during recently requested epochs, keyed by the epoch and the root
+    /// of the latest block prior to the epoch.
+    pub duties_cache:
+        Mutex<LruCache<(Epoch, Hash256), HashMap<PublicKeyBytes, ValidatorDutyBytes>>>,
+    /// The number of duties requests which could not be served from `duties_cache`.
+    pub duties_cache_misses: AtomicU64,
+    /// The index of recently requested validators, keyed by state root and validator pubkey.
+    pub validator_index_cache: Mutex<LruCache<(Hash256, PublicKeyBytes), Option<usize>>>,
+    /// The number of validator lookups which were served from `validator_index_cache`.
+    pub validator_index_cache_hits: AtomicU64,
+    /// The number of validator lookups which could not be served from `validator_index_cache`.
+    pub validator_index_cache_misses: AtomicU64,
+    /// The number of validator lookups which have required building the pubkey cache of a state.
+    pub validator_pubkey_scans: AtomicU64,
 }
 
 pub async fn on_http_request<T: BeaconChainTypes>(
@@ -80,6 +111,73 @@ pub async fn on_http_request<T: BeaconChainTypes>(
     }
 }
 
+/// Returns a `Forbidden` error if the endpoint at `path` belongs to a category of endpoints which
+/// has been disabled in the `config`, or has not been enabled for endpoints which are disabled by
+/// default.
+fn check_endpoint_enabled(config: &Config, method: &Method, path: &str) -> Result<(), ApiError> {
+    let enabled = match (method, path) {
+        (&Method::POST, "/validator/block") | (&Method::POST, "/lighthouse/blocks/batch") => {
+            config.enable_block_publishing
+        }
+        (&Method::POST, "/validator/attestations")
+        | (&Method::POST, "/validator/aggregate_and_proofs") => {
+            config.enable_attestation_publishing
+        }
+        (&Method::POST, "/beacon/proposer_slashing")
+        | (&Method::POST, "/beacon/attester_slashing") => config.enable_slashing_publishing,
+        (&Method::POST, "/lighthouse/op_pool/prune") => config.enable_op_pool_pruning,
+        _ => true,
+    };
+
+    if enabled {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!(
+            "The {} endpoint has been disabled on this node",
+            path
+        )))
+    }
+}
+
+/// Returns a `ServiceUnavailable` error if the endpoint at `path` serves genesis or spec data and
+/// the chain has not yet reached genesis, unless `allow_insecure_genesis_sync` is set.
+///
+/// `/beacon/genesis_time` and `/spec/eth2_config` are always served, since the validator client
+/// reads them in order to wait for genesis.
+fn check_genesis_reached<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    method: &Method,
+    path: &str,
+) -> Result<(), ApiError> {
+    let serves_genesis_data = match (method, path) {
+        (&Method::GET, "/beacon/genesis")
+        | (&Method::GET, "/beacon/genesis_validators_root")
+        | (&Method::GET, "/beacon/state/genesis")
+        | (&Method::GET, "/spec") => true,
+        _ => false,
+    };
+
+    if !serves_genesis_data || ctx.config.allow_insecure_genesis_sync {
+        return Ok(());
+    }
+
+    let slot_clock = &ctx.beacon_chain.slot_clock;
+    if slot_clock.is_prior_to_genesis() != Some(true) {
+        return Ok(());
+    }
+
+    let retry_after = slot_clock
+        .duration_to_slot(slot_clock.genesis_slot())
+        .unwrap_or_default();
+    Err(ApiError::ServiceUnavailableRetryAfter(
+        format!(
+            "The chain has not reached genesis, the {} endpoint is unavailable",
+            path
+        ),
+        retry_after.as_secs().max(1),
+    ))
+}
+
 async fn route<T: BeaconChainTypes>(
     req: Request<Body>,
     ctx: Arc<Context<T>>,
@@ -87,6 +185,8 @@ async fn route<T: BeaconChainTypes>(
     let path = req.uri().path().to_string();
     let ctx = ctx.clone();
     let method = req.method().clone();
+    check_endpoint_enabled(&ctx.config, &method, &path)?;
+    check_genesis_reached(&ctx, &method, &path)?;
     let executor = ctx.executor.clone();
     let handler = Handler::new(req, ctx, executor)?;
 
@@ -99,6 +199,22 @@ async fn route<T: BeaconChainTypes>(
             .static_value(Health::observe().map_err(ApiError::ServerError)?)
             .await?
             .serde_encodings(),
+        (Method::GET, "/node/liveness") => handler
+            .in_core_task(|_, ctx| Ok(node::liveness(ctx)))
+            .await?
+            .status_only(),
+        (Method::GET, "/node/identity") => handler
+            .in_core_task(|_, ctx| Ok(node::identity(ctx)))
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/node/peer_count") => handler
+            .in_blocking_task(|_, ctx| Ok(node::peer_count(ctx)))
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/node/peers") => handler
+            .in_blocking_task(node::peers)
+            .await?
+            .serde_encodings(),
         (Method::GET, "/node/syncing") => handler
             .allow_body()
             .in_blocking_task(|_, ctx| node::syncing(ctx))
@@ -141,30 +257,48 @@ async fn route<T: BeaconChainTypes>(
             .await?
             .all_encodings(),
         (Method::GET, "/beacon/heads") => handler
-            .in_blocking_task(|_, ctx| Ok(beacon::get_heads(ctx)))
+            .in_blocking_task(|_, ctx| beacon::get_heads(ctx))
             .await?
             .all_encodings(),
         (Method::GET, "/beacon/block") => handler
             .in_blocking_task(beacon::get_block)
             .await?
             .all_encodings(),
+        (Method::GET, "/beacon/block/ancestor") => handler
+            .in_blocking_task(beacon::get_block_ancestor)
+            .await?
+            .all_encodings(),
+        (Method::POST, "/beacon/block/validate") => handler
+            .allow_body()
+            .in_blocking_task(beacon::validate_block)
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/beacon/blocks/attestations") => handler
+            .in_blocking_task(beacon::get_block_attestations)
+            .await?
+            .all_encodings(),
         (Method::GET, "/beacon/block_root") => handler
             .in_blocking_task(beacon::get_block_root)
             .await?
             .all_encodings(),
         (Method::GET, "/beacon/fork") => handler
-            .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.fork))
+            .in_blocking_task(beacon::get_fork)
             .await?
             .all_encodings(),
         (Method::GET, "/beacon/fork/stream") => {
             handler.sse_stream(|_, ctx| beacon::stream_forks(ctx)).await
         }
+        (Method::GET, "/beacon/events") => handler.sse_stream(beacon::stream_events).await,
+        (Method::GET, "/beacon/genesis") => handler
+            .in_core_task(|_, ctx| Ok(ctx.genesis_data.clone()))
+            .await?
+            .serde_encodings(),
         (Method::GET, "/beacon/genesis_time") => handler
-            .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.genesis_time))
+            .in_core_task(|_, ctx| Ok(ctx.genesis_data.genesis_time))
             .await?
             .all_encodings(),
         (Method::GET, "/beacon/genesis_validators_root") => handler
-            .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.genesis_validators_root))
+            .in_core_task(|_, ctx| Ok(ctx.genesis_data.genesis_validators_root))
             .await?
             .all_encodings(),
         (Method::GET, "/beacon/validators") => handler
@@ -176,6 +310,11 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(beacon::post_validators)
             .await?
             .all_encodings(),
+        (Method::POST, "/beacon/validators/states") => handler
+            .allow_body()
+            .in_blocking_task(beacon::post_validator_states)
+            .await?
+            .all_encodings(),
         (Method::GET, "/beacon/validators/all") => handler
             .in_blocking_task(beacon::get_all_validators)
             .await?
@@ -184,6 +323,14 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(beacon::get_active_validators)
             .await?
             .all_encodings(),
+        (Method::GET, "/beacon/validators/page") => handler
+            .in_blocking_task(beacon::get_validators_page)
+            .await?
+            .all_encodings(),
+        (Method::GET, "/beacon/validators/balances") => handler
+            .in_blocking_task(beacon::get_validator_balances)
+            .await?
+            .all_encodings(),
         (Method::GET, "/beacon/state") => handler
             .in_blocking_task(beacon::get_state)
             .await?
@@ -192,6 +339,14 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(beacon::get_state_root)
             .await?
             .all_encodings(),
+        (Method::GET, "/beacon/state/randao") => handler
+            .in_blocking_task(beacon::get_randao_mix)
+            .await?
+            .all_encodings(),
+        (Method::GET, "/beacon/state/finality_checkpoints") => handler
+            .in_blocking_task(beacon::get_finality_checkpoints)
+            .await?
+            .all_encodings(),
         (Method::GET, "/beacon/state/genesis") => handler
             .in_blocking_task(|_, ctx| beacon::get_genesis_state(ctx))
             .await?
@@ -200,6 +355,23 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(beacon::get_committees)
             .await?
             .all_encodings(),
+        (Method::GET, "/beacon/pool/attestations/aggregate") => handler
+            .in_blocking_task(beacon::get_pool_attestations_aggregate)
+            .await?
+            .all_encodings(),
+        (Method::GET, "/beacon/pool/summary") => handler
+            .in_blocking_task(|_, ctx| Ok(beacon::get_pool_summary(ctx)))
+            .await?
+            .all_encodings(),
+        (Method::POST, "/beacon/rewards/attestations") => handler
+            .allow_body()
+            .in_blocking_task(beacon::post_attestation_rewards)
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/beacon/rewards/block") => handler
+            .in_blocking_task(beacon::get_block_rewards)
+            .await?
+            .all_encodings(),
         (Method::POST, "/beacon/proposer_slashing") => handler
             .allow_body()
             .in_blocking_task(beacon::proposer_slashing)
@@ -220,6 +392,16 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(validator::post_validator_subscriptions)
             .await?
             .serde_encodings(),
+        (Method::POST, "/validator/liveness") => handler
+            .allow_body()
+            .in_blocking_task(validator::post_validator_liveness)
+            .await?
+            .serde_encodings(),
+        (Method::POST, "/validator/duties/warm") => handler
+            .allow_body()
+            .in_blocking_task(validator::post_validator_duties_warm)
+            .await?
+            .serde_encodings(),
         (Method::GET, "/validator/duties/all") => handler
             .in_blocking_task(validator::get_all_validator_duties)
             .await?
@@ -315,6 +497,43 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(|_, ctx| lighthouse::connected_peers(ctx))
             .await?
             .serde_encodings(),
+        (Method::GET, "/lighthouse/current_slot") => handler
+            .in_blocking_task(|_, ctx| lighthouse::current_slot(ctx))
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/lighthouse/recent_blocks") => handler
+            .in_blocking_task(lighthouse::recent_blocks)
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/lighthouse/validator/effectiveness") => handler
+            .in_blocking_task(lighthouse::validator_effectiveness)
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/lighthouse/validator/duty_history") => handler
+            .in_blocking_task(lighthouse::validator_duty_history)
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/lighthouse/spec/validate") => handler
+            .in_blocking_task(|_, ctx| {
+                Ok(lighthouse::validate_spec::<T::EthSpec>(
+                    &ctx.beacon_chain.spec,
+                ))
+            })
+            .await?
+            .serde_encodings(),
+        (Method::GET, "/lighthouse/op_pool/attestations/export") => handler
+            .in_blocking_task(|_, ctx| lighthouse::export_attestations(ctx))
+            .await?
+            .serde_encodings(),
+        (Method::POST, "/lighthouse/op_pool/prune") => handler
+            .in_blocking_task(|_, ctx| lighthouse::prune_op_pool(ctx))
+            .await?
+            .serde_encodings(),
+        (Method::POST, "/lighthouse/blocks/batch") => handler
+            .allow_body()
+            .in_blocking_task(lighthouse::import_blocks)
+            .await?
+            .serde_encodings(),
         _ => Err(ApiError::NotFound(
             "Request path and/or method not found.".to_owned(),
         )),
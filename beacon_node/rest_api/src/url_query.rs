@@ -1,4 +1,7 @@
-use crate::helpers::{parse_committee_index, parse_epoch, parse_hex_ssz_bytes, parse_slot};
+use crate::helpers::{
+    parse_committee_index, parse_epoch, parse_hex_ssz_bytes, parse_root, parse_slot,
+    parse_state_id, StateId,
+};
 use crate::ApiError;
 use hyper::Request;
 use types::{AttestationData, CommitteeIndex, Epoch, Signature, Slot};
@@ -97,6 +100,29 @@ impl<'a> UrlQuery<'a> {
             .and_then(|(_key, value)| parse_slot(&value))
     }
 
+    /// Returns the state identified by the first occurrence of the `state_id` or `state_root` key,
+    /// if any.
+    ///
+    /// Returns a `BadRequest` error if the value is not a valid state id.
+    pub fn state_id(self) -> Result<Option<StateId>, ApiError> {
+        match self.first_of_opt(&["state_id", "state_root"]) {
+            Some((key, value)) if key == "state_id" => parse_state_id(&value).map(Some),
+            Some((_key, value)) => parse_root(&value).map(|root| Some(StateId::Root(root))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the first occurrence of `key` parsed as a `u64`, if any.
+    pub fn u64_opt(self, key: &str) -> Result<Option<u64>, ApiError> {
+        self.first_of_opt(&[key])
+            .map(|(_key, value)| {
+                value
+                    .parse::<u64>()
+                    .map_err(|e| ApiError::BadRequest(format!("Unable to parse {}: {:?}", key, e)))
+            })
+            .transpose()
+    }
+
     /// Returns the value of the first occurrence of the `committee_index` key.
     pub fn committee_index(self) -> Result<CommitteeIndex, ApiError> {
         self.first_of(&["committee_index"])
@@ -104,9 +130,19 @@ impl<'a> UrlQuery<'a> {
     }
 
     /// Returns the value of the first occurrence of the `randao_reveal` key.
+    ///
+    /// Returns a `BadRequest` error describing the signature as malformed if the value is present
+    /// but cannot be parsed.
     pub fn randao_reveal(self) -> Result<Signature, ApiError> {
-        self.first_of(&["randao_reveal"])
-            .and_then(|(_key, value)| parse_hex_ssz_bytes(&value))
+        self.first_of(&["randao_reveal"]).and_then(|(_key, value)| {
+            parse_hex_ssz_bytes(&value).map_err(|e| match e {
+                ApiError::BadRequest(desc) => ApiError::BadRequest(format!(
+                    "Invalid randao_reveal: malformed signature bytes: {}",
+                    desc
+                )),
+                e => e,
+            })
+        })
     }
 
     /// Returns the value of the first occurrence of the `attestation_data` key.
@@ -1,22 +1,57 @@
-use crate::helpers::{parse_hex_ssz_bytes, publish_beacon_block_to_network};
-use crate::{ApiError, Context, NetworkChannel, UrlQuery};
+use crate::helpers::{block_root_at_slot, parse_hex_ssz_bytes, publish_beacon_block_to_network};
+use crate::{metrics, ApiError, Context, NetworkChannel, UrlQuery};
 use beacon_chain::{
     attestation_verification::Error as AttnError, BeaconChain, BeaconChainError, BeaconChainTypes,
     BlockError, ForkChoiceError, StateSkipConfig,
 };
-use bls::PublicKeyBytes;
+use bls::{verify_signature_sets, PublicKeyBytes};
 use eth2_libp2p::PubsubMessage;
 use hyper::Request;
 use network::NetworkMessage;
-use rest_types::{ValidatorDutiesRequest, ValidatorDutyBytes, ValidatorSubscription};
+use rest_types::{
+    ValidatorDutiesRequest, ValidatorDutyBytes, ValidatorLiveness, ValidatorLivenessRequest,
+    ValidatorSubscription,
+};
 use slog::{error, info, trace, warn, Logger};
+use state_processing::per_block_processing::signature_sets::{
+    get_pubkey_from_state, randao_signature_set,
+};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tree_hash::TreeHash;
 use types::beacon_state::EthSpec;
 use types::{
-    Attestation, AttestationData, BeaconBlock, BeaconState, Epoch, RelativeEpoch, SelectionProof,
-    SignedAggregateAndProof, SignedBeaconBlock, SubnetId,
+    Attestation, AttestationData, BeaconBlock, BeaconState, ChainSpec, Epoch, Hash256,
+    RelativeEpoch, SelectionProof, SignedAggregateAndProof, SignedBeaconBlock, Slot, SubnetId,
 };
 
+/// Checks that the `randao_reveal` of a newly produced `block` was signed by its proposer, so that
+/// a validator client is told of an unverifiable reveal before it signs and publishes the block.
+///
+/// The `state` may be any state in the epoch of the block, such as the post-state of the block.
+fn verify_randao_reveal<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state: &BeaconState<T::EthSpec>,
+    block: &BeaconBlock<T::EthSpec>,
+) -> Result<(), ApiError> {
+    let randao_set = randao_signature_set(
+        state,
+        |validator_index| get_pubkey_from_state(state, validator_index),
+        block,
+        &beacon_chain.spec,
+    )
+    .map_err(|e| ApiError::ServerError(format!("Unable to verify randao_reveal: {:?}", e)))?;
+
+    if verify_signature_sets(std::iter::once(&randao_set)) {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(
+            "Invalid randao_reveal: signature verification failed".to_string(),
+        ))
+    }
+}
+
 /// HTTP Handler to retrieve the duties for a set of validators during a particular epoch. This
 /// method allows for collecting bulk sets of validator duties without risking exceeding the max
 /// URL length with query pairs.
@@ -24,6 +59,8 @@ pub fn post_validator_duties<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
 ) -> Result<Vec<ValidatorDutyBytes>, ApiError> {
+    check_synced(&ctx)?;
+
     let body = req.into_body();
 
     serde_json::from_slice::<ValidatorDutiesRequest>(&body)
@@ -35,13 +72,76 @@ pub fn post_validator_duties<T: BeaconChainTypes>(
         })
         .and_then(|bulk_request| {
             return_validator_duties(
-                &ctx.beacon_chain.clone(),
+                &ctx,
                 bulk_request.epoch,
                 bulk_request.pubkeys.into_iter().map(Into::into).collect(),
             )
         })
 }
 
+/// HTTP Handler to compute the duties for a set of validators during a particular epoch and cache
+/// them, so that subsequent duties requests for those validators during that epoch are served
+/// without loading a state.
+pub fn post_validator_duties_warm<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<(), ApiError> {
+    check_synced(&ctx)?;
+
+    let body = req.into_body();
+
+    let bulk_request = serde_json::from_slice::<ValidatorDutiesRequest>(&body).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into ValidatorDutiesRequest: {:?}",
+            e
+        ))
+    })?;
+
+    return_validator_duties(&ctx, bulk_request.epoch, bulk_request.pubkeys).map(|_| ())
+}
+
+/// HTTP Handler to determine whether each of a set of validators was observed to attest or
+/// propose during an epoch.
+///
+/// The beacon node only retains observations for recent epochs, so the epoch must be the previous,
+/// current or next epoch (the latter allowing for clock disparity).
+pub fn post_validator_liveness<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<ValidatorLiveness>, ApiError> {
+    let body = req.into_body();
+
+    let request = serde_json::from_slice::<ValidatorLivenessRequest>(&body).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into ValidatorLivenessRequest: {:?}",
+            e
+        ))
+    })?;
+    let epoch = request.epoch;
+
+    let current_epoch = ctx.beacon_chain.epoch()?;
+    if epoch + 1 < current_epoch || epoch > current_epoch + 1 {
+        return Err(ApiError::BadRequest(format!(
+            "Unable to determine liveness during epoch {}: only epochs {} to {} are known",
+            epoch,
+            current_epoch.saturating_sub(1_u64),
+            current_epoch + 1
+        )));
+    }
+
+    Ok(request
+        .indices
+        .into_iter()
+        .map(|index| ValidatorLiveness {
+            index,
+            epoch,
+            is_live: ctx
+                .beacon_chain
+                .validator_seen_at_epoch(index as usize, epoch),
+        })
+        .collect())
+}
+
 /// HTTP Handler to retrieve subscriptions for a set of validators. This allows the node to
 /// organise peer discovery and topic subscription for known validators.
 pub fn post_validator_subscriptions<T: BeaconChainTypes>(
@@ -58,6 +158,10 @@ pub fn post_validator_subscriptions<T: BeaconChainTypes>(
             ))
         })
         .and_then(move |subscriptions: Vec<ValidatorSubscription>| {
+            for subscription in &subscriptions {
+                verify_subscription_subnet_id::<T::EthSpec>(subscription, &ctx.beacon_chain.spec)?;
+            }
+
             ctx.network_chan
                 .send(NetworkMessage::Subscribe { subscriptions })
                 .map_err(|e| {
@@ -70,6 +174,36 @@ pub fn post_validator_subscriptions<T: BeaconChainTypes>(
         })
 }
 
+/// Ensures the `subnet_id` of `subscription`, if any, is the subnet of its committee.
+fn verify_subscription_subnet_id<E: EthSpec>(
+    subscription: &ValidatorSubscription,
+    spec: &ChainSpec,
+) -> Result<(), ApiError> {
+    if let Some(subnet_id) = subscription.subnet_id {
+        let expected = SubnetId::compute_subnet::<E>(
+            subscription.slot,
+            subscription.attestation_committee_index,
+            subscription.committee_count_at_slot,
+            spec,
+        )
+        .map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to compute subnet id for validator {}: {:?}",
+                subscription.validator_index, e
+            ))
+        })?;
+
+        if subnet_id != expected {
+            return Err(ApiError::BadRequest(format!(
+                "Subscription for validator {} has subnet id {} but the committee is in subnet {}",
+                subscription.validator_index, *subnet_id, *expected
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// HTTP Handler to retrieve all validator duties for the given epoch.
 pub fn get_all_validator_duties<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
@@ -87,7 +221,7 @@ pub fn get_all_validator_duties<T: BeaconChainTypes>(
         .map(|validator| validator.pubkey.clone())
         .collect();
 
-    return_validator_duties(&ctx.beacon_chain, epoch, validator_pubkeys)
+    return_validator_duties_uncached(&ctx, epoch, validator_pubkeys)
 }
 
 /// HTTP Handler to retrieve all active validator duties for the given epoch.
@@ -108,7 +242,7 @@ pub fn get_active_validator_duties<T: BeaconChainTypes>(
         .map(|validator| validator.pubkey.clone())
         .collect();
 
-    return_validator_duties(&ctx.beacon_chain, epoch, validator_pubkeys)
+    return_validator_duties_uncached(&ctx, epoch, validator_pubkeys)
 }
 
 /// Helper function to return the state that can be used to determine the duties for some `epoch`.
@@ -142,10 +276,88 @@ pub fn get_state_for_epoch<T: BeaconChainTypes>(
     }
 }
 
+/// Returns the root of the latest canonical block prior to the start of `epoch`, which determines
+/// the duties of all validators during `epoch`.
+fn duties_dependent_root<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    epoch: Epoch,
+) -> Result<Hash256, ApiError> {
+    let head = beacon_chain.head_info()?;
+    let start_slot = epoch.start_slot(T::EthSpec::slots_per_epoch());
+
+    if start_slot == beacon_chain.spec.genesis_slot {
+        Ok(beacon_chain.genesis_block_root)
+    } else if start_slot > head.slot {
+        Ok(head.block_root)
+    } else {
+        block_root_at_slot(beacon_chain, start_slot - 1)?.ok_or_else(|| {
+            ApiError::ServerError(format!(
+                "Unable to find the block root prior to epoch {}",
+                epoch
+            ))
+        })
+    }
+}
+
 /// Helper function to get the duties for some `validator_pubkeys` in some `epoch`.
-fn return_validator_duties<T: BeaconChainTypes>(
+///
+/// Duties are served from the cache of `ctx` if they are known for all of the
+/// `validator_pubkeys`. Otherwise, they are computed from a state and added to the cache.
+pub fn return_validator_duties<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    epoch: Epoch,
+    validator_pubkeys: Vec<PublicKeyBytes>,
+) -> Result<Vec<ValidatorDutyBytes>, ApiError> {
+    let dependent_root = duties_dependent_root(&ctx.beacon_chain, epoch)?;
+    let cache_key = (epoch, dependent_root);
+
+    if let Some(cached) = ctx.duties_cache.lock().get(&cache_key) {
+        let duties = validator_pubkeys
+            .iter()
+            .map(|pubkey| cached.get(pubkey).cloned())
+            .collect::<Option<Vec<_>>>();
+        if let Some(duties) = duties {
+            return Ok(duties);
+        }
+    }
+
+    metrics::inc_counter(&metrics::BEACON_HTTP_API_DUTIES_CACHE_MISSES_TOTAL);
+    ctx.duties_cache_misses.fetch_add(1, Ordering::Relaxed);
+    let duties =
+        compute_validator_duties(&ctx.beacon_chain, epoch, dependent_root, validator_pubkeys)?;
+
+    let mut cache = ctx.duties_cache.lock();
+    let mut cached = cache.pop(&cache_key).unwrap_or_default();
+    cached.extend(
+        duties
+            .iter()
+            .map(|duty| (duty.validator_pubkey.clone(), duty.clone())),
+    );
+    cache.put(cache_key, cached);
+
+    Ok(duties)
+}
+
+/// As per `return_validator_duties`, but without reading or populating the cache of `ctx`.
+///
+/// Used for requests for the duties of the whole (active) validator set, which would otherwise
+/// fill each cache entry with the duties of every validator.
+fn return_validator_duties_uncached<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    epoch: Epoch,
+    validator_pubkeys: Vec<PublicKeyBytes>,
+) -> Result<Vec<ValidatorDutyBytes>, ApiError> {
+    let dependent_root = duties_dependent_root(&ctx.beacon_chain, epoch)?;
+    compute_validator_duties(&ctx.beacon_chain, epoch, dependent_root, validator_pubkeys)
+}
+
+/// Computes the duties for some `validator_pubkeys` in some `epoch` from the state for `epoch`.
+///
+/// The `dependent_root` is reported alongside any block proposal slots.
+fn compute_validator_duties<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     epoch: Epoch,
+    dependent_root: Hash256,
     validator_pubkeys: Vec<PublicKeyBytes>,
 ) -> Result<Vec<ValidatorDutyBytes>, ApiError> {
     let mut state = get_state_for_epoch(&beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
@@ -239,6 +451,9 @@ fn return_validator_duties<T: BeaconChainTypes>(
                     attestation_committee_index: duties.map(|d| d.index),
                     committee_count_at_slot,
                     attestation_committee_position: duties.map(|d| d.committee_position),
+                    block_proposal_dependent_root: block_proposal_slots
+                        .as_ref()
+                        .map(|_| dependent_root),
                     block_proposal_slots,
                     aggregator_modulo,
                 })
@@ -250,6 +465,7 @@ fn return_validator_duties<T: BeaconChainTypes>(
                     attestation_committee_index: None,
                     attestation_committee_position: None,
                     block_proposal_slots: None,
+                    block_proposal_dependent_root: None,
                     committee_count_at_slot: None,
                     aggregator_modulo: None,
                 })
@@ -274,7 +490,9 @@ pub fn get_new_beacon_block<T: BeaconChainTypes>(
         None
     };
 
-    let (new_block, _state) = ctx
+    check_synced(&ctx)?;
+
+    let (new_block, state) = ctx
         .beacon_chain
         .produce_block(randao_reveal, slot, validator_graffiti)
         .map_err(|e| {
@@ -290,6 +508,21 @@ pub fn get_new_beacon_block<T: BeaconChainTypes>(
             ))
         })?;
 
+    verify_randao_reveal(&ctx.beacon_chain, &state, &new_block)?;
+
+    // Allows test harnesses to produce blocks which claim to be from a validator other than the
+    // scheduled proposer. Such blocks should always be rejected.
+    #[cfg(feature = "proposer_override")]
+    let new_block = {
+        let mut new_block = new_block;
+        if let Some((_key, value)) = query.first_of_opt(&["proposer_index"]) {
+            new_block.proposer_index = value.parse::<u64>().map_err(|e| {
+                ApiError::BadRequest(format!("Unable to parse proposer_index: {:?}", e))
+            })?;
+        }
+        new_block
+    };
+
     Ok(new_block)
 }
 
@@ -305,7 +538,16 @@ pub fn publish_beacon_block<T: BeaconChainTypes>(
                 })
             .and_then(move |block: SignedBeaconBlock<T::EthSpec>| {
                 let slot = block.slot();
-                match ctx.beacon_chain.process_block(block.clone()) {
+
+                // Run the block through gossip verification before importing it. This ensures
+                // the block was produced by the expected proposer (amongst other checks) and that
+                // we never publish a block which our peers would reject.
+                let result = ctx
+                    .beacon_chain
+                    .verify_block_for_gossip(block.clone())
+                    .and_then(|verified_block| ctx.beacon_chain.process_block(verified_block));
+
+                match result {
                     Ok(block_root) => {
                         // Block was processed, publish via gossipsub
                         info!(
@@ -387,12 +629,84 @@ pub fn get_new_attestation<T: BeaconChainTypes>(
     let slot = query.slot()?;
     let index = query.committee_index()?;
 
+    // Only serve attestations that could still be included in a block, allowing the requested
+    // slot to be one ahead of ours to account for clock disparity with the validator client.
+    let current_slot = ctx.beacon_chain.slot()?;
+    if slot > current_slot + 1 || slot + T::EthSpec::slots_per_epoch() < current_slot {
+        return Err(ApiError::BadRequest(format!(
+            "Unable to produce attestation: slot {} is too far from the current slot {}",
+            slot, current_slot
+        )));
+    }
+
     ctx.beacon_chain
         .produce_unaggregated_attestation(slot, index)
         .map_err(|e| ApiError::BadRequest(format!("Unable to produce attestation: {:?}", e)))
 }
 
+/// Returns a 503 with a `Retry-After` hint if the head is more than the configured
+/// `sync_tolerance` slots behind the current slot.
+///
+/// The observation of the sync speed is forgotten once the head is within the tolerance, so that
+/// the estimate for a later sync is not skewed by the speed of an earlier one.
+fn check_synced<T: BeaconChainTypes>(ctx: &Context<T>) -> Result<(), ApiError> {
+    let tolerance = if let Some(tolerance) = ctx.config.sync_tolerance {
+        tolerance
+    } else {
+        return Ok(());
+    };
+
+    let current_slot = ctx.beacon_chain.slot()?;
+    let head_slot = ctx.beacon_chain.head_info()?.slot;
+    let sync_distance = current_slot.saturating_sub(head_slot);
+
+    if sync_distance <= tolerance {
+        *ctx.sync_observation.lock() = None;
+        return Ok(());
+    }
+
+    let retry_after = estimated_sync_time(ctx, head_slot, current_slot.saturating_sub(tolerance));
+    Err(ApiError::ServiceUnavailableRetryAfter(
+        format!(
+            "Beacon node is not synced, head is {} slots behind the current slot",
+            sync_distance
+        ),
+        retry_after.as_secs().max(1),
+    ))
+}
+
+/// Estimates the time until the head reaches `target_slot`, judging by how far the head has
+/// advanced since the previous estimate.
+///
+/// Returns the duration of a single slot if the sync speed cannot be estimated, including when the
+/// head is behind the previous estimate (i.e., a new sync has started from an earlier head).
+fn estimated_sync_time<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    head_slot: Slot,
+    target_slot: Slot,
+) -> Duration {
+    let now = Instant::now();
+    let previous = ctx.sync_observation.lock().replace((now, head_slot));
+
+    previous
+        .and_then(|(previous_instant, previous_slot)| {
+            let seconds = now.duration_since(previous_instant).as_secs_f64();
+            let slots = head_slot.saturating_sub(previous_slot).as_u64() as f64;
+
+            if seconds > 0.0 && slots > 0.0 {
+                let remaining = target_slot.saturating_sub(head_slot).as_u64() as f64;
+                Some(Duration::from_secs_f64(remaining * seconds / slots))
+            } else {
+                None
+            }
+        })
+        .unwrap_or_else(|| Duration::from_millis(ctx.beacon_chain.spec.milliseconds_per_slot))
+}
+
 /// HTTP Handler to retrieve the aggregate attestation for a slot
+///
+/// Both the naive aggregation pool and the operation pool are consulted, returning whichever
+/// aggregate has the most signers.
 pub fn get_aggregate_attestation<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
@@ -401,21 +715,27 @@ pub fn get_aggregate_attestation<T: BeaconChainTypes>(
 
     let attestation_data = query.attestation_data()?;
 
-    match ctx
+    let naive_aggregate = ctx
         .beacon_chain
         .get_aggregated_attestation(&attestation_data)
-    {
-        Ok(Some(attestation)) => Ok(attestation),
-        Ok(None) => Err(ApiError::NotFound(format!(
-            "No matching aggregate attestation for slot {:?} is known in slot {:?}",
-            attestation_data.slot,
-            ctx.beacon_chain.slot()
-        ))),
-        Err(e) => Err(ApiError::ServerError(format!(
-            "Unable to obtain attestation: {:?}",
-            e
-        ))),
-    }
+        .map_err(|e| ApiError::ServerError(format!("Unable to obtain attestation: {:?}", e)))?;
+    let pool_aggregate = ctx
+        .beacon_chain
+        .op_pool
+        .get_aggregated_attestation(attestation_data.slot, attestation_data.tree_hash_root());
+
+    // Prefer the naive aggregate when both have the same number of signers.
+    pool_aggregate
+        .into_iter()
+        .chain(naive_aggregate)
+        .max_by_key(|attestation| attestation.aggregation_bits.num_set_bits())
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No matching aggregate attestation for slot {:?} is known in slot {:?}",
+                attestation_data.slot,
+                ctx.beacon_chain.slot()
+            ))
+        })
 }
 
 /// HTTP Handler to publish a list of Attestations, which have been signed by a number of validators.
@@ -4,26 +4,43 @@
 extern crate assert_matches;
 
 use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use eth2_libp2p::{PeerId, PubsubMessage};
+use futures::StreamExt;
 use node_test_rig::{
     environment::{Environment, EnvironmentBuilder},
     testing_client_config, ClientConfig, ClientGenesis, LocalBeaconNode,
 };
 use remote_beacon_node::{
-    Committee, HeadBeaconBlock, PersistedOperationPool, PublishStatus, ValidatorResponse,
+    BlockAttestations, BlockRootResponse, Committee, HeadBeaconBlock, PersistedOperationPool,
+    PoolSummary, PublishStatus, ValidatorBalance, ValidatorId, ValidatorResponse, ValidatorStatus,
+    ValidatorSubscription,
 };
-use rest_types::ValidatorDutyBytes;
+use rest_types::{
+    AttestationPoolKind, FinalityCheckpoints, GenesisData, NodeLiveness, PeerCount, PeerDirection,
+    PeerState, ValidatorDutiesRequest, ValidatorDutyBytes, SYNC_TOLERANCE,
+};
+use ssz::Encode;
+use state_processing::{
+    common::DepositDataTree, per_block_processing, per_slot_processing, BlockSignatureStrategy,
+    VerifyOperation,
+};
+use std::borrow::Cow;
 use std::convert::TryInto;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tree_hash::TreeHash;
 use types::{
     test_utils::{
         build_double_vote_attester_slashing, build_proposer_slashing,
-        generate_deterministic_keypair, AttesterSlashingTestTask, ProposerSlashingTestTask,
+        generate_deterministic_keypair, AttesterSlashingTestTask, DepositTestTask,
+        ProposerSlashingTestTask, TestingDepositBuilder, TestingVoluntaryExitBuilder,
     },
-    BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, EthSpec, MinimalEthSpec, PublicKey,
-    RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot, Slot,
-    SubnetId, Validator,
+    BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, Eth1Data, EthSpec, Hash256, Keypair,
+    MinimalEthSpec, PublicKey, RelativeEpoch, Signature, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedRoot, Slot, SubnetId, Validator, VariableList, DEPOSIT_TREE_DEPTH,
 };
+use url::Url;
 
 type E = MinimalEthSpec;
 
@@ -271,6 +288,94 @@ fn validator_produce_attestation() {
     );
 }
 
+#[test]
+fn validator_publish_attestation_outside_slot_window() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let mut config = testing_client_config();
+    config.rest_api.record_network_messages = true;
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let network_messages = node
+        .client
+        .http_network_messages()
+        .expect("should record network messages");
+    let state = beacon_chain.head().expect("should get head").beacon_state;
+    let current_slot = beacon_chain.slot().expect("should get slot");
+
+    // Find a validator that has duties in the current slot of the chain.
+    let mut validator_index = 0;
+    let duties = loop {
+        let duties = state
+            .get_attestation_duties(validator_index, RelativeEpoch::Current)
+            .expect("should have attestation duties cache")
+            .expect("should have attestation duties");
+
+        if duties.slot == current_slot {
+            break duties;
+        } else {
+            validator_index += 1
+        }
+    };
+
+    let mut attestation = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_attestation(duties.slot, duties.index),
+        )
+        .expect("should fetch attestation from http api");
+
+    // Move the attestation well beyond the permissible slot window and sign it, so the slot is
+    // the only fault with the attestation.
+    attestation.data.slot = current_slot + E::slots_per_epoch();
+
+    let keypair = generate_deterministic_keypair(validator_index);
+    attestation
+        .sign(
+            &keypair.sk,
+            duties.committee_position,
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        )
+        .expect("should sign attestation");
+
+    let committee_count = state
+        .get_committee_count_at_slot(duties.slot)
+        .expect("should get committee count");
+    let subnet_id =
+        SubnetId::compute_subnet::<E>(duties.slot, duties.index, committee_count, spec).unwrap();
+
+    let publish_status = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .publish_attestations(vec![(attestation, subnet_id)]),
+        )
+        .expect("should publish attestation");
+    assert!(
+        !publish_status.is_valid(),
+        "an attestation outside the slot window should not be valid"
+    );
+    assert!(
+        network_messages.messages().is_empty(),
+        "an attestation outside the slot window should not be published to the network"
+    );
+}
+
 #[test]
 fn validator_duties() {
     let mut env = build_env();
@@ -349,6 +454,15 @@ fn check_duties<T: BeaconChainTypes>(
 
     state.build_all_caches(spec).expect("should build caches");
 
+    let start_slot = epoch.start_slot(E::slots_per_epoch());
+    let expected_dependent_root = if start_slot == spec.genesis_slot {
+        beacon_chain.genesis_block_root
+    } else {
+        *state
+            .get_block_root(start_slot - 1)
+            .expect("should get dependent root")
+    };
+
     validators
         .iter()
         .zip(duties.iter())
@@ -384,6 +498,12 @@ fn check_duties<T: BeaconChainTypes>(
             );
 
             if proposers_known {
+                assert_eq!(
+                    duty.block_proposal_dependent_root,
+                    Some(expected_dependent_root),
+                    "dependent root should match"
+                );
+
                 let block_proposal_slots = duty.block_proposal_slots.as_ref().unwrap();
 
                 if !block_proposal_slots.is_empty() {
@@ -409,6 +529,7 @@ fn check_duties<T: BeaconChainTypes>(
                 }
             } else {
                 assert_eq!(duty.block_proposal_slots, None);
+                assert_eq!(duty.block_proposal_dependent_root, None);
             }
         });
 
@@ -425,6 +546,120 @@ fn check_duties<T: BeaconChainTypes>(
     }
 }
 
+/// Returns the number of validator duties requests to the HTTP API of `node` which have required
+/// computing duties from a state.
+fn duties_cache_misses(node: &LocalBeaconNode<E>) -> u64 {
+    node.client
+        .http_api_context()
+        .expect("should have http api context")
+        .duties_cache_misses
+        .load(Ordering::Relaxed)
+}
+
+#[test]
+fn validator_duties_warm() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let epoch = Epoch::new(1);
+
+    let validators = beacon_chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("pubkey should be valid"))
+        .collect::<Vec<PublicKey>>();
+
+    env.runtime()
+        .block_on(remote_node.http.validator().warm_duties(epoch, &validators))
+        .expect("should warm duties via http api");
+
+    let misses_before = duties_cache_misses(&node);
+
+    let duties = validators
+        .iter()
+        .map(|validator| {
+            let mut duties = env
+                .runtime()
+                .block_on(
+                    remote_node
+                        .http
+                        .validator()
+                        .get_duties(epoch, &[validator.clone()]),
+                )
+                .expect("should fetch duties from http api");
+            assert_eq!(duties.len(), 1, "should return a single duty");
+            duties.remove(0)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        duties_cache_misses(&node),
+        misses_before,
+        "the duties should be served from the cache"
+    );
+
+    check_duties(duties, epoch, validators, beacon_chain, spec);
+}
+
+#[test]
+fn validator_duties_all_is_not_cached() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let ctx = node
+        .client
+        .http_api_context()
+        .expect("should have http api context");
+
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    for path in &["all", "active"] {
+        let url = Url::parse(&format!(
+            "http://{}:{}/validator/duties/{}",
+            socket_addr.ip(),
+            socket_addr.port(),
+            path
+        ))
+        .expect("should be valid endpoint");
+
+        let duties = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .json_get::<Vec<ValidatorDutyBytes>>(url, vec![("epoch".into(), "0".into())]),
+            )
+            .expect("should fetch duties from http api");
+        assert_eq!(
+            duties.len(),
+            8,
+            "should return the duties of every validator"
+        );
+
+        assert_eq!(
+            ctx.duties_cache.lock().len(),
+            0,
+            "the duties of the whole validator set should not be cached"
+        );
+    }
+}
+
 #[test]
 fn validator_block_post() {
     let mut env = build_env();
@@ -442,6 +677,7 @@ fn validator_block_post() {
             .as_secs()
             - two_slots_secs,
     };
+    config.rest_api.record_network_messages = true;
 
     let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
@@ -450,6 +686,10 @@ fn validator_block_post() {
         .client
         .beacon_chain()
         .expect("client should have beacon chain");
+    let network_messages = node
+        .client
+        .http_network_messages()
+        .expect("should record network messages");
 
     let slot = Slot::new(1);
     let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
@@ -478,14 +718,24 @@ fn validator_block_post() {
             !publish_status.is_valid(),
             "the unsigned published block should not be valid"
         );
+        assert!(
+            network_messages.messages().is_empty(),
+            "the unsigned block should not be published to the network"
+        );
     }
 
     let signed_block = sign_block(beacon_chain.clone(), block, spec);
     let block_root = signed_block.canonical_root();
+    let proposer_index = signed_block.message.proposer_index;
 
     let publish_status = env
         .runtime()
-        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .publish_block(signed_block.clone()),
+        )
         .expect("should publish block");
 
     if cfg!(not(feature = "fake_crypto")) {
@@ -494,6 +744,11 @@ fn validator_block_post() {
             PublishStatus::Valid,
             "the signed published block should be valid"
         );
+        assert_eq!(
+            network_messages.messages(),
+            vec![PubsubMessage::BeaconBlock(Box::new(signed_block))],
+            "the signed block should be published to the network"
+        );
     }
 
     let head = env
@@ -505,6 +760,10 @@ fn validator_block_post() {
         head.block_root, block_root,
         "the published block should become the head block"
     );
+    assert_eq!(
+        head.proposer_index, proposer_index,
+        "the head should report the proposer of the published block"
+    );
 
     // Note: this heads check is not super useful for this test, however it is include so it get
     // _some_ testing. If you remove this call, make sure it's tested somewhere else.
@@ -525,12 +784,25 @@ fn validator_block_post() {
 }
 
 #[test]
-fn validator_block_get() {
+fn validator_block_post_disabled() {
     let mut env = build_env();
 
     let spec = &E::default_spec();
 
-    let node = build_node(&mut env, testing_client_config());
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+    config.rest_api.enable_block_publishing = false;
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
 
     let beacon_chain = node
@@ -538,39 +810,51 @@ fn validator_block_get() {
         .beacon_chain()
         .expect("client should have beacon chain");
 
-    let slot = Slot::new(1);
-    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+    let block = produce_blocks(beacon_chain.clone(), 1, spec)
+        .pop()
+        .expect("should produce a block");
 
-    let block = env
+    let result = env
         .runtime()
-        .block_on(
-            remote_node
-                .http
-                .validator()
-                .produce_block(slot, randao_reveal.clone(), None),
-        )
-        .expect("should fetch block from http api");
+        .block_on(remote_node.http.validator().publish_block(block));
 
-    let (expected_block, _state) = node
-        .client
-        .beacon_chain()
-        .expect("client should have beacon chain")
-        .produce_block(randao_reveal, slot, None)
-        .expect("should produce block");
+    assert_matches!(
+        result.expect_err("should refuse to publish the block"),
+        remote_beacon_node::Error::ReqwestError(e) => {
+            assert_eq!(e.status(), Some(http::StatusCode::FORBIDDEN));
+        }
+    );
+
+    let head = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_head())
+        .expect("should still serve GET requests");
 
     assert_eq!(
-        block, expected_block,
-        "the block returned from the API should be as expected"
+        head.block_root, beacon_chain.genesis_block_root,
+        "the block should not have been imported"
     );
 }
 
 #[test]
-fn validator_block_get_with_graffiti() {
+fn beacon_events() {
     let mut env = build_env();
 
     let spec = &E::default_spec();
 
-    let node = build_node(&mut env, testing_client_config());
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
 
     let beacon_chain = node
@@ -578,643 +862,4960 @@ fn validator_block_get_with_graffiti() {
         .beacon_chain()
         .expect("client should have beacon chain");
 
+    let unknown_topic = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_events(&["banana"]));
+    assert_matches!(
+        unknown_topic.err().expect("should not subscribe to an unknown topic"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+
     let slot = Slot::new(1);
-    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
 
     let block = env
         .runtime()
-        .block_on(remote_node.http.validator().produce_block(
-            slot,
-            randao_reveal.clone(),
-            Some(*b"test-graffiti-test-graffiti-test"),
-        ))
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
         .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
 
-    let (expected_block, _state) = node
-        .client
-        .beacon_chain()
-        .expect("client should have beacon chain")
-        .produce_block(
-            randao_reveal,
-            slot,
-            Some(*b"test-graffiti-test-graffiti-test"),
-        )
-        .expect("should produce block");
+    let events = env.runtime().block_on(async {
+        let events = remote_node
+            .http
+            .beacon()
+            .get_events(&["block", "head"])
+            .await
+            .expect("should subscribe to events");
+        let mut events = Box::pin(events);
+
+        let publish_status = remote_node
+            .http
+            .validator()
+            .publish_block(signed_block)
+            .await
+            .expect("should publish block");
+        assert_eq!(publish_status, PublishStatus::Valid);
+
+        let mut received = vec![];
+        for _ in 0..2 {
+            received.push(
+                events
+                    .next()
+                    .await
+                    .expect("stream should not end")
+                    .expect("should receive event"),
+            );
+        }
+        received
+    });
 
     assert_eq!(
-        block, expected_block,
-        "the block returned from the API should be as expected"
+        events[0].topic, "block",
+        "the block should be imported first"
+    );
+    assert_eq!(
+        events[1].topic, "head",
+        "the block should then become the head"
     );
+    for event in &events {
+        assert!(
+            event.data.contains(&format!("{:?}", block_root)),
+            "the {} event should refer to the published block",
+            event.topic
+        );
+    }
 }
 
 #[test]
-fn beacon_state() {
+fn validator_block_post_incorrect_proposer() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-
-    let (state_by_slot, root) = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_state_by_slot(Slot::new(0)))
-        .expect("should fetch state from http api");
+    let spec = &E::default_spec();
 
-    let (state_by_root, root_2) = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_state_by_root(root))
-        .expect("should fetch state from http api");
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
 
-    let mut db_state = node
-        .client
-        .beacon_chain()
-        .expect("client should have beacon chain")
-        .state_at_slot(Slot::new(0), StateSkipConfig::WithStateRoots)
-        .expect("should find state");
-    db_state.drop_all_caches();
-
-    assert_eq!(
-        root, root_2,
-        "the two roots returned from the api should be identical"
-    );
-    assert_eq!(
-        root,
-        db_state.canonical_root(),
-        "root from database should match that from the API"
-    );
-    assert_eq!(
-        state_by_slot, db_state,
-        "genesis state by slot from api should match that from the DB"
-    );
-    assert_eq!(
-        state_by_root, db_state,
-        "genesis state by root from api should match that from the DB"
-    );
-}
-
-#[test]
-fn beacon_block() {
-    let mut env = build_env();
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+    config.rest_api.record_network_messages = true;
 
-    let node = build_node(&mut env, testing_client_config());
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let (block_by_slot, root) = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_block_by_slot(Slot::new(0)))
-        .expect("should fetch block from http api");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let network_messages = node
+        .client
+        .http_network_messages()
+        .expect("should record network messages");
 
-    let (block_by_root, root_2) = env
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+
+    let mut block = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_block_by_root(root))
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
         .expect("should fetch block from http api");
 
-    let db_block = node
-        .client
-        .beacon_chain()
-        .expect("client should have beacon chain")
-        .block_at_slot(Slot::new(0))
-        .expect("should find block")
-        .expect("block should not be none");
+    // Claim the block was produced by another validator and sign it with that validator's key,
+    // so the only fault with the block is the proposer index.
+    let proposer_index = (block.proposer_index + 1) % 8;
+    block.proposer_index = proposer_index;
 
-    assert_eq!(
-        root, root_2,
-        "the two roots returned from the api should be identical"
-    );
-    assert_eq!(
-        root,
-        db_block.canonical_root(),
-        "root from database should match that from the API"
+    let head = beacon_chain.head().expect("should get head");
+    let signed_block = block.sign(
+        &generate_deterministic_keypair(proposer_index as usize).sk,
+        &head.beacon_state.fork,
+        head.beacon_state.genesis_validators_root,
+        spec,
     );
-    assert_eq!(
-        block_by_slot, db_block,
-        "genesis block by slot from api should match that from the DB"
+
+    let publish_status = env
+        .runtime()
+        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .expect("should publish block");
+
+    assert!(
+        !publish_status.is_valid(),
+        "a block from the incorrect proposer should not be valid"
     );
-    assert_eq!(
-        block_by_root, db_block,
-        "genesis block by root from api should match that from the DB"
+    assert!(
+        network_messages.messages().is_empty(),
+        "a block from the incorrect proposer should not be published to the network"
     );
 }
 
 #[test]
-fn genesis_time() {
+fn validator_subscribe_subnet_id() {
     let mut env = build_env();
 
+    let spec = &E::default_spec();
+
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let genesis_time = env
+    let keypair = generate_deterministic_keypair(0);
+    let duties = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_genesis_time())
-        .expect("should fetch genesis time from http api");
-
-    assert_eq!(
-        node.client
-            .beacon_chain()
-            .expect("should have beacon chain")
-            .head()
-            .expect("should get head")
-            .beacon_state
-            .genesis_time,
-        genesis_time,
-        "should match genesis time from head state"
-    );
-}
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .get_duties(Epoch::new(0), &[keypair.pk.clone()]),
+        )
+        .expect("should fetch duties from http api");
+    let duties = &duties[0];
 
-#[test]
-fn genesis_validators_root() {
-    let mut env = build_env();
+    let slot = duties
+        .attestation_slot
+        .expect("should have attestation slot");
+    let attestation_committee_index = duties
+        .attestation_committee_index
+        .expect("should have committee index");
+    let committee_count_at_slot = duties
+        .committee_count_at_slot
+        .expect("should have committee count");
+    let subnet_id = SubnetId::compute_subnet::<E>(
+        slot,
+        attestation_committee_index,
+        committee_count_at_slot,
+        spec,
+    )
+    .unwrap();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let subscription = |subnet_id| ValidatorSubscription {
+        validator_index: 0,
+        attestation_committee_index,
+        slot,
+        committee_count_at_slot,
+        is_aggregator: false,
+        subnet_id,
+    };
 
-    let genesis_validators_root = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_genesis_validators_root())
-        .expect("should fetch genesis time from http api");
+    for subnet_id in &[None, Some(subnet_id)] {
+        let status = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .validator()
+                    .subscribe(vec![subscription(*subnet_id)]),
+            )
+            .expect("should accept subscription");
+        assert_eq!(status, PublishStatus::Valid);
+    }
 
-    assert_eq!(
-        node.client
-            .beacon_chain()
-            .expect("should have beacon chain")
-            .head()
-            .expect("should get head")
-            .beacon_state
-            .genesis_validators_root,
-        genesis_validators_root,
-        "should match genesis time from head state"
+    let wrong_subnet_id = SubnetId::new((*subnet_id + 1) % spec.attestation_subnet_count);
+    assert_matches!(
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .validator()
+                    .subscribe(vec![subscription(Some(wrong_subnet_id))]),
+            )
+            .expect_err("should reject a subscription with the wrong subnet id"),
+        remote_beacon_node::Error::ReqwestError(e) => {
+            assert_eq!(e.status(), Some(http::StatusCode::BAD_REQUEST));
+        }
     );
 }
 
-#[test]
-fn fork() {
-    let mut env = build_env();
+/// Builds a node whose head is `sync_distance` slots behind the current slot and which refuses to
+/// produce blocks beyond a sync distance of `tolerance`.
+fn build_node_with_sync_distance(
+    env: &mut Environment<E>,
+    sync_distance: u64,
+    tolerance: u64,
+) -> LocalBeaconNode<E> {
+    let spec = &E::default_spec();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let sync_distance_secs = (spec.milliseconds_per_slot / 1_000) * sync_distance;
 
-    let fork = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_fork())
-        .expect("should fetch from http api");
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - sync_distance_secs,
+    };
+    config.rest_api.sync_tolerance = Some(tolerance);
 
-    assert_eq!(
-        node.client
-            .beacon_chain()
-            .expect("should have beacon chain")
-            .head()
-            .expect("should get head")
-            .beacon_state
-            .fork,
-        fork,
-        "should match head state"
-    );
+    build_node(env, config)
 }
 
-#[test]
-fn eth2_config() {
+fn produce_block_with_sync_distance(
+    sync_distance: u64,
+    tolerance: u64,
+) -> Result<BeaconBlock<E>, remote_beacon_node::Error> {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
+    let spec = &E::default_spec();
+
+    let node = build_node_with_sync_distance(&mut env, sync_distance, tolerance);
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let eth2_config = env
-        .runtime()
-        .block_on(remote_node.http.spec().get_eth2_config())
-        .expect("should fetch eth2 config from http api");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
 
-    // TODO: check the entire eth2_config, not just the spec.
+    let slot = beacon_chain.slot().expect("should read slot");
+    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
 
-    assert_eq!(
-        node.client
-            .beacon_chain()
-            .expect("should have beacon chain")
-            .spec,
-        eth2_config.spec,
-        "should match genesis time from head state"
-    );
+    env.runtime().block_on(
+        remote_node
+            .http
+            .validator()
+            .produce_block(slot, randao_reveal, None),
+    )
 }
 
 #[test]
-fn get_version() {
-    let mut env = build_env();
-
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+fn validator_block_get_within_sync_tolerance() {
+    produce_block_with_sync_distance(2, 8).expect("a synced node should produce a block");
+}
 
-    let version = env
-        .runtime()
-        .block_on(remote_node.http.node().get_version())
-        .expect("should fetch version from http api");
+#[test]
+fn validator_block_get_beyond_sync_tolerance() {
+    let result = produce_block_with_sync_distance(16, 8);
 
-    assert_eq!(
-        lighthouse_version::version_with_platform(),
-        version,
-        "result should be as expected"
+    assert_matches!(
+        result.expect_err("an unsynced node should not produce a block"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+        }
     );
 }
 
 #[test]
-fn get_genesis_state_root() {
+fn validator_block_get_beyond_sync_tolerance_retry_after() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-
-    let slot = Slot::new(0);
+    let spec = &E::default_spec();
 
-    let result = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_state_root(slot))
-        .expect("should fetch from http api");
+    let node = build_node_with_sync_distance(&mut env, 16, 8);
 
-    let expected = node
+    let beacon_chain = node
         .client
         .beacon_chain()
-        .expect("should have beacon chain")
-        .rev_iter_state_roots()
-        .expect("should get iter")
-        .map(Result::unwrap)
-        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
-        .map(|(cur_root, _)| cur_root)
-        .expect("chain should have state root at slot");
+        .expect("client should have beacon chain");
 
-    assert_eq!(result, expected, "result should be as expected");
+    let slot = beacon_chain.slot().expect("should read slot");
+    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let uri = format!(
+        "http://{}:{}/validator/block?slot={}&randao_reveal=0x{}",
+        socket_addr.ip(),
+        socket_addr.port(),
+        slot.as_u64(),
+        hex::encode(randao_reveal.as_ssz_bytes())
+    )
+    .parse::<hyper::Uri>()
+    .expect("should be valid uri");
+
+    let response = env
+        .runtime()
+        .block_on(hyper::Client::new().get(uri))
+        .expect("should get response");
+
+    assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+
+    let retry_after = response
+        .headers()
+        .get(hyper::header::RETRY_AFTER)
+        .expect("an unsynced node should suggest when to retry")
+        .to_str()
+        .expect("Retry-After should be a string")
+        .parse::<u64>()
+        .expect("Retry-After should be a number of seconds");
+    assert!(retry_after >= 1, "should not suggest retrying immediately");
 }
 
 #[test]
-fn get_genesis_block_root() {
+fn validator_duties_beyond_sync_tolerance_retry_after() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let spec = &E::default_spec();
 
-    let slot = Slot::new(0);
+    let node = build_node_with_sync_distance(&mut env, 16, 8);
+    let remote_node = node.remote_node().expect("should produce remote node");
 
-    let result = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_block_root(slot))
-        .expect("should fetch from http api");
-
-    let expected = node
+    let beacon_chain = node
         .client
         .beacon_chain()
-        .expect("should have beacon chain")
-        .rev_iter_block_roots()
-        .expect("should get iter")
-        .map(Result::unwrap)
-        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
-        .map(|(cur_root, _)| cur_root)
-        .expect("chain should have state root at slot");
-
-    assert_eq!(result, expected, "result should be as expected");
-}
+        .expect("client should have beacon chain");
+    let has_sync_observation = || {
+        node.client
+            .http_api_context()
+            .expect("should have http api context")
+            .sync_observation
+            .lock()
+            .is_some()
+    };
 
-#[test]
-fn get_validators() {
-    let mut env = build_env();
+    let epoch = Epoch::new(0);
+    let pubkeys = beacon_chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .validators
+        .iter()
+        .map(|validator| validator.pubkey.clone())
+        .collect::<Vec<_>>();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let socket_addr = node
         .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
-    let state = &chain.head().expect("should get head").beacon_state;
-
-    let validators = state.validators.iter().take(2).collect::<Vec<_>>();
-    let pubkeys = validators
-        .iter()
-        .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
-        .collect();
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let request = hyper::Request::post(format!(
+        "http://{}:{}/validator/duties",
+        socket_addr.ip(),
+        socket_addr.port(),
+    ))
+    .header(hyper::header::CONTENT_TYPE, "application/json")
+    .body(hyper::Body::from(
+        serde_json::to_vec(&ValidatorDutiesRequest {
+            epoch,
+            pubkeys: pubkeys.clone(),
+        })
+        .expect("should serialize request"),
+    ))
+    .expect("should build request");
 
-    let result = env
+    let response = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_validators(pubkeys, None))
-        .expect("should fetch from http api");
+        .block_on(hyper::Client::new().request(request))
+        .expect("should get response");
 
-    result
+    assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+    assert!(
+        response.headers().get(hyper::header::RETRY_AFTER).is_some(),
+        "an unsynced node should suggest when to retry"
+    );
+    assert!(
+        has_sync_observation(),
+        "the sync speed should be observed whilst syncing"
+    );
+
+    // Import blocks up to the current slot, bringing the head within the tolerance.
+    let sync_distance = beacon_chain.slot().expect("should read slot").as_u64();
+    for block in produce_blocks(beacon_chain.clone(), sync_distance, spec) {
+        beacon_chain
+            .process_block(block)
+            .expect("should import block");
+    }
+    beacon_chain.fork_choice().expect("should run fork choice");
+
+    let pubkeys = pubkeys
         .iter()
-        .zip(validators.iter())
-        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+        .map(|pubkey| pubkey.try_into().expect("should decode pubkey bytes"))
+        .collect::<Vec<PublicKey>>();
+    env.runtime()
+        .block_on(remote_node.http.validator().get_duties(epoch, &pubkeys))
+        .expect("a synced node should return duties");
+    assert!(
+        !has_sync_observation(),
+        "the sync speed observation should be reset once synced"
+    );
 }
 
 #[test]
-fn get_all_validators() {
+fn beacon_block_validate_repeatedly() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
-        .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
-    let state = &chain.head().expect("should get head").beacon_state;
-
-    let result = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_all_validators(None))
-        .expect("should fetch from http api");
+    let spec = &E::default_spec();
 
-    result
-        .iter()
-        .zip(state.validators.iter())
-        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
-}
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
 
-#[test]
-fn get_active_validators() {
-    let mut env = build_env();
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
 
-    let node = build_node(&mut env, testing_client_config());
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+
+    let beacon_chain = node
         .client
         .beacon_chain()
-        .expect("node should have beacon chain");
-    let state = &chain.head().expect("should get head").beacon_state;
+        .expect("client should have beacon chain");
 
-    let result = env
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+
+    let block = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_active_validators(None))
-        .expect("should fetch from http api");
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
 
-    /*
-     * This test isn't comprehensive because all of the validators in the state are active (i.e.,
-     * there is no one to exclude.
-     *
-     * This should be fixed once we can generate more interesting scenarios with the
-     * `NodeTestRig`.
-     */
+    let head_before = beacon_chain.head_info().expect("should get head info");
 
-    let validators = state
-        .validators
-        .iter()
-        .filter(|validator| validator.is_active_at(state.current_epoch()));
+    let validate = |env: &mut Environment<E>, block: &SignedBeaconBlock<E>| {
+        env.runtime()
+            .block_on(remote_node.http.beacon().validate_block(block.clone()))
+            .expect("should validate block")
+    };
 
-    result
-        .iter()
-        .zip(validators)
-        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+    let first_status = validate(&mut env, &signed_block);
+    let second_status = validate(&mut env, &signed_block);
+    assert_eq!(
+        first_status, second_status,
+        "both requests should give the same result"
+    );
+    if cfg!(not(feature = "fake_crypto")) {
+        assert_eq!(
+            first_status,
+            PublishStatus::Valid,
+            "the signed block should be valid"
+        );
+    }
+
+    // An invalid block should be reported as invalid each time, including from the cache.
+    let mut invalid_block = signed_block.message.clone();
+    invalid_block.state_root = Hash256::repeat_byte(42);
+    let invalid_block = sign_block(beacon_chain.clone(), invalid_block, spec);
+    for _ in 0..2 {
+        assert!(
+            matches!(
+                validate(&mut env, &invalid_block),
+                PublishStatus::Invalid(_)
+            ),
+            "the block with the wrong state root should be invalid"
+        );
+    }
+
+    assert_eq!(
+        beacon_chain
+            .head_info()
+            .expect("should get head info")
+            .block_root,
+        head_before.block_root,
+        "the validated block should not be imported"
+    );
+
+    // Validation should leave the block importable.
+    beacon_chain
+        .process_block(signed_block)
+        .expect("should import the validated block");
 }
 
+#[cfg(feature = "proposer_override")]
 #[test]
-fn get_committees() {
+fn validator_block_post_proposer_override() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+    config.rest_api.record_network_messages = true;
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+
+    let beacon_chain = node
         .client
         .beacon_chain()
-        .expect("node should have beacon chain");
+        .expect("client should have beacon chain");
+    let network_messages = node
+        .client
+        .http_network_messages()
+        .expect("should record network messages");
 
-    let epoch = Epoch::new(0);
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+    let proposer_index = (beacon_chain
+        .block_proposer(slot)
+        .expect("should get proposer index") as u64
+        + 1)
+        % 8;
 
-    let result = env
+    let block = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_committees(epoch))
-        .expect("should fetch from http api");
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block_with_proposer_index(slot, randao_reveal, proposer_index),
+        )
+        .expect("should fetch block from http api");
 
-    let expected = chain
-        .head()
-        .expect("should get head")
-        .beacon_state
-        .get_beacon_committees_at_epoch(RelativeEpoch::Current)
-        .expect("should get committees")
-        .iter()
-        .map(|c| Committee {
-            slot: c.slot,
-            index: c.index,
-            committee: c.committee.to_vec(),
-        })
-        .collect::<Vec<_>>();
+    assert_eq!(
+        block.proposer_index, proposer_index,
+        "the block should be produced for the overridden proposer"
+    );
 
-    assert_eq!(result, expected, "result should be as expected");
+    let head = beacon_chain.head().expect("should get head");
+    let signed_block = block.sign(
+        &generate_deterministic_keypair(proposer_index as usize).sk,
+        &head.beacon_state.fork,
+        head.beacon_state.genesis_validators_root,
+        spec,
+    );
+
+    let publish_status = env
+        .runtime()
+        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .expect("should publish block");
+
+    assert!(
+        !publish_status.is_valid(),
+        "a block from a non-scheduled proposer should not be valid"
+    );
+    assert!(
+        network_messages.messages().is_empty(),
+        "a block from a non-scheduled proposer should not be published to the network"
+    );
 }
 
 #[test]
-fn get_fork_choice() {
+fn validator_block_get() {
     let mut env = build_env();
 
+    let spec = &E::default_spec();
+
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let fork_choice = env
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+
+    let block = env
         .runtime()
-        .block_on(remote_node.http.advanced().get_fork_choice())
-        .expect("should not error when getting fork choice");
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal.clone(), None),
+        )
+        .expect("should fetch block from http api");
+
+    let (expected_block, _state) = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .produce_block(randao_reveal, slot, None)
+        .expect("should produce block");
 
     assert_eq!(
-        fork_choice,
-        *node
-            .client
-            .beacon_chain()
-            .expect("node should have beacon chain")
-            .fork_choice
-            .read()
-            .proto_array()
-            .core_proto_array(),
-        "result should be as expected"
+        block, expected_block,
+        "the block returned from the API should be as expected"
     );
 }
 
 #[test]
-fn get_operation_pool() {
+fn validator_block_get_with_graffiti() {
     let mut env = build_env();
 
+    let spec = &E::default_spec();
+
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let result = env
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+
+    let block = env
+        .runtime()
+        .block_on(remote_node.http.validator().produce_block(
+            slot,
+            randao_reveal.clone(),
+            Some(*b"test-graffiti-test-graffiti-test"),
+        ))
+        .expect("should fetch block from http api");
+
+    let (expected_block, _state) = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .produce_block(
+            randao_reveal,
+            slot,
+            Some(*b"test-graffiti-test-graffiti-test"),
+        )
+        .expect("should produce block");
+
+    assert_eq!(
+        block, expected_block,
+        "the block returned from the API should be as expected"
+    );
+}
+
+#[test]
+fn validator_block_get_with_invalid_randao_reveal() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let slot = Slot::new(1);
+
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let url = Url::parse(&format!(
+        "http://{}:{}/validator/block",
+        socket_addr.ip(),
+        socket_addr.port()
+    ))
+    .expect("should be valid endpoint");
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.json_get::<BeaconBlock<E>>(
+            url,
+            vec![
+                ("slot".into(), format!("{}", slot.as_u64())),
+                ("randao_reveal".into(), "0xdeadbeef".into()),
+            ],
+        ));
+
+    assert_matches!(
+        result.expect_err("should not produce a block with a malformed randao_reveal"),
+        remote_beacon_node::Error::DidNotSucceed { status, body } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+            assert!(
+                body.starts_with("Invalid randao_reveal: malformed signature bytes"),
+                "should describe the signature as malformed, got: {}",
+                body
+            );
+        }
+    );
+
+    // Sign the randao reveal with the key of a validator other than the proposer.
+    let proposer_index = beacon_chain
+        .block_proposer(slot)
+        .expect("should get proposer index");
+    let head = beacon_chain.head().expect("should get head");
+    let epoch = slot.epoch(E::slots_per_epoch());
+    let domain = spec.get_domain(
+        epoch,
+        Domain::Randao,
+        &head.beacon_state.fork,
+        head.beacon_state.genesis_validators_root,
+    );
+    let wrong_randao_reveal = generate_deterministic_keypair(proposer_index + 1)
+        .sk
+        .sign(epoch.signing_root(domain));
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, wrong_randao_reveal, None),
+        );
+
+    if cfg!(not(feature = "fake_crypto")) {
+        assert_matches!(
+            result.expect_err("should not produce a block with a randao_reveal from the wrong key"),
+            remote_beacon_node::Error::DidNotSucceed { status, body } => {
+                assert_eq!(status, http::StatusCode::BAD_REQUEST);
+                assert_eq!(body, "Invalid randao_reveal: signature verification failed");
+            }
+        );
+    }
+}
+
+#[test]
+fn beacon_state() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let (state_by_slot, root) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_state_by_slot(Slot::new(0)))
+        .expect("should fetch state from http api");
+
+    let (state_by_root, root_2) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_state_by_root(root))
+        .expect("should fetch state from http api");
+
+    let mut db_state = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .state_at_slot(Slot::new(0), StateSkipConfig::WithStateRoots)
+        .expect("should find state");
+    db_state.drop_all_caches();
+
+    assert_eq!(
+        root, root_2,
+        "the two roots returned from the api should be identical"
+    );
+    assert_eq!(
+        root,
+        db_state.canonical_root(),
+        "root from database should match that from the API"
+    );
+    assert_eq!(
+        state_by_slot, db_state,
+        "genesis state by slot from api should match that from the DB"
+    );
+    assert_eq!(
+        state_by_root, db_state,
+        "genesis state by root from api should match that from the DB"
+    );
+}
+
+#[test]
+fn beacon_block() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let (block_by_slot, root) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_by_slot(Slot::new(0)))
+        .expect("should fetch block from http api");
+
+    let (block_by_root, root_2) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_by_root(root))
+        .expect("should fetch block from http api");
+
+    let db_block = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .block_at_slot(Slot::new(0))
+        .expect("should find block")
+        .expect("block should not be none");
+
+    assert_eq!(
+        root, root_2,
+        "the two roots returned from the api should be identical"
+    );
+    assert_eq!(
+        root,
+        db_block.canonical_root(),
+        "root from database should match that from the API"
+    );
+    assert_eq!(
+        block_by_slot, db_block,
+        "genesis block by slot from api should match that from the DB"
+    );
+    assert_eq!(
+        block_by_root, db_block,
+        "genesis block by root from api should match that from the DB"
+    );
+}
+
+#[test]
+fn beacon_state_and_block_ssz() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon = remote_node.http.beacon();
+
+    let (json_state, json_state_root) = env
+        .runtime()
+        .block_on(beacon.get_state_by_slot(Slot::new(0)))
+        .expect("should fetch state as json");
+    let (ssz_state, ssz_state_root) = env
+        .runtime()
+        .block_on(beacon.get_state_by_root_ssz(json_state_root))
+        .expect("should fetch state as ssz");
+
+    assert_eq!(json_state_root, ssz_state_root, "state roots should match");
+    assert_eq!(json_state, ssz_state, "ssz state should match json state");
+
+    let (json_block, json_block_root) = env
+        .runtime()
+        .block_on(beacon.get_block_by_slot(Slot::new(0)))
+        .expect("should fetch block as json");
+    let (ssz_block, ssz_block_root) = env
+        .runtime()
+        .block_on(beacon.get_block_by_slot_ssz(Slot::new(0)))
+        .expect("should fetch block as ssz");
+
+    assert_eq!(json_block_root, ssz_block_root, "block roots should match");
+    assert_eq!(json_block, ssz_block, "ssz block should match json block");
+}
+
+#[test]
+fn accept_header_negotiation() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let uri = format!(
+        "http://{}:{}/beacon/block?slot=0",
+        socket_addr.ip(),
+        socket_addr.port()
+    );
+
+    let mut get_with_accept = |accept: &str| {
+        let request = hyper::Request::get(uri.as_str())
+            .header("Accept", accept)
+            .body(hyper::Body::empty())
+            .expect("should build request");
+        env.runtime()
+            .block_on(hyper::Client::new().request(request))
+            .expect("should get response")
+    };
+
+    for (accept, status, content_type) in &[
+        ("*/*", http::StatusCode::OK, Some("application/json")),
+        (
+            "application/json",
+            http::StatusCode::OK,
+            Some("application/json"),
+        ),
+        (
+            "application/octet-stream",
+            http::StatusCode::OK,
+            Some("application/octet-stream"),
+        ),
+        (
+            "application/ssz",
+            http::StatusCode::OK,
+            Some("application/ssz"),
+        ),
+        (
+            "text/html, application/octet-stream;q=0.9",
+            http::StatusCode::OK,
+            Some("application/octet-stream"),
+        ),
+        ("text/html", http::StatusCode::NOT_ACCEPTABLE, None),
+    ] {
+        let response = get_with_accept(accept);
+
+        assert_eq!(response.status(), *status, "{}", accept);
+        if let Some(content_type) = content_type {
+            assert_eq!(
+                response
+                    .headers()
+                    .get("content-type")
+                    .expect("should have content type"),
+                content_type,
+                "{}",
+                accept
+            );
+        }
+    }
+}
+
+#[test]
+fn block_root_from_head() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    // Build two competing blocks at the same slot, each forming its own fork.
+    let slot = Slot::new(1);
+    let fork_roots = [
+        *b"fork-a-fork-a-fork-a-fork-a-fork",
+        *b"fork-b-fork-b-fork-b-fork-b-fork",
+    ]
+    .iter()
+    .map(|graffiti| {
+        let randao_reveal = get_randao_reveal(chain.clone(), slot, spec);
+        let block = env
+            .runtime()
+            .block_on(remote_node.http.validator().produce_block(
+                slot,
+                randao_reveal,
+                Some(*graffiti),
+            ))
+            .expect("should fetch block from http api");
+        let signed_block = sign_block(chain.clone(), block, spec);
+        chain
+            .process_block(signed_block)
+            .expect("should import block")
+    })
+    .collect::<Vec<_>>();
+
+    assert_ne!(fork_roots[0], fork_roots[1], "forks should be distinct");
+
+    chain.fork_choice().expect("should run fork choice");
+    let canonical_head = chain.head_info().expect("should get head").block_root;
+
+    let canonical_root = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_root(slot))
+        .expect("should fetch block root from http api");
+    assert_eq!(
+        canonical_root,
+        BlockRootResponse {
+            root: canonical_head,
+            canonical: true,
+        },
+        "should resolve the slot on the canonical chain"
+    );
+
+    for head in &fork_roots {
+        let root = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_block_root_from_head(slot, *head),
+            )
+            .expect("should fetch block root from http api");
+        assert_eq!(
+            root.root, *head,
+            "should resolve the slot on the given fork"
+        );
+        assert_eq!(
+            root.canonical,
+            *head == canonical_head,
+            "only the canonical fork should be reported as canonical"
+        );
+    }
+}
+
+#[test]
+fn block_ancestor() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * 4,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let mut canonical_roots = vec![chain.genesis_block_root];
+    for block in produce_blocks(chain.clone(), 2, spec) {
+        canonical_roots.push(chain.process_block(block).expect("should import block"));
+    }
+    chain.fork_choice().expect("should run fork choice");
+
+    // Build two competing blocks at the same slot, so that one of them is on a side chain.
+    let slot = Slot::new(3);
+    let fork_roots = [
+        *b"fork-a-fork-a-fork-a-fork-a-fork",
+        *b"fork-b-fork-b-fork-b-fork-b-fork",
+    ]
+    .iter()
+    .map(|graffiti| {
+        let randao_reveal = get_randao_reveal(chain.clone(), slot, spec);
+        let block = env
+            .runtime()
+            .block_on(remote_node.http.validator().produce_block(
+                slot,
+                randao_reveal,
+                Some(*graffiti),
+            ))
+            .expect("should fetch block from http api");
+        let signed_block = sign_block(chain.clone(), block, spec);
+        chain
+            .process_block(signed_block)
+            .expect("should import block")
+    })
+    .collect::<Vec<_>>();
+
+    assert_ne!(fork_roots[0], fork_roots[1], "forks should be distinct");
+
+    for head in &fork_roots {
+        let mut expected_roots = canonical_roots.clone();
+        expected_roots.push(*head);
+
+        for (distance, expected_root) in expected_roots.iter().rev().enumerate() {
+            let (block, root) = env
+                .runtime()
+                .block_on(
+                    remote_node
+                        .http
+                        .beacon()
+                        .get_block_ancestor(*head, distance as u64),
+                )
+                .expect("should fetch block ancestor from http api");
+            assert_eq!(
+                root, *expected_root,
+                "should walk {} parents from {:?}",
+                distance, head
+            );
+            assert_eq!(
+                block.canonical_root(),
+                root,
+                "should return the named block"
+            );
+        }
+
+        assert_matches!(
+            env.runtime()
+                .block_on(
+                    remote_node
+                        .http
+                        .beacon()
+                        .get_block_ancestor(*head, expected_roots.len() as u64)
+                )
+                .expect_err("should not walk beyond genesis"),
+            remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+                assert_eq!(status, http::StatusCode::NOT_FOUND);
+            }
+        );
+    }
+}
+
+#[test]
+fn block_attestations() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    // Pool an attestation from the genesis slot, so the next block includes it.
+    let state = chain.head().expect("should get head").beacon_state;
+    let committee = state
+        .get_beacon_committee(Slot::new(0), 0)
+        .expect("should get committee")
+        .committee
+        .to_vec();
+    let mut attestation = chain
+        .produce_unaggregated_attestation(Slot::new(0), 0)
+        .expect("should produce attestation");
+    attestation
+        .sign(
+            &generate_deterministic_keypair(committee[0]).sk,
+            0,
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        )
+        .expect("should sign attestation");
+    chain
+        .op_pool
+        .insert_attestation(
+            attestation,
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        )
+        .expect("should insert attestation");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(chain.clone(), block, spec);
+    chain
+        .process_block(signed_block)
+        .expect("should import block");
+    chain.fork_choice().expect("should run fork choice");
+
+    let (head_block, head_root) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_by_slot(slot))
+        .expect("should fetch head block from http api");
+    assert!(
+        !head_block.message.body.attestations.is_empty(),
+        "the head block should include attestations"
+    );
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_attestations(slot, 1))
+        .expect("should fetch block attestations from http api");
+
+    assert_eq!(
+        result,
+        vec![BlockAttestations {
+            block_root: head_root,
+            slot,
+            attestations: head_block.message.body.attestations.to_vec(),
+        }],
+        "should match the attestations in the head block"
+    );
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .get_block_attestations(slot, rest_api::MAX_BLOCK_ATTESTATIONS_SLOTS + 1),
+    );
+    assert_matches!(
+        result.expect_err("should refuse too many slots"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+}
+
+#[test]
+fn block_rewards() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let slots_per_epoch = E::slots_per_epoch();
+
+    // Start far enough past genesis that blocks can be produced throughout the next epoch.
+    let genesis_offset_secs = (spec.milliseconds_per_slot / 1_000) * (slots_per_epoch * 2 + 1);
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - genesis_offset_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain.head().expect("should get head").beacon_state;
+    let slot = Slot::new(1);
+    let proposer_index = chain.block_proposer(slot).expect("should get proposer");
+
+    // Pool an attestation from the genesis slot, so the next block includes it.
+    let committee = state
+        .get_beacon_committee(Slot::new(0), 0)
+        .expect("should get committee")
+        .committee
+        .to_vec();
+    let attester_index = committee[0];
+    let mut attestation = chain
+        .produce_unaggregated_attestation(Slot::new(0), 0)
+        .expect("should produce attestation");
+    attestation
+        .sign(
+            &generate_deterministic_keypair(attester_index).sk,
+            0,
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        )
+        .expect("should sign attestation");
+    chain
+        .op_pool
+        .insert_attestation(
+            attestation,
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        )
+        .expect("should insert attestation");
+
+    // Pool a proposer slashing of another validator, so the next block includes it.
+    let slashed_index = (0..8)
+        .find(|&i| i != proposer_index && i != attester_index)
+        .expect("should find a validator to slash");
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        slashed_index as u64,
+        &generate_deterministic_keypair(slashed_index).sk,
+        &state.fork,
+        state.genesis_validators_root,
+        spec,
+    );
+    chain.op_pool.insert_proposer_slashing(
+        proposer_slashing
+            .validate(&state, spec)
+            .expect("should verify proposer slashing"),
+    );
+
+    let randao_reveal = get_randao_reveal(chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(chain.clone(), block, spec);
+    let block_root = chain
+        .process_block(signed_block.clone())
+        .expect("should import block");
+    chain.fork_choice().expect("should run fork choice");
+
+    assert_eq!(signed_block.message.body.attestations.len(), 1);
+    assert_eq!(signed_block.message.body.proposer_slashings.len(), 1);
+
+    let rewards = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_rewards_by_slot(slot))
+        .expect("should fetch block rewards from http api");
+
+    let total_active_balance = state
+        .get_total_active_balance(spec)
+        .expect("should get total active balance");
+    let base_reward = state_processing::common::get_base_reward(
+        &state,
+        attester_index,
+        total_active_balance,
+        spec,
+    )
+    .expect("should get base reward");
+    let whistleblower_reward =
+        state.validators[slashed_index].effective_balance / spec.whistleblower_reward_quotient;
+
+    assert_eq!(rewards.block_root, block_root);
+    assert_eq!(rewards.proposer_index, proposer_index as u64);
+    assert_eq!(
+        rewards.attestations,
+        base_reward / spec.proposer_reward_quotient
+    );
+    assert_eq!(rewards.proposer_slashings, whistleblower_reward);
+    assert_eq!(rewards.attester_slashings, 0);
+    assert_eq!(
+        rewards.total,
+        rewards.attestations + rewards.proposer_slashings
+    );
+
+    assert_eq!(
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_block_rewards_by_root(block_root)
+            )
+            .expect("should fetch block rewards by root"),
+        rewards
+    );
+
+    // Pool the previous and current epoch attestations of a single validator, so that one block
+    // includes both and the proposer is rewarded for each target epoch.
+    let head_state = chain.head().expect("should get head").beacon_state;
+    let mut duties_state = head_state.clone();
+    duties_state
+        .build_all_committee_caches(spec)
+        .expect("should build committee caches");
+    let attestation_slot = |index, relative_epoch| {
+        duties_state
+            .get_attestation_duties(index, relative_epoch)
+            .expect("should get attestation duties")
+            .expect("validator should have attestation duties")
+            .slot
+    };
+    let (validator_index, previous_slot, current_slot) = (0..8)
+        .filter(|&i| i != slashed_index)
+        .map(|i| {
+            (
+                i,
+                attestation_slot(i, RelativeEpoch::Current),
+                attestation_slot(i, RelativeEpoch::Next),
+            )
+        })
+        .find(|(_, previous_slot, current_slot)| *current_slot < *previous_slot + slots_per_epoch)
+        .expect("should find a validator attesting early in the next epoch");
+
+    for attestation_slot in &[previous_slot, current_slot] {
+        let mut attestation = chain
+            .produce_unaggregated_attestation(*attestation_slot, 0)
+            .expect("should produce attestation");
+        attestation
+            .sign(
+                &generate_deterministic_keypair(validator_index).sk,
+                0,
+                &head_state.fork,
+                head_state.genesis_validators_root,
+                spec,
+            )
+            .expect("should sign attestation");
+        chain
+            .op_pool
+            .insert_attestation(
+                attestation,
+                &head_state.fork,
+                head_state.genesis_validators_root,
+                spec,
+            )
+            .expect("should insert attestation");
+    }
+
+    let next_slot = current_slot + 1;
+    let randao_reveal = get_randao_reveal(chain.clone(), next_slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(next_slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(chain.clone(), block, spec);
+    chain
+        .process_block(signed_block.clone())
+        .expect("should import block");
+    chain.fork_choice().expect("should run fork choice");
+
+    let mut target_epochs = signed_block
+        .message
+        .body
+        .attestations
+        .iter()
+        .map(|attestation| attestation.data.target.epoch)
+        .collect::<Vec<_>>();
+    target_epochs.sort();
+    assert_eq!(
+        target_epochs,
+        vec![
+            previous_slot.epoch(slots_per_epoch),
+            current_slot.epoch(slots_per_epoch)
+        ]
+    );
+
+    let rewards = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_block_rewards_by_slot(next_slot),
+        )
+        .expect("should fetch block rewards from http api");
+
+    let mut reward_state = head_state;
+    while reward_state.slot < next_slot {
+        per_slot_processing(&mut reward_state, None, spec).expect("should process slot");
+    }
+    reward_state
+        .build_all_committee_caches(spec)
+        .expect("should build committee caches");
+    let total_active_balance = reward_state
+        .get_total_active_balance(spec)
+        .expect("should get total active balance");
+    let base_reward = state_processing::common::get_base_reward(
+        &reward_state,
+        validator_index,
+        total_active_balance,
+        spec,
+    )
+    .expect("should get base reward");
+
+    assert_eq!(
+        rewards.attestations,
+        2 * (base_reward / spec.proposer_reward_quotient)
+    );
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_block_rewards_by_slot(Slot::new(0)))
+            .expect_err("should not return rewards for the genesis block"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+    assert_matches!(
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_block_rewards_by_root(Hash256::repeat_byte(42))
+            )
+            .expect_err("should not return rewards for an unknown block"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::NOT_FOUND);
+        }
+    );
+}
+
+#[test]
+fn genesis_time() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let genesis_time = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_genesis_time())
+        .expect("should fetch genesis time from http api");
+
+    assert_eq!(
+        node.client
+            .beacon_chain()
+            .expect("should have beacon chain")
+            .head()
+            .expect("should get head")
+            .beacon_state
+            .genesis_time,
+        genesis_time,
+        "should match genesis time from head state"
+    );
+}
+
+#[test]
+fn genesis_validators_root() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let genesis_validators_root = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_genesis_validators_root())
+        .expect("should fetch genesis time from http api");
+
+    assert_eq!(
+        node.client
+            .beacon_chain()
+            .expect("should have beacon chain")
+            .head()
+            .expect("should get head")
+            .beacon_state
+            .genesis_validators_root,
+        genesis_validators_root,
+        "should match genesis time from head state"
+    );
+}
+
+#[test]
+fn validator_liveness() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let num_blocks = 3;
+    let validator_count = 8;
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_blocks + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let mut proposers = vec![];
+    for block in produce_blocks(beacon_chain.clone(), num_blocks, spec) {
+        proposers.push(block.message.proposer_index);
+        beacon_chain
+            .process_block(block)
+            .expect("should import block");
+    }
+
+    let epoch = Epoch::new(0);
+    let indices = (0..validator_count as u64).collect::<Vec<_>>();
+    let liveness = env
+        .runtime()
+        .block_on(remote_node.http.validator().get_liveness(epoch, &indices))
+        .expect("should fetch liveness from http api");
+
+    assert_eq!(
+        liveness.len(),
+        indices.len(),
+        "should return each validator"
+    );
+    for (item, &index) in liveness.iter().zip(indices.iter()) {
+        assert_eq!(item.index, index, "should return validators in order");
+        assert_eq!(item.epoch, epoch, "should return the requested epoch");
+        assert_eq!(
+            item.is_live,
+            proposers.contains(&index),
+            "only block proposers should be live"
+        );
+    }
+
+    assert!(
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .validator()
+                    .get_liveness(Epoch::new(1_000), &indices)
+            )
+            .is_err(),
+        "should not determine liveness for a distant epoch"
+    );
+}
+
+#[test]
+fn genesis_data() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let num_blocks = 3;
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_blocks + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let genesis_state = beacon_chain.head().expect("should get head").beacon_state;
+
+    // Advance the head, so that it differs from the genesis state.
+    for block in produce_blocks(beacon_chain.clone(), num_blocks, spec) {
+        beacon_chain
+            .process_block(block)
+            .expect("should import block");
+    }
+    beacon_chain.fork_choice().expect("should run fork choice");
+    assert_eq!(
+        beacon_chain.head_info().expect("should get head").slot,
+        Slot::new(num_blocks),
+        "the head should have advanced"
+    );
+
+    let genesis_data = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_genesis())
+        .expect("should fetch genesis data from http api");
+
+    assert_eq!(
+        genesis_data,
+        GenesisData {
+            genesis_time: genesis_state.genesis_time,
+            genesis_validators_root: genesis_state.genesis_validators_root,
+            genesis_fork_version: spec.genesis_fork_version,
+        },
+        "should match the genesis state"
+    );
+}
+
+#[test]
+fn fork() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let fork = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_fork())
+        .expect("should fetch from http api");
+
+    assert_eq!(
+        node.client
+            .beacon_chain()
+            .expect("should have beacon chain")
+            .head()
+            .expect("should get head")
+            .beacon_state
+            .fork,
+        fork,
+        "should match head state"
+    );
+}
+
+#[test]
+fn malformed_state_id() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    for path in &[
+        "beacon/state",
+        "beacon/fork",
+        "beacon/state/finality_checkpoints",
+        "beacon/validators/all",
+    ] {
+        let url = Url::parse(&format!(
+            "http://{}:{}/{}",
+            socket_addr.ip(),
+            socket_addr.port(),
+            path
+        ))
+        .expect("should be valid endpoint");
+
+        let result = env.runtime().block_on(
+            remote_node
+                .http
+                .json_get::<serde_json::Value>(url, vec![("state_id".into(), "banana".into())]),
+        );
+
+        assert_matches!(
+            result.expect_err("should not accept a malformed state id"),
+            remote_beacon_node::Error::DidNotSucceed { status, body } => {
+                assert_eq!(status, http::StatusCode::BAD_REQUEST, "{}", path);
+                assert!(
+                    body.starts_with("Invalid state id \"banana\""),
+                    "{} should describe the invalid state id, got: {}",
+                    path,
+                    body
+                );
+            }
+        );
+    }
+}
+
+#[test]
+fn pre_genesis_guard() {
+    let mut env = build_env();
+
+    // A genesis far enough in the future that the test cannot reach it.
+    let genesis_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 3_600;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time,
+    };
+
+    let get_spec_status = |env: &mut Environment<E>, node: &LocalBeaconNode<E>| {
+        let socket_addr = node
+            .client
+            .http_listen_addr()
+            .expect("A remote beacon node must have a http server");
+        let uri = format!("http://{}:{}/spec", socket_addr.ip(), socket_addr.port())
+            .parse::<hyper::Uri>()
+            .expect("should be valid uri");
+        env.runtime()
+            .block_on(hyper::Client::new().get(uri))
+            .expect("should get response")
+            .status()
+    };
+
+    let guarded_node = build_node(&mut env, config.clone());
+    let remote_node = guarded_node
+        .remote_node()
+        .expect("should produce remote node");
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_genesis())
+            .expect_err("should not serve genesis data prior to genesis"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+        }
+    );
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_genesis_validators_root())
+            .expect_err("should not serve the genesis validators root prior to genesis"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+        }
+    );
+    assert_eq!(
+        get_spec_status(&mut env, &guarded_node),
+        http::StatusCode::SERVICE_UNAVAILABLE,
+        "should not serve the spec prior to genesis"
+    );
+    assert_eq!(
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_genesis_time())
+            .expect("should serve the genesis time for validator clients"),
+        genesis_time
+    );
+
+    config.rest_api.allow_insecure_genesis_sync = true;
+    let insecure_node = build_node(&mut env, config);
+    let remote_node = insecure_node
+        .remote_node()
+        .expect("should produce remote node");
+
+    let genesis = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_genesis())
+        .expect("should serve genesis data prior to genesis when allowed");
+    assert_eq!(genesis.genesis_time, genesis_time);
+    assert_eq!(
+        get_spec_status(&mut env, &insecure_node),
+        http::StatusCode::OK,
+        "should serve the spec prior to genesis when allowed"
+    );
+}
+
+#[test]
+fn finalized_only() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let num_blocks = 3;
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_blocks + 1),
+    };
+    config.rest_api.finalized_only = true;
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    // Advance the head beyond the finalized checkpoint, which remains at genesis.
+    for block in produce_blocks(beacon_chain.clone(), num_blocks, spec) {
+        beacon_chain
+            .process_block(block)
+            .expect("should import block");
+    }
+    beacon_chain.fork_choice().expect("should run fork choice");
+    assert_eq!(
+        beacon_chain.head_info().expect("should get head").slot,
+        Slot::new(num_blocks),
+        "the head should be ahead of finality"
+    );
+
+    let mut get_with_state_id = |state_id: &str| {
+        let url = Url::parse(&format!(
+            "http://{}:{}/beacon/state/finality_checkpoints",
+            socket_addr.ip(),
+            socket_addr.port(),
+        ))
+        .expect("should be valid endpoint");
+
+        env.runtime().block_on(
+            remote_node
+                .http
+                .json_get::<serde_json::Value>(url, vec![("state_id".into(), state_id.into())]),
+        )
+    };
+
+    assert_matches!(
+        get_with_state_id("head").expect_err("should not serve the head state"),
+        remote_beacon_node::Error::DidNotSucceed { status, body } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+            assert!(
+                body.starts_with("Only finalized data is served"),
+                "should describe why the state was refused, got: {}",
+                body
+            );
+        }
+    );
+    get_with_state_id("finalized").expect("should serve the finalized state");
+
+    let assert_refused = |result: Result<(), remote_beacon_node::Error>, what: &str| {
+        assert_matches!(
+            result.expect_err(&format!("should not serve {}", what)),
+            remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+                assert_eq!(status, http::StatusCode::BAD_REQUEST, "{}", what);
+            }
+        );
+    };
+    let head = beacon_chain.head_info().expect("should get head");
+    let beacon = remote_node.http.beacon();
+
+    assert_refused(
+        env.runtime().block_on(beacon.get_head()).map(|_| ()),
+        "the head",
+    );
+    assert_refused(
+        env.runtime().block_on(beacon.get_heads()).map(|_| ()),
+        "the heads",
+    );
+    assert_refused(
+        env.runtime()
+            .block_on(beacon.get_block_by_root(head.block_root))
+            .map(|_| ()),
+        "the head block",
+    );
+    assert_refused(
+        env.runtime()
+            .block_on(beacon.get_block_root(head.slot))
+            .map(|_| ()),
+        "the head block root",
+    );
+
+    // Requests which do not identify a state read the finalized state, rather than the head.
+    let page = env
+        .runtime()
+        .block_on(beacon.get_validators_page(None, None, None, 0, 1))
+        .expect("should serve the finalized validators");
+    assert_eq!(
+        page.state_root,
+        beacon_chain
+            .get_block(&beacon_chain.genesis_block_root)
+            .expect("should read genesis block")
+            .expect("should have genesis block")
+            .state_root(),
+        "should read the finalized state"
+    );
+}
+
+#[test]
+fn finalized_flag() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let slots_per_epoch = E::slots_per_epoch();
+
+    // Fill five epochs with full participation, which advances the finalized checkpoint.
+    let num_slots = slots_per_epoch * 5;
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_slots + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    produce_full_participation_chain(beacon_chain.clone(), num_slots, spec);
+
+    let head = beacon_chain.head_info().expect("should get head");
+    let finalized_epoch = head.finalized_checkpoint.epoch;
+    assert!(finalized_epoch > 0, "the chain should have finalized");
+    let finalized_slot = finalized_epoch.start_slot(slots_per_epoch);
+
+    for (slot, expected) in &[(finalized_slot, true), (head.slot, false)] {
+        let block = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_block_response_by_slot(*slot))
+            .expect("should fetch block from http api");
+        assert_eq!(
+            block.finalized, *expected,
+            "block at slot {} should have the correct finalized flag",
+            slot
+        );
+
+        let by_root = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_block_response_by_root(block.root),
+            )
+            .expect("should fetch block by root from http api");
+        assert_eq!(by_root.finalized, *expected);
+
+        let state = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_state_response_by_slot(*slot))
+            .expect("should fetch state from http api");
+        assert_eq!(
+            state.finalized, *expected,
+            "state at slot {} should have the correct finalized flag",
+            slot
+        );
+
+        let pubkeys = state
+            .beacon_state
+            .validators
+            .iter()
+            .take(2)
+            .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
+            .collect();
+        let validators = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_validators(pubkeys, Some(state.root)),
+            )
+            .expect("should fetch validators from http api");
+        assert!(
+            validators
+                .iter()
+                .all(|validator| validator.finalized == *expected),
+            "validators at slot {} should have the correct finalized flag",
+            slot
+        );
+    }
+
+    // The state resolved by `StateId::Finalized` is finalized.
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let url = Url::parse(&format!(
+        "http://{}:{}/beacon/state",
+        socket_addr.ip(),
+        socket_addr.port(),
+    ))
+    .expect("should be valid endpoint");
+    let state = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .json_get::<remote_beacon_node::StateResponse<E>>(
+                    url,
+                    vec![("state_id".into(), "finalized".into())],
+                ),
+        )
+        .expect("should fetch finalized state from http api");
+    assert!(state.finalized, "the finalized state should be finalized");
+}
+
+/// Returns the number of fork requests to the HTTP API of `node` which have required loading a
+/// state.
+fn fork_state_loads(node: &LocalBeaconNode<E>) -> u64 {
+    node.client
+        .http_api_context()
+        .expect("should have http api context")
+        .fork_state_loads
+        .load(Ordering::Relaxed)
+}
+
+#[test]
+fn fork_at_state_root_is_cached() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let head = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .head()
+        .expect("should get head");
+
+    let get_fork = |env: &mut Environment<E>| {
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_fork_at_state_root(head.beacon_state_root),
+            )
+            .expect("should fetch from http api")
+    };
+
+    let fork = get_fork(&mut env);
+    assert_eq!(fork, head.beacon_state.fork, "should match head state");
+    assert_eq!(
+        fork_state_loads(&node),
+        1,
+        "the first request should load the state"
+    );
+
+    let fork = get_fork(&mut env);
+    assert_eq!(fork, head.beacon_state.fork, "should match head state");
+    assert_eq!(
+        fork_state_loads(&node),
+        1,
+        "the second request should be served from the cache"
+    );
+}
+
+#[test]
+fn randao_mix() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    // Cross an epoch boundary, so the mixes of the two epochs differ.
+    let num_blocks = E::slots_per_epoch() + 1;
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_blocks + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain");
+
+    let genesis = chain.head().expect("should get head");
+    for block in produce_blocks(chain.clone(), num_blocks, spec) {
+        chain.process_block(block).expect("should import block");
+    }
+    chain.fork_choice().expect("should run fork choice");
+
+    let head_state = chain.head().expect("should get head").beacon_state;
+    let current_epoch = head_state.current_epoch();
+    assert_eq!(
+        current_epoch,
+        Epoch::new(1),
+        "should be in the second epoch"
+    );
+
+    let mut get_mix = |state_root: Option<Hash256>, epoch: Option<Epoch>| {
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_randao_mix(state_root, epoch))
+    };
+
+    let head_mix = get_mix(None, None).expect("should fetch the head mix");
+    assert_eq!(
+        head_mix,
+        *head_state
+            .get_randao_mix(current_epoch)
+            .expect("should read mix"),
+        "should default to the current epoch of the head state"
+    );
+
+    let previous_mix = get_mix(None, Some(Epoch::new(0))).expect("should fetch a prior mix");
+    assert_eq!(
+        previous_mix,
+        *head_state
+            .get_randao_mix(Epoch::new(0))
+            .expect("should read mix"),
+        "should read the requested epoch"
+    );
+    assert_ne!(previous_mix, head_mix, "mixes should change over epochs");
+
+    assert_eq!(
+        get_mix(Some(genesis.beacon_state_root), None).expect("should fetch the genesis mix"),
+        *genesis
+            .beacon_state
+            .get_randao_mix(Epoch::new(0))
+            .expect("should read mix"),
+        "should read from the requested state"
+    );
+
+    assert_matches!(
+        get_mix(None, Some(current_epoch + 1)).expect_err("should not read a future mix"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+}
+
+/// Returns the number of finality checkpoints requests to the HTTP API of `node` which have
+/// required loading a state.
+fn finality_checkpoints_state_loads(node: &LocalBeaconNode<E>) -> u64 {
+    node.client
+        .http_api_context()
+        .expect("should have http api context")
+        .finality_checkpoints_state_loads
+        .load(Ordering::Relaxed)
+}
+
+#[test]
+fn finality_checkpoints_are_cached() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain");
+
+    // The chain is at genesis, so the head state is also the finalized state.
+    let head = chain.head().expect("should get head");
+    assert_eq!(
+        head.beacon_state.finalized_checkpoint.epoch,
+        Epoch::new(0),
+        "should be finalized at genesis"
+    );
+    let state_root = head.beacon_state_root;
+    let state = head.beacon_state;
+    let expected = FinalityCheckpoints::from(&state);
+
+    let get_checkpoints = |env: &mut Environment<E>| {
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_finality_checkpoints(state_root),
+            )
+            .expect("should fetch from http api")
+    };
+
+    assert_eq!(get_checkpoints(&mut env), expected, "should match state");
+    assert_eq!(
+        finality_checkpoints_state_loads(&node),
+        1,
+        "the first request should load the state"
+    );
+
+    assert_eq!(
+        get_checkpoints(&mut env),
+        expected,
+        "cached checkpoints should match state"
+    );
+    assert_eq!(
+        finality_checkpoints_state_loads(&node),
+        1,
+        "the second request should be served from the cache"
+    );
+}
+
+/// Returns the number of ids of the given kind resolved by requests, for the counter vec metric
+/// with the given `name`.
+fn id_resolutions(name: &str, kind: &str) -> u64 {
+    lighthouse_metrics::gather()
+        .iter()
+        .find(|family| family.get_name() == name)
+        .and_then(|family| {
+            family
+                .get_metric()
+                .iter()
+                .find(|metric| metric.get_label().iter().any(|l| l.get_value() == kind))
+                .map(|metric| metric.get_counter().get_value() as u64)
+        })
+        .unwrap_or(0)
+}
+
+#[test]
+fn id_resolution_metrics() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let head = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .head()
+        .expect("should get head");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let state_root = format!("{:?}", head.beacon_state_root);
+    let state_ids = [
+        ("head", "head"),
+        ("genesis", "genesis"),
+        ("finalized", "finalized"),
+        ("justified", "justified"),
+        ("slot", "0"),
+        ("root", state_root.as_str()),
+    ];
+
+    let state_resolutions =
+        |kind| id_resolutions("beacon_http_api_state_id_resolutions_total", kind);
+    let block_resolutions =
+        |kind| id_resolutions("beacon_http_api_block_id_resolutions_total", kind);
+
+    // Other tests may resolve ids concurrently, so the counters may increase by more than the
+    // requests made here.
+    for &(kind, state_id) in state_ids.iter() {
+        let before = state_resolutions(kind);
+
+        let url = Url::parse(&format!(
+            "http://{}:{}/beacon/state/finality_checkpoints",
+            socket_addr.ip(),
+            socket_addr.port(),
+        ))
+        .expect("should be valid endpoint");
+        env.runtime()
+            .block_on(remote_node.http.json_get::<serde_json::Value>(
+                url,
+                vec![("state_id".into(), state_id.to_string())],
+            ))
+            .expect("should fetch finality checkpoints from http api");
+
+        assert!(
+            state_resolutions(kind) >= before + 1,
+            "should count the resolution of a {} state id",
+            kind
+        );
+    }
+
+    let before = block_resolutions("slot");
+    env.runtime()
+        .block_on(remote_node.http.beacon().get_block_by_slot(Slot::new(0)))
+        .expect("should fetch block by slot from http api");
+    assert!(
+        block_resolutions("slot") >= before + 1,
+        "should count the resolution of a block slot"
+    );
+
+    let before = block_resolutions("root");
+    env.runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_block_by_root(head.beacon_block_root),
+        )
+        .expect("should fetch block by root from http api");
+    assert!(
+        block_resolutions("root") >= before + 1,
+        "should count the resolution of a block root"
+    );
+}
+
+#[test]
+fn eth2_config() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let eth2_config = env
+        .runtime()
+        .block_on(remote_node.http.spec().get_eth2_config())
+        .expect("should fetch eth2 config from http api");
+
+    // TODO: check the entire eth2_config, not just the spec.
+
+    assert_eq!(
+        node.client
+            .beacon_chain()
+            .expect("should have beacon chain")
+            .spec,
+        eth2_config.spec,
+        "should match genesis time from head state"
+    );
+}
+
+#[test]
+fn get_version() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let version = env
+        .runtime()
+        .block_on(remote_node.http.node().get_version())
+        .expect("should fetch version from http api");
+
+    assert_eq!(
+        lighthouse_version::version_with_platform(),
+        version,
+        "result should be as expected"
+    );
+}
+
+#[test]
+fn get_peer_count() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let peer_count = env
+        .runtime()
+        .block_on(remote_node.http.network().get_peer_count())
+        .expect("should fetch peer count from http api");
+
+    assert_eq!(peer_count, 0, "an isolated node should have no peers");
+}
+
+#[test]
+fn get_node_identity() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let network_globals = node
+        .client
+        .network_globals()
+        .expect("node should have network globals");
+
+    let identity = env
+        .runtime()
+        .block_on(remote_node.http.node().get_identity())
+        .expect("should fetch identity from http api");
+
+    assert_eq!(
+        identity.peer_id,
+        network_globals.local_peer_id().to_base58(),
+        "should return the local peer id"
+    );
+    assert_eq!(
+        identity.enr,
+        node.client
+            .enr()
+            .expect("node should have an enr")
+            .to_base64(),
+        "should return the local enr"
+    );
+    assert_eq!(
+        identity.p2p_addresses,
+        node.client
+            .libp2p_listen_addresses()
+            .expect("node should have listen addresses")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        "should return the libp2p listen addresses"
+    );
+    assert!(
+        identity
+            .discovery_addresses
+            .iter()
+            .all(|addr| addr.contains("/udp/")),
+        "discovery addresses should be udp addresses"
+    );
+}
+
+#[test]
+fn get_node_peers() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let network_globals = node
+        .client
+        .network_globals()
+        .expect("node should have network globals");
+
+    let outbound = PeerId::random();
+    let inbound = PeerId::random();
+    let dialing = PeerId::random();
+    let disconnected = PeerId::random();
+    {
+        let mut peers = network_globals.peers.write();
+        peers.connect_outgoing(&outbound);
+        peers.connect_ingoing(&inbound);
+        peers.dialing_peer(&dialing);
+        peers.connect_ingoing(&disconnected);
+        peers.disconnect(&disconnected);
+    }
+
+    let peer_count = env
+        .runtime()
+        .block_on(remote_node.http.node().get_peer_count())
+        .expect("should fetch peer count from http api");
+    assert_eq!(
+        peer_count,
+        PeerCount {
+            connected: 2,
+            connecting: 1,
+            disconnected: 1,
+        },
+        "should count peers in each state"
+    );
+
+    let mut get_peers = |states: Option<&[PeerState]>, directions: Option<&[PeerDirection]>| {
+        let mut peers = env
+            .runtime()
+            .block_on(remote_node.http.node().get_peers(states, directions))
+            .expect("should fetch peers from http api")
+            .into_iter()
+            .map(|peer| (peer.peer_id, peer.state, peer.direction))
+            .collect::<Vec<_>>();
+        peers.sort_by(|a, b| a.0.cmp(&b.0));
+        peers
+    };
+    let expected = |peers: Vec<(&PeerId, PeerState, Option<PeerDirection>)>| {
+        let mut peers = peers
+            .into_iter()
+            .map(|(peer_id, state, direction)| (peer_id.to_string(), state, direction))
+            .collect::<Vec<_>>();
+        peers.sort_by(|a, b| a.0.cmp(&b.0));
+        peers
+    };
+
+    assert_eq!(
+        get_peers(None, None),
+        expected(vec![
+            (
+                &outbound,
+                PeerState::Connected,
+                Some(PeerDirection::Outbound)
+            ),
+            (&inbound, PeerState::Connected, Some(PeerDirection::Inbound)),
+            (
+                &dialing,
+                PeerState::Connecting,
+                Some(PeerDirection::Outbound)
+            ),
+            (&disconnected, PeerState::Disconnected, None),
+        ]),
+        "should return all peers"
+    );
+    assert_eq!(
+        get_peers(Some(&[PeerState::Connected]), None),
+        expected(vec![
+            (
+                &outbound,
+                PeerState::Connected,
+                Some(PeerDirection::Outbound)
+            ),
+            (&inbound, PeerState::Connected, Some(PeerDirection::Inbound)),
+        ]),
+        "should filter by state"
+    );
+    assert_eq!(
+        get_peers(None, Some(&[PeerDirection::Outbound])),
+        expected(vec![
+            (
+                &outbound,
+                PeerState::Connected,
+                Some(PeerDirection::Outbound)
+            ),
+            (
+                &dialing,
+                PeerState::Connecting,
+                Some(PeerDirection::Outbound)
+            ),
+        ]),
+        "should filter by direction"
+    );
+    assert_eq!(
+        get_peers(
+            Some(&[PeerState::Connected, PeerState::Disconnected]),
+            Some(&[PeerDirection::Inbound])
+        ),
+        expected(vec![(
+            &inbound,
+            PeerState::Connected,
+            Some(PeerDirection::Inbound)
+        )]),
+        "should filter by state and direction"
+    );
+}
+
+/// Returns the liveness reported by a node with a genesis time of `now + genesis_offset_slots`
+/// slots.
+fn get_liveness_with_genesis_offset(genesis_offset_slots: i64) -> NodeLiveness {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let genesis_offset_secs = (spec.milliseconds_per_slot / 1_000) as i64 * genesis_offset_slots;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: (now + genesis_offset_secs) as u64,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    env.runtime()
+        .block_on(remote_node.http.node().get_liveness())
+        .expect("should fetch liveness from http api")
+}
+
+#[test]
+fn get_liveness() {
+    assert_eq!(
+        get_liveness_with_genesis_offset(-2),
+        NodeLiveness::Synced,
+        "a node within the sync tolerance should be synced"
+    );
+    assert_eq!(
+        get_liveness_with_genesis_offset(-(SYNC_TOLERANCE as i64) - 8),
+        NodeLiveness::Syncing,
+        "a node beyond the sync tolerance should be syncing"
+    );
+    assert_eq!(
+        get_liveness_with_genesis_offset(8),
+        NodeLiveness::Unavailable,
+        "a node prior to genesis should be unavailable"
+    );
+}
+
+#[test]
+fn get_genesis_state_root() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let slot = Slot::new(0);
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_state_root(slot))
+        .expect("should fetch from http api");
+
+    let expected = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .rev_iter_state_roots()
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
+        .map(|(cur_root, _)| cur_root)
+        .expect("chain should have state root at slot");
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_genesis_block_root() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let slot = Slot::new(0);
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_root(slot))
+        .expect("should fetch from http api");
+
+    let expected = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .rev_iter_block_roots()
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
+        .map(|(cur_root, _)| cur_root)
+        .expect("chain should have state root at slot");
+
+    assert_eq!(result.root, expected, "result should be as expected");
+    assert!(result.canonical, "genesis block should be canonical");
+}
+
+#[test]
+fn get_validators() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let validators = state.validators.iter().take(2).collect::<Vec<_>>();
+    let pubkeys = validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
+        .collect();
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators(pubkeys, None))
+        .expect("should fetch from http api");
+
+    result
+        .iter()
+        .zip(validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+/// Returns the number of validator lookups to the HTTP API of `node` which have required building
+/// the pubkey cache of a state.
+fn validator_pubkey_scans(node: &LocalBeaconNode<E>) -> u64 {
+    node.client
+        .http_api_context()
+        .expect("should have http api context")
+        .validator_pubkey_scans
+        .load(Ordering::Relaxed)
+}
+
+/// Imports blocks which vote for an eth1 deposit of a new validator with the given `keypair`, the
+/// last of which includes the deposit.
+fn import_deposit<T: BeaconChainTypes>(
+    beacon_chain: Arc<BeaconChain<T>>,
+    keypair: &Keypair,
+    spec: &ChainSpec,
+) {
+    let mut builder = TestingDepositBuilder::new(keypair.pk.clone(), spec.max_effective_balance);
+    builder.sign(DepositTestTask::Valid, keypair, spec);
+    let mut deposit = builder.build();
+
+    let mut state = beacon_chain.head().expect("should get head").beacon_state;
+    let deposit_index = state.eth1_deposit_index as usize;
+
+    // Only the proof of the new deposit is verified, so the leaves of existing deposits may be
+    // anything.
+    let mut leaves = vec![Hash256::zero(); deposit_index];
+    leaves.push(deposit.data.tree_hash_root());
+    let tree = DepositDataTree::create(&leaves, leaves.len(), DEPOSIT_TREE_DEPTH);
+    deposit.proof = tree.generate_proof(deposit_index).1.into();
+
+    let eth1_data = Eth1Data {
+        deposit_root: tree.root(),
+        deposit_count: leaves.len() as u64,
+        block_hash: Hash256::repeat_byte(42),
+    };
+
+    let votes_required = T::EthSpec::slots_per_eth1_voting_period() as u64 / 2 + 1;
+    for slot in 1..=votes_required {
+        let slot = Slot::new(slot);
+        let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+        let (mut block, _) = beacon_chain
+            .produce_block_on_state(state.clone(), slot, randao_reveal, None)
+            .expect("should produce block");
+        block.body.eth1_data = eth1_data.clone();
+        if slot == votes_required {
+            block.body.deposits =
+                VariableList::new(vec![deposit.clone()]).expect("should fit a deposit");
+        }
+
+        while state.slot < slot {
+            per_slot_processing(&mut state, None, spec).expect("should process slot");
+        }
+        per_block_processing(
+            &mut state,
+            &SignedBeaconBlock {
+                message: block.clone(),
+                signature: Signature::empty(),
+            },
+            None,
+            BlockSignatureStrategy::NoVerification,
+            spec,
+        )
+        .expect("should process block");
+        block.state_root = state
+            .update_tree_hash_cache()
+            .expect("should compute state root");
+
+        beacon_chain
+            .process_block(sign_block(beacon_chain.clone(), block, spec))
+            .expect("should import block");
+        beacon_chain.fork_choice().expect("should run fork choice");
+    }
+}
+
+#[test]
+fn get_validators_uses_pubkey_cache_at_head() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+    let slots = E::slots_per_eth1_voting_period() as u64;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * slots,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let head = chain.head().expect("should get head");
+    let state = &head.beacon_state;
+
+    let validators = state.validators.iter().collect::<Vec<_>>();
+    let new_keypair = generate_deterministic_keypair(validators.len());
+    let pubkeys = validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
+        .chain(std::iter::once(new_keypair.pk.clone()))
+        .collect::<Vec<PublicKey>>();
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators(pubkeys.clone(), None),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(
+        validator_pubkey_scans(&node),
+        0,
+        "head lookups should not scan the validator set"
+    );
+
+    result
+        .iter()
+        .zip(validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+    assert_eq!(
+        result.last().expect("should have response").validator_index,
+        None,
+        "an unknown validator should have no index"
+    );
+
+    // Queries at a specific state must still build the pubkey cache of that state.
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators(pubkeys.clone(), Some(head.beacon_state_root)),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(
+        validator_pubkey_scans(&node),
+        1,
+        "state root lookups should scan the validator set"
+    );
+
+    result
+        .iter()
+        .zip(validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+
+    // A validator which joins after the pubkey cache was built must be found at the head.
+    import_deposit(chain.clone(), &new_keypair, spec);
+
+    let head = chain.head().expect("should get head");
+    let state = &head.beacon_state;
+    assert_eq!(
+        state.validators.len(),
+        validators.len() + 1,
+        "the deposit should add a validator"
+    );
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators(pubkeys, None))
+        .expect("should fetch from http api");
+    assert_eq!(
+        validator_pubkey_scans(&node),
+        1,
+        "head lookups should not scan the validator set"
+    );
+
+    result
+        .iter()
+        .zip(state.validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+    assert_eq!(
+        result.last().expect("should have response").validator_index,
+        Some(validators.len()),
+        "the new validator should have the next index"
+    );
+}
+
+/// Returns the number of validator lookups to the HTTP API of `node` which were served from, and
+/// which missed, the validator index cache.
+fn validator_index_cache_hits_and_misses(node: &LocalBeaconNode<E>) -> (u64, u64) {
+    let context = node
+        .client
+        .http_api_context()
+        .expect("should have http api context");
+    (
+        context.validator_index_cache_hits.load(Ordering::Relaxed),
+        context.validator_index_cache_misses.load(Ordering::Relaxed),
+    )
+}
+
+#[test]
+fn get_validators_caches_indices_at_state() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let head = chain.head().expect("should get head");
+    let state = &head.beacon_state;
+
+    let validators = state.validators.iter().collect::<Vec<_>>();
+    let pubkeys = validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
+        .collect::<Vec<PublicKey>>();
+
+    assert_eq!(validator_index_cache_hits_and_misses(&node), (0, 0));
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators(pubkeys.clone(), Some(head.beacon_state_root)),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(
+        validator_index_cache_hits_and_misses(&node),
+        (0, 1),
+        "the first lookup should miss the cache"
+    );
+    result
+        .iter()
+        .zip(validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators(pubkeys, Some(head.beacon_state_root)),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(
+        validator_index_cache_hits_and_misses(&node),
+        (1, 1),
+        "the second lookup should hit the cache"
+    );
+    result
+        .iter()
+        .zip(validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_validator_at_states() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let genesis_state_root = chain.head().expect("should get head").beacon_state_root;
+
+    // Import a block so there are two distinct states to query.
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(chain.clone(), block, spec);
+    chain
+        .process_block(signed_block)
+        .expect("should import block");
+    chain.fork_choice().expect("should run fork choice");
+
+    let head_state_root = chain.head().expect("should get head").beacon_state_root;
+    assert_ne!(
+        genesis_state_root, head_state_root,
+        "the head should have advanced"
+    );
+
+    let state_roots = vec![genesis_state_root, head_state_root];
+    let pubkey: PublicKey = (&chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .validators[0]
+        .pubkey)
+        .try_into()
+        .expect("should decode pubkey bytes");
+
+    let batch = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validator_at_states(pubkey, state_roots.clone()),
+        )
+        .expect("should fetch from http api");
+
+    assert_eq!(
+        batch.len(),
+        state_roots.len(),
+        "should have one response per state"
+    );
+
+    state_roots
+        .iter()
+        .zip(batch.iter())
+        .for_each(|(state_root, response)| {
+            let state = chain
+                .get_state(state_root, None)
+                .expect("should read state")
+                .expect("should have state");
+            compare_validator_response(&state, response, &state.validators[0]);
+
+            let single = env
+                .runtime()
+                .block_on(
+                    remote_node
+                        .http
+                        .beacon()
+                        .get_validators(vec![pubkey.clone()], Some(*state_root)),
+                )
+                .expect("should fetch from http api");
+            assert_eq!(
+                single,
+                vec![response.clone()],
+                "should match the per-state get_validators response"
+            );
+        });
+
+    let too_many_roots = vec![head_state_root; rest_api::MAX_VALIDATOR_STATES + 1];
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .get_validator_at_states(pubkey, too_many_roots),
+    );
+    assert_matches!(
+        result.expect_err("should refuse too many state roots"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+}
+
+#[test]
+fn get_all_validators() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_all_validators(None))
+        .expect("should fetch from http api");
+
+    result
+        .iter()
+        .zip(state.validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_active_validators() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_active_validators(None))
+        .expect("should fetch from http api");
+
+    /*
+     * This test isn't comprehensive because all of the validators in the state are active (i.e.,
+     * there is no one to exclude.
+     *
+     * This should be fixed once we can generate more interesting scenarios with the
+     * `NodeTestRig`.
+     */
+
+    let validators = state
+        .validators
+        .iter()
+        .filter(|validator| validator.is_active_at(state.current_epoch()));
+
+    result
+        .iter()
+        .zip(validators)
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_validators_page() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let head = chain.head().expect("should get head");
+    let state = &head.beacon_state;
+    let total = state.validators.len() as u64;
+
+    let page = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators_page(None, None, None, 2, 3),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(page.state_root, head.beacon_state_root);
+    assert_eq!(page.total, total);
+    assert_eq!(page.validators.len(), 3, "should return a bounded page");
+    page.validators
+        .iter()
+        .zip(state.validators.iter().skip(2))
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+
+    let page = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators_page(None, None, None, total - 1, 3),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(page.validators.len(), 1, "should truncate the last page");
+
+    let page = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validators_page(None, None, None, total + 1, 3),
+        )
+        .expect("should fetch from http api");
+    assert!(
+        page.validators.is_empty(),
+        "offset past the end should return an empty page"
+    );
+    assert_eq!(page.total, total);
+
+    let page = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators_page(
+            None,
+            None,
+            None,
+            0,
+            rest_api::MAX_VALIDATORS_PAGE_LIMIT + 1,
+        ))
+        .expect("should fetch from http api");
+    assert!(page.validators.len() as u64 <= rest_api::MAX_VALIDATORS_PAGE_LIMIT);
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_all_validators_paged(
+            Some(head.beacon_state_root),
+            None,
+            None,
+            3,
+        ))
+        .expect("should fetch from http api");
+    assert_eq!(result.len() as u64, total, "should concatenate all pages");
+    result
+        .iter()
+        .zip(state.validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_validators_page_filtered_by_status() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+    let total = state.validators.len() as u64;
+
+    // All validators in the test rig are active from genesis.
+    let statuses = [
+        ValidatorStatus::ActiveOngoing,
+        ValidatorStatus::PendingQueued,
+    ];
+    let page = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators_page(
+            None,
+            None,
+            Some(&statuses[..]),
+            1,
+            3,
+        ))
+        .expect("should fetch from http api");
+    assert_eq!(page.total, total);
+    page.validators
+        .iter()
+        .zip(state.validators.iter().skip(1))
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+
+    let page = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators_page(
+            None,
+            None,
+            Some(&[ValidatorStatus::ExitedSlashed][..]),
+            0,
+            3,
+        ))
+        .expect("should fetch from http api");
+    assert_eq!(page.total, 0, "the filter should apply before paging");
+    assert!(page.validators.is_empty());
+
+    // The status filter intersects with the id filter.
+    let validator_ids = vec![
+        ValidatorId::Index(1),
+        ValidatorId::PublicKey(state.validators[3].pubkey.clone()),
+    ];
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_all_validators_paged(
+            None,
+            Some(validator_ids.as_slice()),
+            Some(&[ValidatorStatus::ActiveOngoing][..]),
+            1,
+        ))
+        .expect("should fetch from http api");
+    assert_eq!(
+        result
+            .iter()
+            .map(|response| response.validator_index)
+            .collect::<Vec<_>>(),
+        vec![Some(1), Some(3)]
+    );
+
+    let page = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators_page(
+            None,
+            Some(validator_ids.as_slice()),
+            Some(&[ValidatorStatus::ActiveExiting][..]),
+            0,
+            3,
+        ))
+        .expect("should fetch from http api");
+    assert_eq!(page.total, 0, "no validator should match both filters");
+
+    let url = Url::parse(&format!(
+        "http://{}:{}/beacon/validators/page",
+        socket_addr.ip(),
+        socket_addr.port(),
+    ))
+    .expect("should be valid endpoint");
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .json_get::<serde_json::Value>(url, vec![("status".into(), "banana".into())]),
+    );
+    assert_matches!(
+        result.expect_err("should not accept an unknown status"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+}
+
+#[test]
+fn get_validator_balances() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let head = chain.head().expect("should get head");
+    let state = &head.beacon_state;
+
+    let expected = state
+        .balances
+        .iter()
+        .enumerate()
+        .map(|(index, balance)| ValidatorBalance {
+            index: index as u64,
+            balance: *balance,
+        })
+        .collect::<Vec<_>>();
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validator_balances(None, None))
+        .expect("should fetch from http api");
+    assert_eq!(result, expected, "should return all balances");
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validator_balances(Some(head.beacon_state_root), None),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(result, expected, "should return all balances at state root");
+
+    let unknown_pubkey = generate_deterministic_keypair(state.validators.len()).pk;
+    let validator_ids = vec![
+        ValidatorId::Index(1),
+        ValidatorId::PublicKey(state.validators[3].pubkey.clone()),
+        ValidatorId::Index(state.validators.len() as u64),
+        ValidatorId::PublicKey(unknown_pubkey.into()),
+    ];
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_validator_balances(None, Some(validator_ids.as_slice())),
+        )
+        .expect("should fetch from http api");
+    assert_eq!(
+        result,
+        vec![expected[1].clone(), expected[3].clone()],
+        "should filter by index and pubkey, omitting unknown validators"
+    );
+}
+
+#[test]
+fn get_committees() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let epoch = Epoch::new(0);
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_committees(epoch))
+        .expect("should fetch from http api");
+
+    let expected = chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .get_beacon_committees_at_epoch(RelativeEpoch::Current)
+        .expect("should get committees")
+        .iter()
+        .map(|c| Committee {
+            slot: c.slot,
+            index: c.index,
+            committee: c.committee.to_vec(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_committees_beyond_next_epoch() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let epoch = chain.epoch().expect("should get epoch") + 5;
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_committees(epoch))
+            .expect_err("should not return committees beyond the next epoch"),
+        remote_beacon_node::Error::DidNotSucceed { status, body } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+            assert!(
+                body.starts_with(&format!("Unable to get committees for epoch {}", epoch)),
+                "should describe why the epoch was refused, got: {}",
+                body
+            );
+        }
+    );
+}
+
+#[test]
+fn get_fork_choice() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let fork_choice = env
+        .runtime()
+        .block_on(remote_node.http.advanced().get_fork_choice())
+        .expect("should not error when getting fork choice");
+
+    assert_eq!(
+        fork_choice,
+        *node
+            .client
+            .beacon_chain()
+            .expect("node should have beacon chain")
+            .fork_choice
+            .read()
+            .proto_array()
+            .core_proto_array(),
+        "result should be as expected"
+    );
+}
+
+#[test]
+fn get_operation_pool() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.advanced().get_operation_pool())
+        .expect("should not error when getting fork choice");
+
+    let expected = PersistedOperationPool::from_operation_pool(
+        &node
+            .client
+            .beacon_chain()
+            .expect("node should have chain")
+            .op_pool,
+    );
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_pool_attestations_aggregate() {
+    let mut env = build_env();
+
+    // Use enough validators that each committee has more than one member.
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 64,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let spec = &chain.spec;
+
+    let state = chain.head().expect("should get head").beacon_state;
+    let slot = chain.slot().expect("should get slot");
+    let committee = state
+        .get_beacon_committee(slot, 0)
+        .expect("should get committee")
+        .committee
+        .to_vec();
+    assert!(
+        committee.len() > 1,
+        "committee should have multiple members"
+    );
+
+    let unsigned = chain
+        .produce_unaggregated_attestation(slot, 0)
+        .expect("should produce attestation");
+    let data_root = unsigned.data.tree_hash_root();
+
+    // Insert a single-bit attestation for each member of the committee.
+    for (committee_position, validator_index) in committee.iter().enumerate() {
+        let mut attestation = unsigned.clone();
+        attestation
+            .sign(
+                &generate_deterministic_keypair(*validator_index).sk,
+                committee_position,
+                &state.fork,
+                state.genesis_validators_root,
+                spec,
+            )
+            .expect("should sign attestation");
+
+        chain
+            .op_pool
+            .insert_attestation(
+                attestation,
+                &state.fork,
+                state.genesis_validators_root,
+                spec,
+            )
+            .expect("should insert attestation");
+    }
+
+    let aggregate = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_pool_attestations_aggregate(slot, data_root),
+        )
+        .expect("should get aggregate from http api");
+
+    assert_eq!(aggregate.data, unsigned.data, "should have the same data");
+    assert_eq!(
+        aggregate.aggregation_bits.num_set_bits(),
+        committee.len(),
+        "should aggregate every inserted attestation"
+    );
+
+    // The aggregate for validators should be served from the operation pool when the naive
+    // aggregation pool does not know the attestation data.
+    let validator_aggregate = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_aggregate_attestation(&unsigned.data),
+        )
+        .expect("should get validator aggregate from http api");
+
+    assert_eq!(
+        validator_aggregate, aggregate,
+        "should serve the aggregate from the operation pool"
+    );
+
+    // Requesting data that is not in the pool should fail.
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .get_pool_attestations_aggregate(slot + 1, data_root),
+    );
+    assert!(result.is_err(), "should not find unknown attestation data");
+}
+
+#[test]
+fn get_pool_summary() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let get_summary = |env: &mut Environment<E>| {
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_pool_summary())
+            .expect("should get pool summary from http api")
+    };
+
+    let summary = get_summary(&mut env);
+    assert_eq!(summary.attestations, 0, "should have no attestations");
+    assert_eq!(
+        summary.attester_slashings, 0,
+        "should have no attester slashings"
+    );
+    assert_eq!(
+        summary.proposer_slashings, 0,
+        "should have no proposer slashings"
+    );
+    assert_eq!(summary.voluntary_exits, 0, "should have no voluntary exits");
+
+    let state = chain.head().expect("should get head").beacon_state;
+    let spec = &chain.spec;
+    let fork = &state.fork;
+    let genesis_validators_root = state.genesis_validators_root;
+
+    // Attestation.
+    let slot = chain.slot().expect("should get slot");
+    let committee = state
+        .get_beacon_committee(slot, 0)
+        .expect("should get committee")
+        .committee
+        .to_vec();
+    let mut attestation = chain
+        .produce_unaggregated_attestation(slot, 0)
+        .expect("should produce attestation");
+    attestation
+        .sign(
+            &generate_deterministic_keypair(committee[0]).sk,
+            0,
+            fork,
+            genesis_validators_root,
+            spec,
+        )
+        .expect("should sign attestation");
+    chain
+        .op_pool
+        .insert_attestation(attestation, fork, genesis_validators_root, spec)
+        .expect("should insert attestation");
+
+    // Proposer slashing.
+    let proposer_index = chain
+        .block_proposer(state.slot)
+        .expect("should get proposer index");
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &generate_deterministic_keypair(proposer_index).sk,
+        fork,
+        genesis_validators_root,
+        spec,
+    );
+    assert!(env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .proposer_slashing(proposer_slashing)
+        )
+        .expect("should insert proposer slashing"));
+
+    // Attester slashing.
+    let attester_index = (proposer_index + 1) % state.validators.len();
+    let attester_slashing = build_double_vote_attester_slashing(
+        AttesterSlashingTestTask::Valid,
+        &[attester_index as u64],
+        &[&generate_deterministic_keypair(attester_index).sk],
+        fork,
+        genesis_validators_root,
+        spec,
+    );
+    assert!(env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .attester_slashing(attester_slashing)
+        )
+        .expect("should insert attester slashing"));
+
+    // Voluntary exit.
+    //
+    // The validators have not been active for long enough to exit, so verify the exit against a
+    // spec without the minimum activity period.
+    let exit_index = (proposer_index + 2) % state.validators.len();
+    let mut exit_spec = spec.clone();
+    exit_spec.shard_committee_period = 0;
+    let exit = TestingVoluntaryExitBuilder::new(state.current_epoch(), exit_index as u64).build(
+        &generate_deterministic_keypair(exit_index).sk,
+        fork,
+        genesis_validators_root,
+        spec,
+    );
+    chain.op_pool.insert_voluntary_exit(
+        exit.validate(&state, &exit_spec)
+            .expect("exit should be valid"),
+    );
+
+    let summary = get_summary(&mut env);
+    assert_eq!(summary.attestations, 1, "should have one attestation");
+    assert_eq!(
+        summary.attester_slashings, 1,
+        "should have one attester slashing"
+    );
+    assert_eq!(
+        summary.proposer_slashings, 1,
+        "should have one proposer slashing"
+    );
+    assert_eq!(summary.voluntary_exits, 1, "should have one voluntary exit");
+}
+
+#[test]
+fn prune_op_pool_is_disabled_by_default() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().prune_op_pool());
+    assert_matches!(
+        result.expect_err("should refuse to prune the op pool"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::FORBIDDEN);
+        }
+    );
+}
+
+#[test]
+fn prune_op_pool() {
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.enable_op_pool_pruning = true;
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain.head().expect("should get head").beacon_state;
+    let spec = &chain.spec;
+    let fork = &state.fork;
+    let genesis_validators_root = state.genesis_validators_root;
+
+    // A valid proposer slashing, which should survive pruning.
+    let proposer_index = 0;
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &generate_deterministic_keypair(proposer_index).sk,
+        fork,
+        genesis_validators_root,
+        spec,
+    );
+    chain.op_pool.insert_proposer_slashing(
+        proposer_slashing
+            .validate(&state, spec)
+            .expect("proposer slashing should be valid"),
+    );
+
+    // An attester slashing from an unknown fork, which should be pruned.
+    let attester_index = 1;
+    let attester_slashing = build_double_vote_attester_slashing(
+        AttesterSlashingTestTask::Valid,
+        &[attester_index as u64],
+        &[&generate_deterministic_keypair(attester_index).sk],
+        fork,
+        genesis_validators_root,
+        spec,
+    );
+    let mut stale_fork = *fork;
+    stale_fork.current_version = [0xff; 4];
+    chain.op_pool.insert_attester_slashing(
+        attester_slashing
+            .validate(&state, spec)
+            .expect("attester slashing should be valid"),
+        stale_fork,
+    );
+
+    let pruned = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().prune_op_pool())
+        .expect("should prune op pool via http api");
+
+    assert_eq!(
+        pruned,
+        PoolSummary {
+            attestations: 0,
+            attester_slashings: 1,
+            proposer_slashings: 0,
+            voluntary_exits: 0,
+        },
+        "should prune only the stale attester slashing"
+    );
+    assert_eq!(
+        chain.op_pool.num_attester_slashings(),
+        0,
+        "the attester slashing should be removed from the pool"
+    );
+    assert_eq!(
+        chain.op_pool.num_proposer_slashings(),
+        1,
+        "the proposer slashing should remain in the pool"
+    );
+}
+
+/// Builds a chain of signed blocks at slots `1..=num_blocks` on top of the head, without importing
+/// any of them (assuming the given `beacon_chain` uses deterministic keypairs).
+fn produce_blocks<T: BeaconChainTypes>(
+    beacon_chain: Arc<BeaconChain<T>>,
+    num_blocks: u64,
+    spec: &ChainSpec,
+) -> Vec<SignedBeaconBlock<T::EthSpec>> {
+    let mut state = beacon_chain.head().expect("should get head").beacon_state;
+    let mut blocks = vec![];
+    for slot in 1..=num_blocks {
+        let slot = Slot::new(slot);
+        let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+        let (block, post_state) = beacon_chain
+            .produce_block_on_state(state, slot, randao_reveal, None)
+            .expect("should produce block");
+        blocks.push(sign_block(beacon_chain.clone(), block, spec));
+        state = post_state;
+    }
+    blocks
+}
+
+#[test]
+fn heads_are_ordered() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * 3,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    // Build three heads on the genesis block: one at slot 1 and two at slot 2 which differ only in
+    // their graffiti.
+    let genesis_state = beacon_chain.head().expect("should get head").beacon_state;
+    let produce_block = |slot: Slot, graffiti| {
+        let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+        let (block, _state) = beacon_chain
+            .produce_block_on_state(genesis_state.clone(), slot, randao_reveal, graffiti)
+            .expect("should produce block");
+        let block = sign_block(beacon_chain.clone(), block, spec);
+        let slot = block.slot();
+        let block_root = beacon_chain
+            .process_block(block)
+            .expect("should import block");
+        HeadBeaconBlock {
+            beacon_block_root: block_root,
+            beacon_block_slot: slot,
+        }
+    };
+    let mut expected = vec![
+        produce_block(Slot::new(2), Some([1; 32])),
+        produce_block(Slot::new(1), None),
+        produce_block(Slot::new(2), Some([2; 32])),
+    ];
+    beacon_chain.fork_choice().expect("should run fork choice");
+
+    expected.sort_by(|a, b| {
+        b.beacon_block_slot
+            .cmp(&a.beacon_block_slot)
+            .then_with(|| a.beacon_block_root.cmp(&b.beacon_block_root))
+    });
+
+    for _ in 0..3 {
+        let heads = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_heads())
+            .expect("should get heads");
+        assert_eq!(
+            heads, expected,
+            "heads should be ordered by descending slot then ascending root"
+        );
+    }
+}
+
+#[test]
+fn import_blocks() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let num_blocks = 3;
+
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_blocks + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let blocks = produce_blocks(beacon_chain, num_blocks, spec);
+
+    let results = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().import_blocks(blocks.clone()))
+        .expect("should import blocks via http api");
+
+    assert_eq!(
+        results.len(),
+        blocks.len(),
+        "should return a result for each block"
+    );
+    for (result, block) in results.iter().zip(blocks.iter()) {
+        assert_eq!(result.block_root, block.canonical_root());
+        assert_eq!(result.slot, block.slot());
+        assert!(
+            result.is_imported(),
+            "block at slot {} should be imported, got {:?}",
+            result.slot,
+            result.error
+        );
+    }
+
+    let head = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_head())
+        .expect("should get head");
+
+    assert_eq!(
+        head.block_root,
+        blocks.last().expect("should have blocks").canonical_root(),
+        "the last imported block should become the head block"
+    );
+}
+
+#[test]
+fn get_recent_blocks() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let block_slots = [1, 2, 4, 5, 7];
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (block_slots[block_slots.len() - 1] + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let genesis_root = beacon_chain.genesis_block_root;
+    let mut blocks = vec![(genesis_root, Slot::new(0))];
+    for &slot in &block_slots {
+        let slot = Slot::new(slot);
+        let state = beacon_chain.head().expect("should get head").beacon_state;
+        let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+        let (block, _state) = beacon_chain
+            .produce_block_on_state(state, slot, randao_reveal, None)
+            .expect("should produce block");
+        let block = sign_block(beacon_chain.clone(), block, spec);
+        let block_root = beacon_chain
+            .process_block(block)
+            .expect("should import block");
+        beacon_chain.fork_choice().expect("should run fork choice");
+        blocks.push((block_root, slot));
+    }
+    blocks.reverse();
+
+    // The canonical block roots, with the repeated roots of skipped slots removed.
+    let mut canonical_blocks = beacon_chain
+        .rev_iter_block_roots()
+        .expect("should get block roots iter")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("should read block roots");
+    canonical_blocks.reverse();
+    canonical_blocks.dedup_by_key(|(root, _slot)| *root);
+    canonical_blocks.reverse();
+    assert_eq!(canonical_blocks, blocks, "test blocks should be canonical");
+
+    for count in 0..=blocks.len() as u64 + 1 {
+        let recent_blocks = env
+            .runtime()
+            .block_on(remote_node.http.lighthouse().get_recent_blocks(count))
+            .expect("should fetch recent blocks from http api")
+            .into_iter()
+            .map(|block| (block.root, block.slot))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            recent_blocks,
+            blocks
+                .iter()
+                .take(count as usize)
+                .cloned()
+                .collect::<Vec<_>>(),
+            "should return the last {} canonical blocks",
+            count
+        );
+    }
+}
+
+#[test]
+fn get_blocks_by_range() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let block_slots = [1, 2, 4, 5, 7];
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (block_slots[block_slots.len() - 1] + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let mut roots = vec![(Slot::new(0), beacon_chain.genesis_block_root)];
+    for &slot in &block_slots {
+        let slot = Slot::new(slot);
+        let state = beacon_chain.head().expect("should get head").beacon_state;
+        let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+        let (block, _state) = beacon_chain
+            .produce_block_on_state(state, slot, randao_reveal, None)
+            .expect("should produce block");
+        let block = sign_block(beacon_chain.clone(), block, spec);
+        let block_root = beacon_chain
+            .process_block(block)
+            .expect("should import block");
+        beacon_chain.fork_choice().expect("should run fork choice");
+        roots.push((slot, block_root));
+    }
+
+    // The range extends beyond the head, which should be treated like skipped slots.
+    for (start_slot, count, step, expected) in vec![
+        (0, 10, None, vec![0, 1, 2, 4, 5, 7]),
+        (1, 4, Some(1), vec![1, 2, 4]),
+        (3, 1, None, vec![]),
+        (0, 5, Some(2), vec![0, 2, 4]),
+        (1, 4, Some(3), vec![1, 4, 7]),
+        (0, 0, None, vec![]),
+    ] {
+        let blocks = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .beacon()
+                    .get_blocks_by_range(Slot::new(start_slot), count, step)
+                    .collect::<Vec<_>>(),
+            )
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("should fetch blocks by range from http api");
+
+        assert_eq!(
+            blocks.iter().map(|block| block.slot()).collect::<Vec<_>>(),
+            expected.into_iter().map(Slot::new).collect::<Vec<_>>(),
+            "should return the non-skipped slots from {} (count {}, step {:?})",
+            start_slot,
+            count,
+            step
+        );
+
+        for block in blocks {
+            assert!(
+                roots.contains(&(block.slot(), block.canonical_root())),
+                "should return the imported block at slot {}",
+                block.slot()
+            );
+        }
+    }
+}
+#[test]
+fn get_current_slot() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let current_slot = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().get_current_slot())
+        .expect("should get current slot from http api");
+
+    assert_eq!(
+        current_slot.slot,
+        chain.slot().expect("should get slot"),
+        "should match the chain slot"
+    );
+    assert_eq!(
+        current_slot.genesis_time,
+        chain
+            .head()
+            .expect("should get head")
+            .beacon_state
+            .genesis_time,
+        "should match the head state genesis time"
+    );
+}
+
+#[test]
+fn validate_spec() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let violations = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().validate_spec())
+        .expect("should validate spec via http api");
+
+    assert!(
+        violations.is_empty(),
+        "the default spec should be self-consistent: {:?}",
+        violations
+    );
+}
+
+fn compare_validator_response<T: EthSpec>(
+    state: &BeaconState<T>,
+    response: &ValidatorResponse,
+    validator: &Validator,
+) {
+    let response_validator = response.validator.clone().expect("should have validator");
+    let i = response
+        .validator_index
+        .expect("should have validator index");
+    let balance = response.balance.expect("should have balance");
+
+    assert_eq!(response.pubkey, validator.pubkey, "pubkey");
+    assert_eq!(response_validator, *validator, "validator");
+    assert_eq!(state.balances[i], balance, "balances");
+    assert_eq!(state.validators[i], *validator, "validator index");
+}
+
+#[test]
+fn proposer_slashing() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+
+    let spec = &chain.spec;
+
+    // Check that there are no proposer slashings before insertion
+    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(proposer_slashings.len(), 0);
+
+    let slot = state.slot;
+    let proposer_index = chain
+        .block_proposer(slot)
+        .expect("should get proposer index");
+    let keypair = generate_deterministic_keypair(proposer_index);
+    let key = &keypair.sk;
+    let fork = &state.fork;
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &key,
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    let result = env
         .runtime()
-        .block_on(remote_node.http.advanced().get_operation_pool())
-        .expect("should not error when getting fork choice");
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .proposer_slashing(proposer_slashing.clone()),
+        )
+        .expect("should fetch from http api");
+    assert!(result, true);
 
-    let expected = PersistedOperationPool::from_operation_pool(
-        &node
-            .client
-            .beacon_chain()
-            .expect("node should have chain")
-            .op_pool,
+    // Length should be just one as we've inserted only one proposer slashing
+    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(proposer_slashing.clone(), proposer_slashings[0]);
+
+    let mut invalid_proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &key,
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+    invalid_proposer_slashing.signed_header_2 = invalid_proposer_slashing.signed_header_1.clone();
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .proposer_slashing(invalid_proposer_slashing),
+    );
+    assert!(result.is_err());
+
+    // Length should still be one as we've inserted nothing since last time.
+    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(proposer_slashing, proposer_slashings[0]);
+}
+
+#[test]
+fn attester_slashing() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let slot = state.slot;
+    let spec = &chain.spec;
+
+    let proposer_index = chain
+        .block_proposer(slot)
+        .expect("should get proposer index");
+    let keypair = generate_deterministic_keypair(proposer_index);
+
+    let secret_keys = vec![&keypair.sk];
+    let validator_indices = vec![proposer_index as u64];
+    let fork = &state.fork;
+
+    // Checking there are no attester slashings before insertion
+    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(attester_slashings.len(), 0);
+
+    let attester_slashing = build_double_vote_attester_slashing(
+        AttesterSlashingTestTask::Valid,
+        &validator_indices[..],
+        &secret_keys[..],
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .attester_slashing(attester_slashing.clone()),
+        )
+        .expect("should fetch from http api");
+    assert!(result, true);
+
+    // Length should be just one as we've inserted only one attester slashing
+    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(attester_slashings.len(), 1);
+    assert_eq!(attester_slashing, attester_slashings[0]);
+
+    // Building an invalid attester slashing
+    let mut invalid_attester_slashing = build_double_vote_attester_slashing(
+        AttesterSlashingTestTask::Valid,
+        &validator_indices[..],
+        &secret_keys[..],
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+    invalid_attester_slashing.attestation_2 = invalid_attester_slashing.attestation_1.clone();
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .attester_slashing(invalid_attester_slashing),
+    );
+    result.unwrap_err();
+
+    // Length should still be one as we've failed to insert the attester slashing.
+    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(attester_slashings.len(), 1);
+    assert_eq!(attester_slashing, attester_slashings[0]);
+}
+
+#[test]
+fn attestation_rewards() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let slots_per_epoch = E::slots_per_epoch();
+
+    // Start far enough in the past that the epoch transition rewarding epoch 0 has occurred.
+    let three_epochs_secs = (spec.milliseconds_per_slot / 1_000) * slots_per_epoch * 3;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - three_epochs_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let epoch = Epoch::new(0);
+
+    let rewards = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_attestation_rewards(epoch, &[]),
+        )
+        .expect("should fetch rewards for all validators");
+
+    assert_eq!(rewards.len(), 8, "should return rewards for all validators");
+
+    // No blocks were produced, so no attestations were included and every validator is penalized
+    // for each component. Ensure the rewards match the change in balance at the epoch transition.
+    let before_transition = beacon_chain
+        .state_at_slot(
+            (epoch + 2).start_slot(slots_per_epoch) - 1,
+            StateSkipConfig::WithStateRoots,
+        )
+        .expect("should get state before transition");
+    let after_transition = beacon_chain
+        .state_at_slot(
+            (epoch + 2).start_slot(slots_per_epoch),
+            StateSkipConfig::WithStateRoots,
+        )
+        .expect("should get state after transition");
+
+    for (i, reward) in rewards.iter().enumerate() {
+        assert_eq!(reward.validator_index, i as u64);
+        assert!(
+            reward.source < 0,
+            "validator should be penalized for the source"
+        );
+        assert_eq!(reward.target, reward.source);
+        assert_eq!(reward.head, reward.source);
+        assert_eq!(reward.inclusion_delay, 0);
+
+        let total = reward.source
+            + reward.target
+            + reward.head
+            + reward.inclusion_delay
+            + reward.inactivity_penalty;
+        assert_eq!(
+            after_transition.balances[i] as i64 - before_transition.balances[i] as i64,
+            total,
+            "rewards should match the change in balance"
+        );
+    }
+
+    let some_rewards = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_attestation_rewards(epoch, &[3, 1]),
+        )
+        .expect("should fetch rewards for some validators");
+
+    assert_eq!(some_rewards, vec![rewards[3].clone(), rewards[1].clone()]);
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.beacon().get_attestation_rewards(epoch, &[8]))
+            .expect_err("should not return rewards for an unknown validator"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
     );
+}
+
+#[test]
+fn attestation_rewards_for_full_participation() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+    let slots_per_epoch = E::slots_per_epoch();
+
+    // Fill four epochs, so the state which rewards epoch 1 is no longer the head.
+    let num_slots = slots_per_epoch * 4 - 1;
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_slots + 1),
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    produce_full_participation_chain(beacon_chain.clone(), num_slots, spec);
+
+    let epoch = Epoch::new(1);
+
+    let rewards = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_attestation_rewards(epoch, &[]),
+        )
+        .expect("should fetch rewards for all validators");
+
+    assert_eq!(rewards.len(), 8, "should return rewards for all validators");
+
+    // Every validator attested during the epoch and was included in the next block. Ensure the
+    // rewards match the change in balance at the epoch transition.
+    let before_transition = beacon_chain
+        .state_at_slot(
+            (epoch + 2).start_slot(slots_per_epoch) - 1,
+            StateSkipConfig::WithStateRoots,
+        )
+        .expect("should get state before transition");
+    let mut after_transition = before_transition.clone();
+    per_slot_processing(&mut after_transition, None, spec).expect("should process epoch");
+
+    for (i, reward) in rewards.iter().enumerate() {
+        assert_eq!(reward.validator_index, i as u64);
+        assert!(
+            reward.source > 0,
+            "validator should be rewarded for the source"
+        );
+        assert_eq!(reward.target, reward.source);
+        assert_eq!(reward.head, reward.source);
+        assert!(
+            reward.inclusion_delay > 0,
+            "validator should be rewarded for the inclusion delay"
+        );
 
-    assert_eq!(result, expected, "result should be as expected");
+        let total = reward.source
+            + reward.target
+            + reward.head
+            + reward.inclusion_delay
+            + reward.inactivity_penalty;
+        assert_eq!(
+            after_transition.balances[i] as i64 - before_transition.balances[i] as i64,
+            total,
+            "rewards should match the change in balance"
+        );
+    }
 }
 
-fn compare_validator_response<T: EthSpec>(
-    state: &BeaconState<T>,
-    response: &ValidatorResponse,
-    validator: &Validator,
+/// Produces and imports a block at each of the `num_slots` slots following genesis, with every
+/// validator attesting to the block at each slot. Attestations are included in the block at the
+/// following slot.
+fn produce_full_participation_chain<T: BeaconChainTypes>(
+    beacon_chain: Arc<BeaconChain<T>>,
+    num_slots: u64,
+    spec: &ChainSpec,
 ) {
-    let response_validator = response.validator.clone().expect("should have validator");
-    let i = response
-        .validator_index
-        .expect("should have validator index");
-    let balance = response.balance.expect("should have balance");
+    let genesis_validators_root = beacon_chain.genesis_validators_root;
 
-    assert_eq!(response.pubkey, validator.pubkey, "pubkey");
-    assert_eq!(response_validator, *validator, "validator");
-    assert_eq!(state.balances[i], balance, "balances");
-    assert_eq!(state.validators[i], *validator, "validator index");
+    for slot in 1..=num_slots {
+        let slot = Slot::new(slot);
+        let state = beacon_chain.head().expect("should get head").beacon_state;
+        let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+        let (block, mut state) = beacon_chain
+            .produce_block_on_state(state, slot, randao_reveal, None)
+            .expect("should produce block");
+        let block = sign_block(beacon_chain.clone(), block, spec);
+        let block_root = beacon_chain
+            .process_block(block)
+            .expect("should import block");
+        beacon_chain.fork_choice().expect("should run fork choice");
+
+        state
+            .build_committee_cache(RelativeEpoch::Current, spec)
+            .expect("should build committee cache");
+        for committee in state
+            .get_beacon_committees_at_slot(slot)
+            .expect("should get committees")
+        {
+            let mut attestation = beacon_chain
+                .produce_unaggregated_attestation_for_block(
+                    slot,
+                    committee.index,
+                    block_root,
+                    Cow::Borrowed(&state),
+                )
+                .expect("should produce attestation");
+            for (position, &validator_index) in committee.committee.iter().enumerate() {
+                attestation
+                    .sign(
+                        &generate_deterministic_keypair(validator_index).sk,
+                        position,
+                        &state.fork,
+                        genesis_validators_root,
+                        spec,
+                    )
+                    .expect("should sign attestation");
+            }
+            beacon_chain
+                .op_pool
+                .insert_attestation(attestation, &state.fork, genesis_validators_root, spec)
+                .expect("should insert attestation into op pool");
+        }
+    }
 }
 
 #[test]
-fn proposer_slashing() {
+fn validator_effectiveness() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
+    let spec = &E::default_spec();
+    let slots_per_epoch = E::slots_per_epoch();
+    let validator_count = 8;
+
+    // Fill four epochs, which finalizes the inclusion of attestations during the first three.
+    let num_slots = slots_per_epoch * 4 - 1;
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_slots + 1),
+    };
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let beacon_chain = node
         .client
         .beacon_chain()
-        .expect("node should have beacon chain");
-
-    let state = chain
-        .head()
-        .expect("should have retrieved state")
-        .beacon_state;
+        .expect("client should have beacon chain");
 
-    let spec = &chain.spec;
+    produce_full_participation_chain(beacon_chain.clone(), num_slots, spec);
+
+    // No validator attests to the genesis block, so epoch 0 is excluded.
+    let epochs = 2;
+    let mut all_effectiveness = vec![];
+    for validator_index in 0..validator_count as u64 {
+        let effectiveness = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .lighthouse()
+                    .get_validator_effectiveness(&ValidatorId::Index(validator_index), epochs),
+            )
+            .expect("should fetch effectiveness from http api");
+
+        assert_eq!(effectiveness.validator_index, validator_index);
+        assert_eq!(
+            effectiveness
+                .epochs
+                .iter()
+                .map(|epoch| epoch.epoch)
+                .collect::<Vec<_>>(),
+            vec![Epoch::new(1), Epoch::new(2)],
+            "should evaluate the most recent epochs with final inclusion"
+        );
+        for epoch in &effectiveness.epochs {
+            assert_eq!(
+                epoch.inclusion_delay,
+                Some(1),
+                "should be included promptly"
+            );
+            assert!(epoch.is_source_attester && epoch.is_target_attester && epoch.is_head_attester);
+        }
+        assert!(
+            effectiveness
+                .effectiveness
+                .expect("validator should be active")
+                > 0.99,
+            "a fully participating validator should be maximally effective"
+        );
 
-    // Check that there are no proposer slashings before insertion
-    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(proposer_slashings.len(), 0);
+        all_effectiveness.push(effectiveness);
+    }
 
-    let slot = state.slot;
-    let proposer_index = chain
-        .block_proposer(slot)
-        .expect("should get proposer index");
-    let keypair = generate_deterministic_keypair(proposer_index);
-    let key = &keypair.sk;
-    let fork = &state.fork;
-    let proposer_slashing = build_proposer_slashing::<E>(
-        ProposerSlashingTestTask::Valid,
-        proposer_index as u64,
-        &key,
-        fork,
-        state.genesis_validators_root,
-        spec,
+    let by_pubkey = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().get_validator_effectiveness(
+            &ValidatorId::PublicKey(generate_deterministic_keypair(3).pk.into()),
+            epochs,
+        ))
+        .expect("should fetch effectiveness by pubkey from http api");
+    assert_eq!(by_pubkey, all_effectiveness[3], "should identify by pubkey");
+
+    assert_matches!(
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .lighthouse()
+                    .get_validator_effectiveness(&ValidatorId::Index(0), 0)
+            )
+            .expect_err("should not evaluate zero epochs"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
     );
+}
 
-    let result = env
-        .runtime()
-        .block_on(
-            remote_node
-                .http
-                .beacon()
-                .proposer_slashing(proposer_slashing.clone()),
-        )
-        .expect("should fetch from http api");
-    assert!(result, true);
+#[test]
+fn export_pool_attestations() {
+    let mut env = build_env();
 
-    // Length should be just one as we've inserted only one proposer slashing
-    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(proposer_slashings.len(), 1);
-    assert_eq!(proposer_slashing.clone(), proposer_slashings[0]);
+    let spec = &E::default_spec();
+    // Enough validators for each committee to have several members.
+    let validator_count = 64;
+    let num_slots = 2;
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
 
-    let mut invalid_proposer_slashing = build_proposer_slashing::<E>(
-        ProposerSlashingTestTask::Valid,
-        proposer_index as u64,
-        &key,
-        fork,
-        state.genesis_validators_root,
-        spec,
-    );
-    invalid_proposer_slashing.signed_header_2 = invalid_proposer_slashing.signed_header_1.clone();
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_slots + 1),
+    };
 
-    let result = env.runtime().block_on(
-        remote_node
-            .http
-            .beacon()
-            .proposer_slashing(invalid_proposer_slashing),
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    // Fills the op pool with one fully aggregated attestation per committee.
+    produce_full_participation_chain(beacon_chain.clone(), num_slots, spec);
+
+    let head = beacon_chain.head().expect("should get head");
+    let mut state = head.beacon_state;
+    state
+        .build_committee_cache(RelativeEpoch::Current, spec)
+        .expect("should build committee cache");
+
+    // Add a single-signer attestation to the naive aggregation pool.
+    let naive_slot = Slot::new(num_slots);
+    let committee = state
+        .get_beacon_committee(naive_slot, 0)
+        .expect("should get committee");
+    let mut naive_attestation = beacon_chain
+        .produce_unaggregated_attestation_for_block(
+            naive_slot,
+            committee.index,
+            head.beacon_block_root,
+            Cow::Borrowed(&state),
+        )
+        .expect("should produce attestation");
+    naive_attestation
+        .sign(
+            &generate_deterministic_keypair(committee.committee[0]).sk,
+            0,
+            &state.fork,
+            beacon_chain.genesis_validators_root,
+            spec,
+        )
+        .expect("should sign attestation");
+    beacon_chain
+        .naive_aggregation_pool
+        .write()
+        .insert(&naive_attestation)
+        .expect("should insert attestation into naive pool");
+
+    let current_slot = beacon_chain.slot().expect("should get current slot");
+    let exported = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().export_attestations())
+        .expect("should export attestations from http api");
+
+    let mut expected_op_pool = (1..=num_slots)
+        .map(Slot::new)
+        .flat_map(|slot| {
+            state
+                .get_beacon_committees_at_slot(slot)
+                .expect("should get committees")
+                .into_iter()
+                .map(move |committee| (slot, committee.index, committee.committee.len() as u64))
+        })
+        .collect::<Vec<_>>();
+    expected_op_pool.sort();
+
+    let mut op_pool = exported
+        .iter()
+        .filter(|exported| exported.pool == AttestationPoolKind::OpPool)
+        .map(|exported| (exported.slot, exported.committee_index, exported.bit_count))
+        .collect::<Vec<_>>();
+    op_pool.sort();
+
+    assert_eq!(
+        op_pool, expected_op_pool,
+        "should export every op pool attestation with its full bit count"
     );
-    assert!(result.is_err());
 
-    // Length should still be one as we've inserted nothing since last time.
-    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(proposer_slashings.len(), 1);
-    assert_eq!(proposer_slashing, proposer_slashings[0]);
+    let naive = exported
+        .iter()
+        .filter(|exported| exported.pool == AttestationPoolKind::Naive)
+        .collect::<Vec<_>>();
+    assert_eq!(naive.len(), 1, "should export the naive pool attestation");
+    assert_eq!(naive[0].slot, naive_slot);
+    assert_eq!(naive[0].committee_index, 0);
+    assert_eq!(naive[0].bit_count, 1);
+    assert_eq!(naive[0].attestation, naive_attestation);
+
+    for exported in &exported {
+        assert_eq!(exported.slot, exported.attestation.data.slot);
+        assert!(
+            exported.age >= (current_slot - exported.slot).as_u64(),
+            "age should be measured from the current slot"
+        );
+    }
 }
 
 #[test]
-fn attester_slashing() {
+fn validator_duty_history() {
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
+    let spec = &E::default_spec();
+    let num_blocks = E::slots_per_epoch() + 1;
+    let slot_secs = spec.milliseconds_per_slot / 1_000;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - slot_secs * (num_blocks + 1),
+    };
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let beacon_chain = node
         .client
         .beacon_chain()
-        .expect("node should have beacon chain");
-
-    let state = chain
-        .head()
-        .expect("should have retrieved state")
-        .beacon_state;
-    let slot = state.slot;
-    let spec = &chain.spec;
-
-    let proposer_index = chain
-        .block_proposer(slot)
-        .expect("should get proposer index");
-    let keypair = generate_deterministic_keypair(proposer_index);
+        .expect("client should have beacon chain");
 
-    let secret_keys = vec![&keypair.sk];
-    let validator_indices = vec![proposer_index as u64];
-    let fork = &state.fork;
+    for block in produce_blocks(beacon_chain.clone(), num_blocks, spec) {
+        beacon_chain
+            .process_block(block)
+            .expect("should import block");
+    }
+    beacon_chain.fork_choice().expect("should run fork choice");
 
-    // Checking there are no attester slashings before insertion
-    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(attester_slashings.len(), 0);
+    let current_epoch = beacon_chain.epoch().expect("should get epoch");
+    let validator_index = 3;
+    let pubkey = generate_deterministic_keypair(validator_index).pk;
 
-    let attester_slashing = build_double_vote_attester_slashing(
-        AttesterSlashingTestTask::Valid,
-        &validator_indices[..],
-        &secret_keys[..],
-        fork,
-        state.genesis_validators_root,
-        spec,
+    // More epochs are requested than have occurred since genesis.
+    let history = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .lighthouse()
+                .get_validator_duty_history(&ValidatorId::Index(validator_index as u64), 3),
+        )
+        .expect("should fetch duty history from http api");
+    assert_eq!(
+        history.iter().map(|duty| duty.epoch).collect::<Vec<_>>(),
+        (0..=current_epoch.as_u64())
+            .map(Epoch::new)
+            .collect::<Vec<_>>(),
+        "should return each epoch since genesis"
     );
 
-    let result = env
+    let current_duties = env
         .runtime()
         .block_on(
             remote_node
                 .http
-                .beacon()
-                .attester_slashing(attester_slashing.clone()),
+                .validator()
+                .get_duties(current_epoch, &[pubkey.clone()]),
         )
-        .expect("should fetch from http api");
-    assert!(result, true);
-
-    // Length should be just one as we've inserted only one attester slashing
-    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(attester_slashings.len(), 1);
-    assert_eq!(attester_slashing, attester_slashings[0]);
-
-    // Building an invalid attester slashing
-    let mut invalid_attester_slashing = build_double_vote_attester_slashing(
-        AttesterSlashingTestTask::Valid,
-        &validator_indices[..],
-        &secret_keys[..],
-        fork,
-        state.genesis_validators_root,
-        spec,
+        .expect("should fetch duties from http api");
+    assert_eq!(
+        history.last().expect("should have current epoch").duty,
+        current_duties[0],
+        "should match the attester duties of the current epoch"
     );
-    invalid_attester_slashing.attestation_2 = invalid_attester_slashing.attestation_1.clone();
 
-    let result = env.runtime().block_on(
-        remote_node
-            .http
-            .beacon()
-            .attester_slashing(invalid_attester_slashing),
+    let by_pubkey = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .lighthouse()
+                .get_validator_duty_history(&ValidatorId::PublicKey(pubkey.into()), 3),
+        )
+        .expect("should fetch duty history by pubkey from http api");
+    assert_eq!(by_pubkey, history, "should identify by pubkey");
+
+    assert_matches!(
+        env.runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .lighthouse()
+                    .get_validator_duty_history(&ValidatorId::Index(0), 0)
+            )
+            .expect_err("should not return zero epochs"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
     );
-    result.unwrap_err();
-
-    // Length should still be one as we've failed to insert the attester slashing.
-    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(attester_slashings.len(), 1);
-    assert_eq!(attester_slashing, attester_slashings[0]);
 }
 
 mod validator_attestation {
@@ -1329,6 +5930,49 @@ mod validator_attestation {
             }
         );
     }
+
+    #[test]
+    fn rejects_slot_beyond_bounds() {
+        let (mut env, node, client, url, duties) = setup();
+
+        let current_slot = node
+            .client
+            .beacon_chain()
+            .expect("client should have beacon chain")
+            .slot()
+            .expect("should read slot");
+
+        let get_attestation = |slot: Slot| {
+            client.json_get::<Attestation<MinimalEthSpec>>(
+                url.clone(),
+                vec![
+                    ("slot".into(), format!("{}", slot)),
+                    ("committee_index".into(), format!("{}", duties.index)),
+                ],
+            )
+        };
+
+        env.runtime()
+            .block_on(get_attestation(current_slot))
+            .expect("should produce an attestation for the current slot");
+
+        let far_future_slot = current_slot + 100;
+        assert_matches!(
+            env.runtime()
+                .block_on(get_attestation(far_future_slot))
+                .expect_err("should not produce an attestation for a far future slot"),
+            DidNotSucceed { status, body } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(
+                    body,
+                    format!(
+                        "Unable to produce attestation: slot {} is too far from the current slot {}",
+                        far_future_slot, current_slot
+                    )
+                );
+            }
+        );
+    }
 }
 
 #[cfg(target_os = "linux")]
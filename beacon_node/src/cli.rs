@@ -67,6 +67,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("50")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("subnet-subscription-lead-slots")
+                .long("subnet-subscription-lead-slots")
+                .value_name("SLOTS")
+                .help("The number of slots before an attestation duty at which to subscribe to \
+                       its subnet, in addition to a fraction of a slot.")
+                .default_value("0")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("subnet-subscription-linger-slots")
+                .long("subnet-subscription-linger-slots")
+                .value_name("SLOTS")
+                .help("The number of slots after an attestation duty during which to remain \
+                       subscribed to its subnet.")
+                .default_value("0")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("boot-nodes")
                 .long("boot-nodes")
@@ -163,6 +181,48 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("http-sync-tolerance")
+                .long("http-sync-tolerance")
+                .value_name("SLOTS")
+                .help("Refuse to produce blocks or return validator duties via the RESTful HTTP \
+                    API whilst the head is more than this many slots behind the current slot. \
+                    Disabled by default.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-disable-publishing")
+                .long("http-disable-publishing")
+                .value_name("CATEGORIES")
+                .help("Refuse requests to the RESTful HTTP API which publish the given \
+                    comma-separated categories of objects to the network. Possible values are \
+                    \"blocks\", \"attestations\" and \"slashings\". Disabled endpoints respond \
+                    with 403 Forbidden.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-finalized-only")
+                .long("http-finalized-only")
+                .help("Refuse requests to the RESTful HTTP API for states or blocks later than \
+                    the finalized checkpoint. Intended for replicas which only serve finalized \
+                    data.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-allow-insecure-genesis-sync")
+                .long("http-allow-insecure-genesis-sync")
+                .help("Serve the genesis and spec endpoints of the RESTful HTTP API before the \
+                    chain has reached genesis, even though the data may not yet be final. \
+                    Intended for bootstrapping devnets.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-enable-op-pool-pruning")
+                .long("http-enable-op-pool-pruning")
+                .help("Serve requests to the RESTful HTTP API which prune the operation pool. \
+                    Disabled by default, since the API is unauthenticated.")
+                .takes_value(false),
+        )
         /* Websocket related arguments */
         .arg(
             Arg::with_name("ws")
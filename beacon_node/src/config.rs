@@ -112,6 +112,42 @@ pub fn get_config<E: EthSpec>(
         client_config.rest_api.allow_origin = allow_origin.to_string();
     }
 
+    if let Some(tolerance) = cli_args.value_of("http-sync-tolerance") {
+        client_config.rest_api.sync_tolerance = Some(
+            tolerance
+                .parse::<u64>()
+                .map_err(|_| "http-sync-tolerance is not a valid u64.")?,
+        );
+    }
+
+    if cli_args.is_present("http-finalized-only") {
+        client_config.rest_api.finalized_only = true;
+    }
+
+    if cli_args.is_present("http-allow-insecure-genesis-sync") {
+        client_config.rest_api.allow_insecure_genesis_sync = true;
+    }
+
+    if cli_args.is_present("http-enable-op-pool-pruning") {
+        client_config.rest_api.enable_op_pool_pruning = true;
+    }
+
+    if let Some(categories) = cli_args.value_of("http-disable-publishing") {
+        for category in categories.split(',') {
+            match category.trim() {
+                "blocks" => client_config.rest_api.enable_block_publishing = false,
+                "attestations" => client_config.rest_api.enable_attestation_publishing = false,
+                "slashings" => client_config.rest_api.enable_slashing_publishing = false,
+                other => {
+                    return Err(format!(
+                        "Invalid http-disable-publishing category: {}",
+                        other
+                    ))
+                }
+            }
+        }
+    }
+
     /*
      * Websocket server
      */
@@ -292,6 +328,22 @@ pub fn set_network_config(
             .map_err(|_| format!("Invalid number of target peers: {}", target_peers_str))?;
     }
 
+    if let Some(lead_slots_str) = cli_args.value_of("subnet-subscription-lead-slots") {
+        config.subnet_subscription_lead_slots = lead_slots_str
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid subnet subscription lead slots: {}", lead_slots_str))?;
+    }
+
+    if let Some(linger_slots_str) = cli_args.value_of("subnet-subscription-linger-slots") {
+        config.subnet_subscription_linger_slots =
+            linger_slots_str.parse::<u64>().map_err(|_| {
+                format!(
+                    "Invalid subnet subscription linger slots: {}",
+                    linger_slots_str
+                )
+            })?;
+    }
+
     if let Some(port_str) = cli_args.value_of("port") {
         let port = port_str
             .parse::<u16>()
@@ -785,6 +785,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .map(|s: ColdStateSummary| s.slot))
     }
 
+    /// Load the slot of a hot or frozen state, given its root.
+    pub fn load_state_slot(&self, state_root: &Hash256) -> Result<Option<Slot>, Error> {
+        if let Some(summary) = self.load_hot_state_summary(state_root)? {
+            Ok(Some(summary.slot))
+        } else {
+            self.load_cold_state_slot(state_root)
+        }
+    }
+
     /// Load a hot state's summary, given its root.
     pub fn load_hot_state_summary(
         &self,
@@ -4,11 +4,14 @@
 //! Presently, this is only used for testing but it _could_ become a user-facing library.
 
 use eth2_config::Eth2Config;
+use futures::stream::{self, Stream, StreamExt};
 use reqwest::{Client, ClientBuilder, Response, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
+use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::time::Duration;
+use tokio::time::delay_for;
 use types::{
     Attestation, AttestationData, AttesterSlashing, BeaconBlock, BeaconState, CommitteeIndex,
     Epoch, EthSpec, Fork, Graffiti, Hash256, ProposerSlashing, PublicKey, PublicKeyBytes,
@@ -19,9 +22,15 @@ use url::Url;
 pub use operation_pool::PersistedOperationPool;
 pub use proto_array::core::ProtoArray;
 pub use rest_types::{
-    CanonicalHeadResponse, Committee, HeadBeaconBlock, Health, IndividualVotesRequest,
-    IndividualVotesResponse, SyncingResponse, ValidatorDutiesRequest, ValidatorDutyBytes,
-    ValidatorRequest, ValidatorResponse, ValidatorSubscription,
+    AttestationPoolKind, AttestationRewards, AttestationRewardsRequest, BlockAttestations,
+    BlockImportResult, BlockRewards, BlockRootResponse, CanonicalHeadResponse, Committee,
+    CurrentSlotResponse, EpochEffectiveness, EpochValidatorDuty, ExportedAttestation,
+    FinalityCheckpoints, GenesisData, HeadBeaconBlock, Health, IndividualVotesRequest,
+    IndividualVotesResponse, NodeIdentity, NodeLiveness, PeerCount, PeerData, PeerDirection,
+    PeerState, PoolSummary, RecentBlock, SyncingResponse, ValidatorBalance, ValidatorDutiesRequest,
+    ValidatorDutyBytes, ValidatorEffectiveness, ValidatorId, ValidatorLiveness,
+    ValidatorLivenessRequest, ValidatorRequest, ValidatorResponse, ValidatorStatesRequest,
+    ValidatorStatus, ValidatorSubscription, ValidatorsPage,
 };
 
 // Setting a long timeout for debug ensures that crypto-heavy operations can still succeed.
@@ -31,6 +40,97 @@ pub const REQUEST_TIMEOUT_SECONDS: u64 = 15;
 #[cfg(not(debug_assertions))]
 pub const REQUEST_TIMEOUT_SECONDS: u64 = 5;
 
+/// The longest time waited before retrying a failed request, by default.
+pub const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The maximum number of concurrent requests made by `Beacon::get_blocks_by_range`.
+pub const BLOCKS_BY_RANGE_CONCURRENCY: usize = 4;
+
+/// Configures the reuse of connections to the beacon node between requests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConnectionPoolConfig {
+    /// The time for which an idle connection is kept open for reuse. If `None`, idle connections
+    /// are never closed by the client.
+    pub idle_timeout: Option<Duration>,
+    /// The maximum number of idle connections to keep open. Setting this to `0` results in a new
+    /// connection for every request.
+    pub max_idle: usize,
+}
+
+impl Default for ConnectionPoolConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout: Some(Duration::from_secs(90)),
+            max_idle: 8,
+        }
+    }
+}
+
+/// Determines which failed `GET` requests are sent again.
+///
+/// `POST` requests are never retried, since they may have side-effects (e.g., publishing a block)
+/// even if the request failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a failed request is retried.
+    pub max_retries: usize,
+    /// The `n`th retry is sent after waiting `base_backoff * 2^n`, up to `max_backoff`.
+    pub base_backoff: Duration,
+    /// The longest time waited before any retry.
+    pub max_backoff: Duration,
+    /// Responses with these status codes are retried. Timeouts and connection failures are always
+    /// retried.
+    pub retry_statuses: Vec<StatusCode>,
+}
+
+impl RetryPolicy {
+    /// Retries up to `max_retries` times on the transient `502`, `503` and `504` status codes,
+    /// waiting at most `MAX_RETRY_BACKOFF` before each retry.
+    pub fn new(max_retries: usize, base_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            base_backoff,
+            max_backoff: MAX_RETRY_BACKOFF,
+            retry_statuses: vec![
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+
+    /// Returns `true` if a request which failed with `error` on its `attempt`th retry (counting
+    /// from zero) should be sent again.
+    pub fn should_retry(&self, error: &Error, attempt: usize) -> bool {
+        if attempt >= self.max_retries || !error.is_retryable() {
+            return false;
+        }
+
+        match error {
+            Error::DidNotSucceed { status, .. } => self.retry_statuses.contains(status),
+            _ => true,
+        }
+    }
+
+    /// Returns the time to wait before sending the `attempt`th retry (counting from zero).
+    ///
+    /// Saturates at `max_backoff` rather than overflowing for large `attempt` values.
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        u32::try_from(attempt)
+            .ok()
+            .and_then(|attempt| 2_u32.checked_pow(attempt))
+            .and_then(|factor| self.base_backoff.checked_mul(factor))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Never retries.
+    fn default() -> Self {
+        Self::new(0, Duration::from_secs(0))
+    }
+}
+
 #[derive(Clone)]
 /// Connects to a remote Lighthouse (or compatible) node via HTTP.
 pub struct RemoteBeaconNode<E: EthSpec> {
@@ -44,11 +144,35 @@ impl<E: EthSpec> RemoteBeaconNode<E> {
     }
 
     pub fn new_with_timeout(http_endpoint: String, timeout: Duration) -> Result<Self, String> {
+        Self::new_with_pool(http_endpoint, timeout, ConnectionPoolConfig::default())
+    }
+
+    /// As per `new_with_timeout`, but reuses connections as configured by `pool`.
+    pub fn new_with_pool(
+        http_endpoint: String,
+        timeout: Duration,
+        pool: ConnectionPoolConfig,
+    ) -> Result<Self, String> {
         Ok(Self {
-            http: HttpClient::new(http_endpoint, timeout)
+            http: HttpClient::new_with_pool(http_endpoint, timeout, pool)
                 .map_err(|e| format!("Unable to create http client: {:?}", e))?,
         })
     }
+
+    /// As per `new_with_timeout`, but retries failed `GET` requests up to `max_retries` times.
+    ///
+    /// See `HttpClient::with_get_retries`. For finer control, see `HttpClient::with_retry_policy`.
+    pub fn new_with_retries(
+        http_endpoint: String,
+        timeout: Duration,
+        max_retries: usize,
+        base_backoff: Duration,
+    ) -> Result<Self, String> {
+        let node = Self::new_with_timeout(http_endpoint, timeout)?;
+        Ok(Self {
+            http: node.http.with_get_retries(max_retries, base_backoff),
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -59,34 +183,139 @@ pub enum Error {
     ReqwestError(reqwest::Error),
     /// There was an error when encoding/decoding an object using serde.
     SerdeJsonError(serde_json::Error),
+    /// There was an error when decoding an SSZ response body.
+    SszDecodeError(ssz::DecodeError),
     /// The server responded to the request, however it did not return a 200-type success code.
     DidNotSucceed { status: StatusCode, body: String },
     /// The request input was invalid.
     InvalidInput,
+    /// The body of the response exceeded the maximum response size of the client.
+    ResponseTooLarge { limit: usize },
+}
+
+impl Error {
+    /// Returns `true` if the request did not complete within the timeout of the client.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Error::ReqwestError(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the request may succeed if it is sent again.
+    ///
+    /// This is the case for timeouts, connection failures and server-side errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::ReqwestError(e) => e.is_timeout() || e.is_connect(),
+            Error::DidNotSucceed { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct HttpClient<E> {
     client: Client,
+    /// Used for long-lived event streams, so it has a connect timeout but no total timeout.
+    stream_client: Client,
     url: Url,
     timeout: Duration,
+    pool: ConnectionPoolConfig,
+    retry_policy: RetryPolicy,
+    max_response_size: Option<usize>,
     _phantom: PhantomData<E>,
 }
 
 impl<E: EthSpec> HttpClient<E> {
     /// Creates a new instance (without connecting to the node).
+    ///
+    /// Every request made by the instance fails with a timeout error if it has not completed
+    /// within `timeout`.
     pub fn new(server_url: String, timeout: Duration) -> Result<Self, Error> {
+        Self::new_with_pool(server_url, timeout, ConnectionPoolConfig::default())
+    }
+
+    /// As per `new`, but reuses connections to the node as configured by `pool`.
+    pub fn new_with_pool(
+        server_url: String,
+        timeout: Duration,
+        pool: ConnectionPoolConfig,
+    ) -> Result<Self, Error> {
         Ok(Self {
             client: ClientBuilder::new()
                 .timeout(timeout)
+                .pool_idle_timeout(pool.idle_timeout)
+                .pool_max_idle_per_host(pool.max_idle)
+                .build()
+                .expect("should build from static configuration"),
+            stream_client: ClientBuilder::new()
+                .connect_timeout(timeout)
+                .pool_idle_timeout(pool.idle_timeout)
+                .pool_max_idle_per_host(pool.max_idle)
                 .build()
                 .expect("should build from static configuration"),
             url: Url::parse(&server_url)?,
-            timeout: Duration::from_secs(15),
+            timeout,
+            pool,
+            retry_policy: RetryPolicy::default(),
+            max_response_size: None,
             _phantom: PhantomData,
         })
     }
 
+    /// Retries `GET` requests which time out, fail to connect or receive a transient `5xx` status
+    /// up to `max_retries` times, waiting `base_backoff * 2^n` (up to `MAX_RETRY_BACKOFF`) before
+    /// the `n`th retry.
+    ///
+    /// See `RetryPolicy::new`.
+    pub fn with_get_retries(self, max_retries: usize, base_backoff: Duration) -> Self {
+        self.with_retry_policy(RetryPolicy::new(max_retries, base_backoff))
+    }
+
+    /// Retries failed `GET` requests as determined by `policy`.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Fails any request with a response body longer than `limit` bytes, without reading more than
+    /// `limit` bytes of the body.
+    ///
+    /// Protects against a faulty node exhausting memory with an unbounded response. Does not apply
+    /// to event streams (see `Beacon::get_events`).
+    pub fn with_max_response_size(mut self, limit: usize) -> Self {
+        self.max_response_size = Some(limit);
+        self
+    }
+
+    /// Returns the timeout which applies to each request.
+    ///
+    /// When `GET` requests are retried, the timeout applies to each attempt individually.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the configuration of the connection pool.
+    pub fn pool_config(&self) -> ConnectionPoolConfig {
+        self.pool
+    }
+
+    /// Returns the maximum size of a response body, if any.
+    pub fn max_response_size(&self) -> Option<usize> {
+        self.max_response_size
+    }
+
+    /// Returns the maximum number of times a failed `GET` request is retried.
+    pub fn get_retries(&self) -> usize {
+        self.retry_policy.max_retries
+    }
+
+    /// Returns the policy which determines which failed `GET` requests are retried.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
     pub fn beacon(&self) -> Beacon<E> {
         Beacon(self.clone())
     }
@@ -103,6 +332,10 @@ impl<E: EthSpec> HttpClient<E> {
         Node(self.clone())
     }
 
+    pub fn network(&self) -> Network<E> {
+        Network(self.clone())
+    }
+
     pub fn advanced(&self) -> Advanced<E> {
         Advanced(self.clone())
     }
@@ -111,6 +344,10 @@ impl<E: EthSpec> HttpClient<E> {
         Consensus(self.clone())
     }
 
+    pub fn lighthouse(&self) -> Lighthouse<E> {
+        Lighthouse(self.clone())
+    }
+
     fn url(&self, path: &str) -> Result<Url, Error> {
         self.url.join(path).map_err(|e| e.into())
     }
@@ -124,41 +361,133 @@ impl<E: EthSpec> HttpClient<E> {
             .map_err(Error::from)
     }
 
+    pub async fn ssz_post<T: Encode>(&self, url: Url, body: &T) -> Result<Response, Error> {
+        self.client
+            .post(&url.to_string())
+            .header("Content-Type", "application/octet-stream")
+            .body(body.as_ssz_bytes())
+            .send()
+            .await
+            .map_err(Error::from)
+    }
+
     pub async fn json_get<T: DeserializeOwned>(
         &self,
-        mut url: Url,
+        url: Url,
         query_pairs: Vec<(String, String)>,
     ) -> Result<T, Error> {
-        query_pairs.into_iter().for_each(|(key, param)| {
-            url.query_pairs_mut().append_pair(&key, &param);
-        });
+        let success = self.get(url, query_pairs, "application/json").await?;
+        self.json_response(success).await
+    }
 
-        let response = self
-            .client
-            .get(&url.to_string())
-            .send()
-            .await
-            .map_err(Error::from)?;
+    /// Decodes the JSON body of the `response`.
+    ///
+    /// Fails if the body exceeds the maximum response size of the client.
+    pub async fn json_response<T: DeserializeOwned>(&self, response: Response) -> Result<T, Error> {
+        let bytes = self.response_bytes(response).await?;
+        serde_json::from_slice(&bytes).map_err(Error::from)
+    }
+
+    /// Reads the body of the `response`, failing as soon as it exceeds the maximum response size
+    /// of the client.
+    async fn response_bytes(&self, mut response: Response) -> Result<Vec<u8>, Error> {
+        let limit = if let Some(limit) = self.max_response_size {
+            limit
+        } else {
+            return response
+                .bytes()
+                .await
+                .map(|bytes| bytes.to_vec())
+                .map_err(Error::from);
+        };
+
+        if response
+            .content_length()
+            .map_or(false, |length| length > limit as u64)
+        {
+            return Err(Error::ResponseTooLarge { limit });
+        }
 
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json::<T>().await.map_err(Error::from)
+        let mut body = vec![];
+        while let Some(chunk) = response.chunk().await.map_err(Error::from)? {
+            if body.len() + chunk.len() > limit {
+                return Err(Error::ResponseTooLarge { limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(body)
     }
-}
 
-/// Returns an `Error` (with a description) if the `response` was not a 200-type success response.
-///
-/// Distinct from `Response::error_for_status` because it includes the body of the response as
-/// text. This ensures the error message from the server is not discarded.
-async fn error_for_status(response: Response) -> Result<Response, Error> {
-    let status = response.status();
-
-    if status.is_success() {
-        Ok(response)
-    } else {
-        let text_result = response.text().await;
-        match text_result {
-            Err(e) => Err(Error::ReqwestError(e)),
-            Ok(body) => Err(Error::DidNotSucceed { status, body }),
+    /// Reads the body of the `response` as text, failing as soon as it exceeds the maximum response
+    /// size of the client.
+    async fn response_text(&self, response: Response) -> Result<String, Error> {
+        let bytes = self.response_bytes(response).await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Returns an `Error` (with a description) if the `response` was not a 200-type success
+    /// response.
+    ///
+    /// Distinct from `Response::error_for_status` because it includes the body of the response as
+    /// text. This ensures the error message from the server is not discarded.
+    async fn error_for_status(&self, response: Response) -> Result<Response, Error> {
+        let status = response.status();
+
+        if status.is_success() {
+            Ok(response)
+        } else {
+            let body = self.response_text(response).await?;
+            Err(Error::DidNotSucceed { status, body })
+        }
+    }
+
+    /// Performs a `GET` request which asks for an SSZ-encoded response body and decodes it.
+    pub async fn ssz_get<T: Decode>(
+        &self,
+        url: Url,
+        query_pairs: Vec<(String, String)>,
+    ) -> Result<T, Error> {
+        let success = self
+            .get(url, query_pairs, "application/octet-stream")
+            .await?;
+        let bytes = self.response_bytes(success).await?;
+        T::from_ssz_bytes(&bytes).map_err(Error::from)
+    }
+
+    /// Performs a `GET` request, returning the response if it was successful.
+    ///
+    /// The request is retried as configured by `Self::with_retry_policy`.
+    async fn get(
+        &self,
+        mut url: Url,
+        query_pairs: Vec<(String, String)>,
+        accept: &str,
+    ) -> Result<Response, Error> {
+        query_pairs.into_iter().for_each(|(key, param)| {
+            url.query_pairs_mut().append_pair(&key, &param);
+        });
+
+        let mut attempt = 0;
+        loop {
+            let result = match self
+                .client
+                .get(&url.to_string())
+                .header("Accept", accept)
+                .send()
+                .await
+            {
+                Ok(response) => self.error_for_status(response).await,
+                Err(e) => Err(Error::from(e)),
+            };
+
+            match result {
+                Err(e) if self.retry_policy.should_retry(&e, attempt) => {
+                    delay_for(self.retry_policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
         }
     }
 }
@@ -181,6 +510,38 @@ impl PublishStatus {
     }
 }
 
+/// An event received from the `/beacon/events` stream.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ServerSentEvent {
+    /// The topic the event was published on, e.g., `head`.
+    pub topic: String,
+    /// The JSON encoded event.
+    pub data: String,
+}
+
+impl ServerSentEvent {
+    /// Parses a single SSE message (without its trailing blank line), returning `None` if it does
+    /// not contain any data.
+    fn from_message(message: &str) -> Option<Self> {
+        let mut topic = String::new();
+        let mut data: Option<String> = None;
+
+        for line in message.lines() {
+            if let Some(value) = line.strip_prefix("event:") {
+                topic = value.trim_start().to_string();
+            } else if let Some(value) = line.strip_prefix("data:") {
+                let value = value.trim_start();
+                data = Some(match data {
+                    Some(existing) => format!("{}\n{}", existing, value),
+                    None => value.to_string(),
+                });
+            }
+        }
+
+        data.map(|data| ServerSentEvent { topic, data })
+    }
+}
+
 /// Provides the functions on the `/validator` endpoint of the node.
 #[derive(Clone)]
 pub struct Validator<E>(HttpClient<E>);
@@ -236,7 +597,7 @@ impl<E: EthSpec> Validator<E> {
         match response.status() {
             StatusCode::OK => Ok(PublishStatus::Valid),
             StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
-                response.text().await.map_err(Error::from)?,
+                client.response_text(response).await?,
             )),
             _ => response
                 .error_for_status()
@@ -259,7 +620,7 @@ impl<E: EthSpec> Validator<E> {
         match response.status() {
             StatusCode::OK => Ok(PublishStatus::Valid),
             StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
-                response.text().await.map_err(Error::from)?,
+                client.response_text(response).await?,
             )),
             _ => response
                 .error_for_status()
@@ -286,8 +647,51 @@ impl<E: EthSpec> Validator<E> {
 
         let url = self.url("duties")?;
         let response = client.json_post::<_>(url, bulk_request).await?;
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json().await.map_err(Error::from)
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
+    }
+
+    /// Returns whether or not each of the validators with the given `indices` was observed by the
+    /// beacon node to attest or propose during `epoch`.
+    pub async fn get_liveness(
+        &self,
+        epoch: Epoch,
+        indices: &[u64],
+    ) -> Result<Vec<ValidatorLiveness>, Error> {
+        let client = self.0.clone();
+
+        let request = ValidatorLivenessRequest {
+            epoch,
+            indices: indices.to_vec(),
+        };
+
+        let url = self.url("liveness")?;
+        let response = client.json_post::<_>(url, request).await?;
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
+    }
+
+    /// Requests that the beacon node computes and caches the duties of the given
+    /// `validator_pubkeys` during `epoch`, so that subsequent duties requests are fast.
+    pub async fn warm_duties(
+        &self,
+        epoch: Epoch,
+        validator_pubkeys: &[PublicKey],
+    ) -> Result<(), Error> {
+        let client = self.0.clone();
+
+        let bulk_request = ValidatorDutiesRequest {
+            epoch,
+            pubkeys: validator_pubkeys
+                .iter()
+                .map(|pubkey| pubkey.clone().into())
+                .collect(),
+        };
+
+        let url = self.url("duties/warm")?;
+        let response = client.json_post::<_>(url, bulk_request).await?;
+        client.error_for_status(response).await?;
+        Ok(())
     }
 
     /// Posts a block to the beacon node, expecting it to verify it and publish it to the network.
@@ -299,7 +703,7 @@ impl<E: EthSpec> Validator<E> {
         match response.status() {
             StatusCode::OK => Ok(PublishStatus::Valid),
             StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
-                response.text().await.map_err(Error::from)?,
+                client.response_text(response).await?,
             )),
             _ => response
                 .error_for_status()
@@ -330,6 +734,29 @@ impl<E: EthSpec> Validator<E> {
         client.json_get::<BeaconBlock<E>>(url, query_pairs).await
     }
 
+    /// Requests a new (unsigned) block from the beacon node, claiming to be from `proposer_index`
+    /// rather than the scheduled proposer.
+    ///
+    /// The override is only honoured by beacon nodes built with the `proposer_override` feature,
+    /// it is intended for testing that such blocks are rejected.
+    pub async fn produce_block_with_proposer_index(
+        &self,
+        slot: Slot,
+        randao_reveal: Signature,
+        proposer_index: u64,
+    ) -> Result<BeaconBlock<E>, Error> {
+        let client = self.0.clone();
+        let url = self.url("block")?;
+
+        let query_pairs = vec![
+            ("slot".into(), format!("{}", slot.as_u64())),
+            ("randao_reveal".into(), as_ssz_hex_string(&randao_reveal)),
+            ("proposer_index".into(), format!("{}", proposer_index)),
+        ];
+
+        client.json_get::<BeaconBlock<E>>(url, query_pairs).await
+    }
+
     /// Subscribes a list of validators to particular slots for attestation production/publication.
     pub async fn subscribe(
         &self,
@@ -342,7 +769,7 @@ impl<E: EthSpec> Validator<E> {
         match response.status() {
             StatusCode::OK => Ok(PublishStatus::Valid),
             StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
-                response.text().await.map_err(Error::from)?,
+                client.response_text(response).await?,
             )),
             _ => response
                 .error_for_status()
@@ -364,6 +791,13 @@ impl<E: EthSpec> Beacon<E> {
             .map_err(Into::into)
     }
 
+    /// Returns the genesis time, genesis validators root and genesis fork version.
+    pub async fn get_genesis(&self) -> Result<GenesisData, Error> {
+        let client = self.0.clone();
+        let url = self.url("genesis")?;
+        client.json_get(url, vec![]).await
+    }
+
     /// Returns the genesis time.
     pub async fn get_genesis_time(&self) -> Result<u64, Error> {
         let client = self.0.clone();
@@ -385,6 +819,46 @@ impl<E: EthSpec> Beacon<E> {
         client.json_get(url, vec![]).await
     }
 
+    /// Returns the fork of the state with the given `state_root`.
+    pub async fn get_fork_at_state_root(&self, state_root: Hash256) -> Result<Fork, Error> {
+        let client = self.0.clone();
+        let query_params = vec![("state_root".into(), root_as_string(state_root))];
+        let url = self.url("fork")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Returns the finality checkpoints of the state with the given `state_root`.
+    pub async fn get_finality_checkpoints(
+        &self,
+        state_root: Hash256,
+    ) -> Result<FinalityCheckpoints, Error> {
+        let client = self.0.clone();
+        let query_params = vec![("state_root".into(), root_as_string(state_root))];
+        let url = self.url("state/finality_checkpoints")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Returns the randao mix of the given `epoch` from the state with the given `state_root`.
+    ///
+    /// The canonical head state is used if `state_root` is `None`, and the current epoch of the
+    /// state is used if `epoch` is `None`.
+    pub async fn get_randao_mix(
+        &self,
+        state_root: Option<Hash256>,
+        epoch: Option<Epoch>,
+    ) -> Result<Hash256, Error> {
+        let client = self.0.clone();
+        let mut query_params = vec![];
+        if let Some(state_root) = state_root {
+            query_params.push(("state_root".into(), root_as_string(state_root)));
+        }
+        if let Some(epoch) = epoch {
+            query_params.push(("epoch".into(), format!("{}", epoch.as_u64())));
+        }
+        let url = self.url("state/randao")?;
+        client.json_get(url, query_params).await
+    }
+
     /// Returns info about the head of the canonical beacon chain.
     pub async fn get_head(&self) -> Result<CanonicalHeadResponse, Error> {
         let client = self.0.clone();
@@ -417,17 +891,131 @@ impl<E: EthSpec> Beacon<E> {
             .await
     }
 
+    /// Returns the block and block root at the given slot, transferred as SSZ.
+    pub async fn get_block_by_slot_ssz(
+        &self,
+        slot: Slot,
+    ) -> Result<(SignedBeaconBlock<E>, Hash256), Error> {
+        self.get_block_ssz("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the block and block root at the given root, transferred as SSZ.
+    pub async fn get_block_by_root_ssz(
+        &self,
+        root: Hash256,
+    ) -> Result<(SignedBeaconBlock<E>, Hash256), Error> {
+        self.get_block_ssz("root".to_string(), root_as_string(root))
+            .await
+    }
+
+    /// Returns the block at the given slot, along with its root and whether it is finalized.
+    pub async fn get_block_response_by_slot(&self, slot: Slot) -> Result<BlockResponse<E>, Error> {
+        self.get_block_response("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the block at the given root, along with its root and whether it is finalized.
+    pub async fn get_block_response_by_root(
+        &self,
+        root: Hash256,
+    ) -> Result<BlockResponse<E>, Error> {
+        self.get_block_response("root".to_string(), root_as_string(root))
+            .await
+    }
+
+    /// Returns a stream of the canonical blocks at the `count` slots `start_slot`,
+    /// `start_slot + step`, `start_slot + 2 * step`, etc., in ascending slot order. `step`
+    /// defaults to `1` (a `step` of `0` is treated as `1`).
+    ///
+    /// Skipped slots (and slots beyond the head of the node) are omitted from the stream rather
+    /// than returning an error. At most `BLOCKS_BY_RANGE_CONCURRENCY` requests are in flight at
+    /// any one time.
+    pub fn get_blocks_by_range(
+        &self,
+        start_slot: Slot,
+        count: u64,
+        step: Option<u64>,
+    ) -> impl Stream<Item = Result<SignedBeaconBlock<E>, Error>> {
+        let beacon = self.clone();
+        let step = step.unwrap_or(1).max(1);
+
+        stream::iter((0..count).map(move |i| start_slot + i * step))
+            .map(move |slot| {
+                let beacon = beacon.clone();
+                async move { (slot, beacon.get_block_by_slot(slot).await) }
+            })
+            .buffered(BLOCKS_BY_RANGE_CONCURRENCY)
+            .filter_map(|(slot, result)| async move {
+                match result {
+                    // The node returns the prior block for a skipped slot.
+                    Ok((block, _root)) if block.slot() != slot => None,
+                    Ok((block, _root)) => Some(Ok(block)),
+                    Err(Error::DidNotSucceed { status, .. }) if status == StatusCode::NOT_FOUND => {
+                        None
+                    }
+                    Err(e) => Some(Err(e)),
+                }
+            })
+    }
+
     /// Returns the block and block root at the given slot.
     async fn get_block(
         &self,
         query_key: String,
         query_param: String,
     ) -> Result<(SignedBeaconBlock<E>, Hash256), Error> {
+        self.get_block_response(query_key, query_param)
+            .await
+            .map(|response| (response.beacon_block, response.root))
+    }
+
+    /// Returns the block identified by the given query parameter.
+    async fn get_block_response(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<BlockResponse<E>, Error> {
         let client = self.0.clone();
         let url = self.url("block")?;
         client
             .json_get::<BlockResponse<E>>(url, vec![(query_key, query_param)])
             .await
+    }
+
+    /// Returns the block and block root at the given slot, transferred as SSZ.
+    async fn get_block_ssz(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<(SignedBeaconBlock<E>, Hash256), Error> {
+        let client = self.0.clone();
+        let url = self.url("block")?;
+        client
+            .ssz_get::<rest_types::BlockResponse<E>>(url, vec![(query_key, query_param)])
+            .await
+            .map(|response| (response.beacon_block, response.root))
+    }
+
+    /// Returns the block and block root `distance` parents before the block with the given root.
+    ///
+    /// The block need not be on the canonical chain.
+    pub async fn get_block_ancestor(
+        &self,
+        root: Hash256,
+        distance: u64,
+    ) -> Result<(SignedBeaconBlock<E>, Hash256), Error> {
+        let client = self.0.clone();
+        let url = self.url("block/ancestor")?;
+        client
+            .json_get::<BlockResponse<E>>(
+                url,
+                vec![
+                    ("root".into(), root_as_string(root)),
+                    ("distance".into(), format!("{}", distance)),
+                ],
+            )
+            .await
             .map(|response| (response.beacon_block, response.root))
     }
 
@@ -446,6 +1034,24 @@ impl<E: EthSpec> Beacon<E> {
             .await
     }
 
+    /// Returns the state and state root at the given slot, transferred as SSZ.
+    pub async fn get_state_by_slot_ssz(
+        &self,
+        slot: Slot,
+    ) -> Result<(BeaconState<E>, Hash256), Error> {
+        self.get_state_ssz("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the state and state root at the given root, transferred as SSZ.
+    pub async fn get_state_by_root_ssz(
+        &self,
+        root: Hash256,
+    ) -> Result<(BeaconState<E>, Hash256), Error> {
+        self.get_state_ssz("root".to_string(), root_as_string(root))
+            .await
+    }
+
     /// Returns the root of the state at the given slot.
     pub async fn get_state_root(&self, slot: Slot) -> Result<Hash256, Error> {
         let client = self.0.clone();
@@ -455,26 +1061,175 @@ impl<E: EthSpec> Beacon<E> {
             .await
     }
 
-    /// Returns the root of the block at the given slot.
-    pub async fn get_block_root(&self, slot: Slot) -> Result<Hash256, Error> {
-        let client = self.0.clone();
-        let url = self.url("block_root")?;
-        client
+    /// Subscribes to the events of the given `topics`, returning a stream of the events as they
+    /// occur.
+    ///
+    /// Only connecting to the node is subject to the timeout of the client, so the stream stays
+    /// open for as long as the node keeps it open.
+    pub async fn get_events(
+        &self,
+        topics: &[&str],
+    ) -> Result<impl Stream<Item = Result<ServerSentEvent, Error>>, Error> {
+        let mut url = self.url("events")?;
+        url.query_pairs_mut()
+            .append_pair("topics", &topics.join(","));
+
+        let response = self
+            .0
+            .stream_client
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+        let response = self.0.error_for_status(response).await?;
+
+        Ok(stream::unfold(
+            Some((response, String::new())),
+            |state| async move {
+                let (mut response, mut buffer) = state?;
+                loop {
+                    if let Some(end) = buffer.find("\n\n") {
+                        let message: String = buffer.drain(..end + 2).collect();
+                        if let Some(event) = ServerSentEvent::from_message(&message) {
+                            return Some((Ok(event), Some((response, buffer))));
+                        }
+                        continue;
+                    }
+
+                    match response.chunk().await {
+                        Ok(Some(bytes)) => buffer.push_str(&String::from_utf8_lossy(&bytes)),
+                        Ok(None) => return None,
+                        Err(e) => return Some((Err(Error::from(e)), None)),
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Requests the beacon node to fully verify the given block, without importing or publishing
+    /// it.
+    ///
+    /// A `PublishStatus::Valid` response indicates the block is valid, not that it was published.
+    pub async fn validate_block(
+        &self,
+        block: SignedBeaconBlock<E>,
+    ) -> Result<PublishStatus, Error> {
+        let client = self.0.clone();
+        let url = self.url("block/validate")?;
+        let response = client.json_post::<_>(url, block).await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(PublishStatus::Valid),
+            StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
+                client.response_text(response).await?,
+            )),
+            _ => response
+                .error_for_status()
+                .map_err(Error::from)
+                .map(|_| PublishStatus::Unknown),
+        }
+    }
+
+    /// Returns the attestations included in each canonical block with a slot in
+    /// `start_slot..start_slot + count`.
+    pub async fn get_block_attestations(
+        &self,
+        start_slot: Slot,
+        count: u64,
+    ) -> Result<Vec<BlockAttestations<E>>, Error> {
+        let client = self.0.clone();
+        let url = self.url("blocks/attestations")?;
+        client
+            .json_get(
+                url,
+                vec![
+                    ("start_slot".into(), format!("{}", start_slot.as_u64())),
+                    ("count".into(), format!("{}", count)),
+                ],
+            )
+            .await
+    }
+
+    /// Returns the root of the block at the given slot.
+    pub async fn get_block_root(&self, slot: Slot) -> Result<BlockRootResponse, Error> {
+        let client = self.0.clone();
+        let url = self.url("block_root")?;
+        client
             .json_get(url, vec![("slot".into(), format!("{}", slot.as_u64()))])
             .await
     }
 
+    /// Returns the block root at the given slot on the chain ending at the block with root `head`.
+    ///
+    /// Unlike `get_block_root`, the result is unaffected by re-orgs of the canonical chain.
+    pub async fn get_block_root_from_head(
+        &self,
+        slot: Slot,
+        head: Hash256,
+    ) -> Result<BlockRootResponse, Error> {
+        let client = self.0.clone();
+        let url = self.url("block_root")?;
+        client
+            .json_get(
+                url,
+                vec![
+                    ("slot".into(), format!("{}", slot.as_u64())),
+                    ("head".into(), root_as_string(head)),
+                ],
+            )
+            .await
+    }
+
+    /// Returns the state at the given slot, along with its root and whether it is finalized.
+    pub async fn get_state_response_by_slot(&self, slot: Slot) -> Result<StateResponse<E>, Error> {
+        self.get_state_response("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the state at the given root, along with its root and whether it is finalized.
+    pub async fn get_state_response_by_root(
+        &self,
+        root: Hash256,
+    ) -> Result<StateResponse<E>, Error> {
+        self.get_state_response("root".to_string(), root_as_string(root))
+            .await
+    }
+
     /// Returns the state and state root at the given slot.
     async fn get_state(
         &self,
         query_key: String,
         query_param: String,
     ) -> Result<(BeaconState<E>, Hash256), Error> {
+        self.get_state_response(query_key, query_param)
+            .await
+            .map(|response| (response.beacon_state, response.root))
+    }
+
+    /// Returns the state identified by the given query parameter.
+    async fn get_state_response(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<StateResponse<E>, Error> {
         let client = self.0.clone();
         let url = self.url("state")?;
         client
             .json_get::<StateResponse<E>>(url, vec![(query_key, query_param)])
             .await
+    }
+
+    /// Returns the state and state root at the given slot, transferred as SSZ.
+    async fn get_state_ssz(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<(BeaconState<E>, Hash256), Error> {
+        let client = self.0.clone();
+        let url = self.url("state")?;
+        client
+            .ssz_get::<rest_types::StateResponse<E>>(url, vec![(query_key, query_param)])
+            .await
             .map(|response| (response.beacon_state, response.root))
     }
 
@@ -499,8 +1254,73 @@ impl<E: EthSpec> Beacon<E> {
 
         let url = self.url("validators")?;
         let response = client.json_post::<_>(url, bulk_request).await?;
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json().await.map_err(Error::from)
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
+    }
+
+    /// Returns the rewards of the validators with the given `validator_indices` for their
+    /// attestations during `epoch`, or of all validators if `validator_indices` is empty.
+    pub async fn get_attestation_rewards(
+        &self,
+        epoch: Epoch,
+        validator_indices: &[u64],
+    ) -> Result<Vec<AttestationRewards>, Error> {
+        let client = self.0.clone();
+
+        let req_body = AttestationRewardsRequest {
+            epoch,
+            validator_indices: validator_indices.to_vec(),
+        };
+
+        let url = self.url("rewards/attestations")?;
+        let response = client.json_post::<_>(url, req_body).await?;
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
+    }
+
+    /// Returns the rewards of the proposer of the block at the given slot.
+    pub async fn get_block_rewards_by_slot(&self, slot: Slot) -> Result<BlockRewards, Error> {
+        self.get_block_rewards("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the rewards of the proposer of the block at the given root.
+    pub async fn get_block_rewards_by_root(&self, root: Hash256) -> Result<BlockRewards, Error> {
+        self.get_block_rewards("root".to_string(), root_as_string(root))
+            .await
+    }
+
+    /// Returns the rewards of the proposer of the block identified by the query.
+    async fn get_block_rewards(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<BlockRewards, Error> {
+        let client = self.0.clone();
+        let url = self.url("rewards/block")?;
+        client
+            .json_get::<BlockRewards>(url, vec![(query_key, query_param)])
+            .await
+    }
+
+    /// Returns the validator with the given `validator_pubkey` in each of the given `state_roots`,
+    /// in the same order.
+    pub async fn get_validator_at_states(
+        &self,
+        validator_pubkey: PublicKey,
+        state_roots: Vec<Hash256>,
+    ) -> Result<Vec<ValidatorResponse>, Error> {
+        let client = self.0.clone();
+
+        let bulk_request = ValidatorStatesRequest {
+            state_roots,
+            pubkey: validator_pubkey.into(),
+        };
+
+        let url = self.url("validators/states")?;
+        let response = client.json_post::<_>(url, bulk_request).await?;
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
     }
 
     /// Returns all validators.
@@ -543,6 +1363,118 @@ impl<E: EthSpec> Beacon<E> {
         client.json_get(url, query_params).await
     }
 
+    /// Returns a page of at most `limit` validators, starting at the validator with index `offset`.
+    ///
+    /// If `validator_ids` or `statuses` are `Some`, only the validators matching one of the given
+    /// ids and one of the given statuses are included, and `offset` counts only those validators.
+    ///
+    /// The node may return fewer than `limit` validators if `limit` exceeds its maximum page size.
+    ///
+    /// If `state_root` is `Some`, the query will use the given state instead of the default
+    /// canonical head state.
+    pub async fn get_validators_page(
+        &self,
+        state_root: Option<Hash256>,
+        validator_ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+        offset: u64,
+        limit: u64,
+    ) -> Result<ValidatorsPage, Error> {
+        let client = self.0.clone();
+
+        let mut query_params = vec![
+            ("offset".into(), format!("{}", offset)),
+            ("limit".into(), format!("{}", limit)),
+        ];
+        if let Some(state_root) = state_root {
+            query_params.push(("state_root".into(), root_as_string(state_root)));
+        }
+        query_params.extend(
+            validator_ids
+                .unwrap_or(&[])
+                .iter()
+                .map(|validator_id| ("id".into(), validator_id_as_string(validator_id))),
+        );
+        query_params.extend(
+            statuses
+                .unwrap_or(&[])
+                .iter()
+                .map(|status| ("status".into(), status.to_string())),
+        );
+
+        let url = self.url("validators/page")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Returns all validators matching the given `validator_ids` and `statuses` (see
+    /// `Self::get_validators_page`), downloading them in pages of at most `page_size` validators.
+    ///
+    /// All pages are read from the same state, even if the head changes between requests.
+    pub async fn get_all_validators_paged(
+        &self,
+        state_root: Option<Hash256>,
+        validator_ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+        page_size: u64,
+    ) -> Result<Vec<ValidatorResponse>, Error> {
+        if page_size == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let first_page = self
+            .get_validators_page(state_root, validator_ids, statuses, 0, page_size)
+            .await?;
+        let state_root = first_page.state_root;
+        let total = first_page.total;
+        let mut validators = first_page.validators;
+
+        while (validators.len() as u64) < total {
+            let page = self
+                .get_validators_page(
+                    Some(state_root),
+                    validator_ids,
+                    statuses,
+                    validators.len() as u64,
+                    page_size,
+                )
+                .await?;
+            if page.validators.is_empty() {
+                break;
+            }
+            validators.extend(page.validators);
+        }
+
+        Ok(validators)
+    }
+
+    /// Returns the balances of the validators identified by `validator_ids`, or of all validators
+    /// if `validator_ids` is `None`. Unknown validators are omitted from the response.
+    ///
+    /// If `state_root` is `Some`, the query will use the given state instead of the default
+    /// canonical head state.
+    pub async fn get_validator_balances(
+        &self,
+        state_root: Option<Hash256>,
+        validator_ids: Option<&[ValidatorId]>,
+    ) -> Result<Vec<ValidatorBalance>, Error> {
+        let client = self.0.clone();
+
+        let mut query_params = if let Some(state_root) = state_root {
+            vec![("state_root".into(), root_as_string(state_root))]
+        } else {
+            vec![]
+        };
+        query_params.extend(
+            validator_ids
+                .unwrap_or(&[])
+                .iter()
+                .map(|validator_id| ("id".into(), validator_id_as_string(validator_id))),
+        );
+
+        let url = self.url("validators/balances")?;
+        client.json_get(url, query_params).await
+    }
+
     /// Returns committees at the given epoch.
     pub async fn get_committees(&self, epoch: Epoch) -> Result<Vec<Committee>, Error> {
         let client = self.0.clone();
@@ -553,6 +1485,30 @@ impl<E: EthSpec> Beacon<E> {
             .await
     }
 
+    /// Returns a single aggregate of all the attestations in the operation pool with the given
+    /// `slot` and attestation data root.
+    pub async fn get_pool_attestations_aggregate(
+        &self,
+        slot: Slot,
+        data_root: Hash256,
+    ) -> Result<Attestation<E>, Error> {
+        let client = self.0.clone();
+        let query_params = vec![
+            ("slot".into(), format!("{}", slot.as_u64())),
+            ("data_root".into(), root_as_string(data_root)),
+        ];
+
+        let url = self.url("pool/attestations/aggregate")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Returns the number of each type of operation in the operation pool.
+    pub async fn get_pool_summary(&self) -> Result<PoolSummary, Error> {
+        let client = self.0.clone();
+        let url = self.url("pool/summary")?;
+        client.json_get(url, vec![]).await
+    }
+
     pub async fn proposer_slashing(
         &self,
         proposer_slashing: ProposerSlashing,
@@ -561,8 +1517,8 @@ impl<E: EthSpec> Beacon<E> {
 
         let url = self.url("proposer_slashing")?;
         let response = client.json_post::<_>(url, proposer_slashing).await?;
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json().await.map_err(Error::from)
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
     }
 
     pub async fn attester_slashing(
@@ -573,8 +1529,8 @@ impl<E: EthSpec> Beacon<E> {
 
         let url = self.url("attester_slashing")?;
         let response = client.json_post::<_>(url, attester_slashing).await?;
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json().await.map_err(Error::from)
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
     }
 }
 
@@ -597,6 +1553,26 @@ impl<E: EthSpec> Spec<E> {
     }
 }
 
+/// Provides the functions on the `/network` endpoint of the node.
+#[derive(Clone)]
+pub struct Network<E>(HttpClient<E>);
+
+impl<E: EthSpec> Network<E> {
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.0
+            .url("network/")
+            .and_then(move |url| url.join(path).map_err(Error::from))
+            .map_err(Into::into)
+    }
+
+    /// Returns the number of peers connected to the node.
+    pub async fn get_peer_count(&self) -> Result<usize, Error> {
+        let client = self.0.clone();
+        let url = self.url("peer_count")?;
+        client.json_get(url, vec![]).await
+    }
+}
+
 /// Provides the functions on the `/node` endpoint of the node.
 #[derive(Clone)]
 pub struct Node<E>(HttpClient<E>);
@@ -621,6 +1597,70 @@ impl<E: EthSpec> Node<E> {
         client.json_get(url, vec![]).await
     }
 
+    /// Returns the peer ID, ENR and network addresses of the node.
+    pub async fn get_identity(&self) -> Result<NodeIdentity, Error> {
+        let client = self.0.clone();
+        let url = self.url("identity")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns the number of peers known to the node in each connection state.
+    pub async fn get_peer_count(&self) -> Result<PeerCount, Error> {
+        let client = self.0.clone();
+        let url = self.url("peer_count")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns the peers known to the node.
+    ///
+    /// If `states` or `directions` are `Some`, only the peers matching one of the given states and
+    /// one of the given directions are included.
+    pub async fn get_peers(
+        &self,
+        states: Option<&[PeerState]>,
+        directions: Option<&[PeerDirection]>,
+    ) -> Result<Vec<PeerData>, Error> {
+        let client = self.0.clone();
+
+        let mut query_params: Vec<(String, String)> = states
+            .unwrap_or(&[])
+            .iter()
+            .map(|state| ("state".into(), state.to_string()))
+            .collect();
+        query_params.extend(
+            directions
+                .unwrap_or(&[])
+                .iter()
+                .map(|direction| ("direction".into(), direction.to_string())),
+        );
+
+        let url = self.url("peers")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Returns the readiness of the node to serve validators, as determined by the status code of
+    /// its liveness probe.
+    pub async fn get_liveness(&self) -> Result<NodeLiveness, Error> {
+        let url = self.url("liveness")?;
+        let response = self
+            .0
+            .client
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        match response.status() {
+            StatusCode::OK => Ok(NodeLiveness::Synced),
+            StatusCode::PARTIAL_CONTENT => Ok(NodeLiveness::Syncing),
+            StatusCode::SERVICE_UNAVAILABLE => Ok(NodeLiveness::Unavailable),
+            status => Err(Error::DidNotSucceed {
+                status,
+                body: self.0.response_text(response).await?,
+            }),
+        }
+    }
+
     pub async fn syncing_status(&self) -> Result<SyncingResponse, Error> {
         let client = self.0.clone();
         let url = self.url("syncing")?;
@@ -678,8 +1718,8 @@ impl<E: EthSpec> Consensus<E> {
 
         let url = self.url("individual_votes")?;
         let response = client.json_post::<_>(url, req_body).await?;
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json().await.map_err(Error::from)
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
     }
 
     /// Gets a `VoteCount` for the given `epoch`.
@@ -691,11 +1731,127 @@ impl<E: EthSpec> Consensus<E> {
     }
 }
 
+/// Provides the functions on the `/lighthouse` endpoint of the node.
+#[derive(Clone)]
+pub struct Lighthouse<E>(HttpClient<E>);
+
+impl<E: EthSpec> Lighthouse<E> {
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.0
+            .url("lighthouse/")
+            .and_then(move |url| url.join(path).map_err(Error::from))
+            .map_err(Into::into)
+    }
+
+    /// Returns the current slot of the node, along with the genesis time it was computed from.
+    pub async fn get_current_slot(&self) -> Result<CurrentSlotResponse, Error> {
+        let client = self.0.clone();
+        let url = self.url("current_slot")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns the effectiveness of the attestations of the validator with `validator_id` during
+    /// the last `epochs` epochs for which attestation inclusion is final.
+    pub async fn get_validator_effectiveness(
+        &self,
+        validator_id: &ValidatorId,
+        epochs: u64,
+    ) -> Result<ValidatorEffectiveness, Error> {
+        let client = self.0.clone();
+        let url = self.url("validator/effectiveness")?;
+        client
+            .json_get(
+                url,
+                vec![
+                    ("id".into(), validator_id_as_string(validator_id)),
+                    ("epochs".into(), format!("{}", epochs)),
+                ],
+            )
+            .await
+    }
+
+    /// Returns the duties of the given validator during each of the last `epochs` epochs, ending
+    /// at the current epoch.
+    pub async fn get_validator_duty_history(
+        &self,
+        validator_id: &ValidatorId,
+        epochs: u64,
+    ) -> Result<Vec<EpochValidatorDuty>, Error> {
+        let client = self.0.clone();
+        let url = self.url("validator/duty_history")?;
+        client
+            .json_get(
+                url,
+                vec![
+                    ("id".into(), validator_id_as_string(validator_id)),
+                    ("epochs".into(), format!("{}", epochs)),
+                ],
+            )
+            .await
+    }
+
+    /// Checks the internal consistency of the node's spec, returning a description of each
+    /// violated invariant. An empty list indicates that the spec is self-consistent.
+    pub async fn validate_spec(&self) -> Result<Vec<String>, Error> {
+        let client = self.0.clone();
+        let url = self.url("spec/validate")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns the root and slot of the last `count` blocks in the canonical chain, in order of
+    /// descending slot.
+    pub async fn get_recent_blocks(&self, count: u64) -> Result<Vec<RecentBlock>, Error> {
+        let client = self.0.clone();
+        let url = self.url("recent_blocks")?;
+        client
+            .json_get(url, vec![("count".into(), format!("{}", count))])
+            .await
+    }
+
+    /// Returns all attestations in the operation pool and the naive aggregation pool of the node,
+    /// along with some metadata about each attestation.
+    pub async fn export_attestations(&self) -> Result<Vec<ExportedAttestation<E>>, Error> {
+        let client = self.0.clone();
+        let url = self.url("op_pool/attestations/export")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Prunes the operation pool of the node, returning the number of each type of operation that
+    /// was removed.
+    pub async fn prune_op_pool(&self) -> Result<PoolSummary, Error> {
+        let client = self.0.clone();
+        let url = self.url("op_pool/prune")?;
+        let response = client
+            .client
+            .post(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
+    }
+
+    /// Imports the `blocks`, in order, returning the outcome of importing each block.
+    ///
+    /// The blocks are not published to the network.
+    pub async fn import_blocks(
+        &self,
+        blocks: Vec<SignedBeaconBlock<E>>,
+    ) -> Result<Vec<BlockImportResult>, Error> {
+        let client = self.0.clone();
+        let url = self.url("blocks/batch")?;
+        let response = client.ssz_post(url, &blocks).await?;
+        let success = client.error_for_status(response).await?;
+        client.json_response(success).await
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(bound = "T: EthSpec")]
 pub struct BlockResponse<T: EthSpec> {
     pub beacon_block: SignedBeaconBlock<T>,
     pub root: Hash256,
+    pub finalized: bool,
 }
 
 #[derive(Deserialize)]
@@ -703,6 +1859,7 @@ pub struct BlockResponse<T: EthSpec> {
 pub struct StateResponse<T: EthSpec> {
     pub beacon_state: BeaconState<T>,
     pub root: Hash256,
+    pub finalized: bool,
 }
 
 fn root_as_string(root: Hash256) -> String {
@@ -713,6 +1870,13 @@ fn as_ssz_hex_string<T: Encode>(item: &T) -> String {
     format!("0x{}", hex::encode(item.as_ssz_bytes()))
 }
 
+fn validator_id_as_string(validator_id: &ValidatorId) -> String {
+    match validator_id {
+        ValidatorId::Index(index) => format!("{}", index),
+        ValidatorId::PublicKey(pubkey) => as_ssz_hex_string(pubkey),
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Error {
         Error::ReqwestError(e)
@@ -730,3 +1894,435 @@ impl From<serde_json::Error> for Error {
         Error::SerdeJsonError(e)
     }
 }
+
+impl From<ssz::DecodeError> for Error {
+    fn from(e: ssz::DecodeError) -> Error {
+        Error::SszDecodeError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use std::time::Instant;
+    use types::MinimalEthSpec;
+
+    /// Starts a server which accepts connections but never responds to them.
+    fn unresponsive_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind listener");
+        let addr = listener.local_addr().expect("should have local addr");
+
+        std::thread::spawn(move || {
+            // Hold the connections open without responding.
+            let _connections = listener.incoming().collect::<Vec<_>>();
+        });
+
+        format!("http://{}/", addr)
+    }
+
+    /// Starts a server which responds to the first `failures` requests with `status` and to all
+    /// others with a JSON string, counting the requests it receives.
+    fn failing_server(status: &'static str, failures: usize) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind listener");
+        let addr = listener.local_addr().expect("should have local addr");
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().filter_map(Result::ok) {
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+                let response = if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    format!(
+                        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        status
+                    )
+                } else {
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 3\r\nConnection: close\r\n\r\n\"v\"".to_string()
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (format!("http://{}/", addr), requests)
+    }
+
+    /// Starts a server which keeps connections alive and responds to every request with a JSON
+    /// string, counting the connections it accepts.
+    fn keep_alive_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind listener");
+        let addr = listener.local_addr().expect("should have local addr");
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().filter_map(Result::ok) {
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(move || {
+                    let mut request = vec![];
+                    let mut buf = [0; 4096];
+                    loop {
+                        match stream.read(&mut buf) {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                        // `GET` requests have no body, so each one ends with a blank line.
+                        while let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                            request.drain(..end + 4);
+                            if stream
+                                .write_all(
+                                    b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 3\r\n\r\n\"v\"",
+                                )
+                                .is_err()
+                            {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        (format!("http://{}/", addr), connections)
+    }
+
+    /// Starts a server which responds to every request with `status` and a body of `length` bytes,
+    /// written in small chunks and without a `Content-Length` header.
+    fn streaming_server(status: &'static str, length: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind listener");
+        let addr = listener.local_addr().expect("should have local addr");
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().filter_map(Result::ok) {
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(
+                    format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n",
+                        status
+                    )
+                    .as_bytes(),
+                );
+                for _ in 0..length / 1024 {
+                    if stream.write_all(&[b' '; 1024]).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        format!("http://{}/", addr)
+    }
+
+    /// Starts a server which responds to every request with an event stream, sending a single
+    /// `head` event after `delay`.
+    fn event_stream_server(delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind listener");
+        let addr = listener.local_addr().expect("should have local addr");
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().filter_map(Result::ok) {
+                std::thread::spawn(move || {
+                    let mut buf = [0; 4096];
+                    let _ = stream.read(&mut buf);
+                    let _ = stream.write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n",
+                    );
+                    std::thread::sleep(delay);
+                    let _ = stream.write_all(b"event: head\ndata: {}\n\n");
+                    // Hold the stream open, as a beacon node would.
+                    std::thread::sleep(delay);
+                });
+            }
+        });
+
+        format!("http://{}/", addr)
+    }
+
+    #[tokio::test]
+    async fn event_streams_outlive_the_request_timeout() {
+        let timeout = Duration::from_millis(200);
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(
+            event_stream_server(timeout * 3),
+            timeout,
+        )
+        .expect("should create client");
+
+        let start = Instant::now();
+        let events = node
+            .http
+            .beacon()
+            .get_events(&["head"])
+            .await
+            .expect("should subscribe to events");
+        let mut events = Box::pin(events);
+        let event = events
+            .next()
+            .await
+            .expect("stream should not end")
+            .expect("should read an event after the request timeout");
+
+        assert!(
+            start.elapsed() > timeout,
+            "the event should be sent after the request timeout"
+        );
+        assert_eq!(
+            event,
+            ServerSentEvent {
+                topic: "head".to_string(),
+                data: "{}".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_time_out() {
+        let timeout = Duration::from_millis(500);
+        let node =
+            RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(unresponsive_server(), timeout)
+                .expect("should create client");
+
+        assert_eq!(node.http.timeout(), timeout);
+
+        let assert_times_out = |result: Result<(), Error>, start: Instant, request: &str| {
+            let error = result.expect_err(&format!("{} should not succeed", request));
+            assert!(
+                error.is_timeout(),
+                "{} should time out, got: {:?}",
+                request,
+                error
+            );
+            assert!(
+                start.elapsed() < timeout * 4,
+                "{} should time out within the configured bound",
+                request
+            );
+        };
+
+        let start = Instant::now();
+        let result = node.http.node().syncing_status().await.map(|_| ());
+        assert_times_out(result, start, "syncing");
+
+        let start = Instant::now();
+        let result = node
+            .http
+            .validator()
+            .get_duties(Epoch::new(0), &[])
+            .await
+            .map(|_| ());
+        assert_times_out(result, start, "duties");
+
+        let start = Instant::now();
+        let result = node
+            .http
+            .validator()
+            .produce_block(Slot::new(1), Signature::empty(), None)
+            .await
+            .map(|_| ());
+        assert_times_out(result, start, "block production");
+    }
+
+    #[tokio::test]
+    async fn only_get_requests_are_retried() {
+        let (url, requests) = failing_server("503 Service Unavailable", usize::max_value());
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_retries(
+            url,
+            Duration::from_secs(REQUEST_TIMEOUT_SECONDS),
+            2,
+            Duration::from_millis(10),
+        )
+        .expect("should create client");
+
+        assert_eq!(node.http.get_retries(), 2);
+
+        let result = node.http.node().syncing_status().await;
+        assert_matches_status(result.map(|_| ()), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            requests.load(Ordering::SeqCst),
+            3,
+            "get should be retried twice"
+        );
+
+        requests.store(0, Ordering::SeqCst);
+        let result = node
+            .http
+            .validator()
+            .publish_block(SignedBeaconBlock {
+                message: BeaconBlock::empty(&MinimalEthSpec::default_spec()),
+                signature: Signature::empty(),
+            })
+            .await;
+        assert!(result.is_err(), "post should not succeed");
+        assert_eq!(
+            requests.load(Ordering::SeqCst),
+            1,
+            "post should not be retried"
+        );
+    }
+
+    #[tokio::test]
+    async fn sequential_requests_reuse_connections() {
+        let timeout = Duration::from_secs(REQUEST_TIMEOUT_SECONDS);
+
+        let (url, connections) = keep_alive_server();
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(url, timeout)
+            .expect("should create client");
+
+        assert_eq!(node.http.pool_config(), ConnectionPoolConfig::default());
+
+        for _ in 0..16 {
+            let version = node.http.node().get_version().await;
+            assert_eq!(version.expect("should get version"), "v");
+        }
+        assert_eq!(
+            connections.load(Ordering::SeqCst),
+            1,
+            "requests should share a single connection"
+        );
+
+        let (url, connections) = keep_alive_server();
+        let pool = ConnectionPoolConfig {
+            idle_timeout: None,
+            max_idle: 0,
+        };
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_pool(url, timeout, pool)
+            .expect("should create client");
+
+        for _ in 0..16 {
+            node.http
+                .node()
+                .get_version()
+                .await
+                .expect("should get version");
+        }
+        assert_eq!(
+            connections.load(Ordering::SeqCst),
+            16,
+            "requests should not reuse connections without a pool"
+        );
+    }
+
+    #[test]
+    fn retry_backoff_is_capped() {
+        let policy = RetryPolicy::new(usize::max_value(), Duration::from_millis(10));
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(10));
+        assert_eq!(policy.backoff(3), Duration::from_millis(80));
+        assert_eq!(policy.backoff(20), MAX_RETRY_BACKOFF);
+        assert_eq!(
+            policy.backoff(32),
+            MAX_RETRY_BACKOFF,
+            "should not overflow the factor"
+        );
+        assert_eq!(
+            policy.backoff(usize::max_value()),
+            MAX_RETRY_BACKOFF,
+            "should not overflow the attempt"
+        );
+
+        let policy = RetryPolicy::new(1, Duration::from_secs(u64::max_value()));
+        assert_eq!(
+            policy.backoff(1),
+            MAX_RETRY_BACKOFF,
+            "should not overflow the duration"
+        );
+    }
+
+    #[tokio::test]
+    async fn transient_server_errors_are_retried() {
+        let timeout = Duration::from_secs(REQUEST_TIMEOUT_SECONDS);
+        let policy = RetryPolicy::new(3, Duration::from_millis(10));
+
+        let (url, requests) = failing_server("503 Service Unavailable", 2);
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(url, timeout)
+            .expect("should create client");
+        let http = node.http.with_retry_policy(policy.clone());
+
+        let version = http.node().get_version().await;
+        assert_eq!(version.expect("get should succeed after retries"), "v");
+        assert_eq!(
+            requests.load(Ordering::SeqCst),
+            3,
+            "get should be retried until it succeeds"
+        );
+
+        let (url, requests) = failing_server("500 Internal Server Error", 2);
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(url, timeout)
+            .expect("should create client");
+        let http = node.http.with_retry_policy(policy);
+
+        let result = http.node().get_version().await;
+        assert_matches_status(result.map(|_| ()), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            requests.load(Ordering::SeqCst),
+            1,
+            "get should not be retried on a non-transient status"
+        );
+    }
+
+    #[tokio::test]
+    async fn responses_are_size_limited() {
+        let timeout = Duration::from_secs(REQUEST_TIMEOUT_SECONDS);
+        let limit = 64 * 1024;
+
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(
+            streaming_server("200 OK", 1 << 30),
+            timeout,
+        )
+        .expect("should create client");
+        let http = node.http.with_max_response_size(limit);
+
+        assert_eq!(http.max_response_size(), Some(limit));
+
+        match http.node().get_version().await {
+            Err(Error::ResponseTooLarge { limit: l }) => assert_eq!(l, limit),
+            other => panic!("should exceed the response size limit, got: {:?}", other),
+        }
+
+        // The bodies of error and rejection responses are limited too.
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(
+            streaming_server("404 Not Found", 1 << 30),
+            timeout,
+        )
+        .expect("should create client");
+        let http = node.http.with_max_response_size(limit);
+
+        match http.node().get_version().await {
+            Err(Error::ResponseTooLarge { limit: l }) => assert_eq!(l, limit),
+            other => panic!("should exceed the response size limit, got: {:?}", other),
+        }
+
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(
+            streaming_server("202 Accepted", 1 << 30),
+            timeout,
+        )
+        .expect("should create client");
+        let http = node.http.with_max_response_size(limit);
+
+        match http.validator().publish_attestations(vec![]).await {
+            Err(Error::ResponseTooLarge { limit: l }) => assert_eq!(l, limit),
+            other => panic!("should exceed the response size limit, got: {:?}", other),
+        }
+
+        let (url, _) = keep_alive_server();
+        let node = RemoteBeaconNode::<MinimalEthSpec>::new_with_timeout(url, timeout)
+            .expect("should create client");
+        let http = node.http.with_max_response_size(limit);
+
+        let version = http.node().get_version().await;
+        assert_eq!(version.expect("should read response within the limit"), "v");
+    }
+
+    fn assert_matches_status(result: Result<(), Error>, expected: StatusCode) {
+        match result {
+            Err(Error::DidNotSucceed { status, .. }) => assert_eq!(status, expected),
+            other => panic!("expected status {}, got: {:?}", expected, other),
+        }
+    }
+}
@@ -7,11 +7,16 @@ pub enum ApiError {
     ServerError(String),
     NotImplemented(String),
     BadRequest(String),
+    Forbidden(String),
     NotFound(String),
     UnsupportedType(String),
     ImATeapot(String),       // Just in case.
     ProcessingError(String), // A 202 error, for when a block/attestation cannot be processed, but still transmitted.
     InvalidHeaderValue(String),
+    ServiceUnavailable(String),
+    /// As per `ServiceUnavailable`, with a hint of the number of seconds after which to retry.
+    ServiceUnavailableRetryAfter(String, u64),
+    NotAcceptable(String),
 }
 
 pub type ApiResult = Result<Response<Body>, ApiError>;
@@ -23,21 +28,36 @@ impl ApiError {
             ApiError::ServerError(desc) => (StatusCode::INTERNAL_SERVER_ERROR, desc),
             ApiError::NotImplemented(desc) => (StatusCode::NOT_IMPLEMENTED, desc),
             ApiError::BadRequest(desc) => (StatusCode::BAD_REQUEST, desc),
+            ApiError::Forbidden(desc) => (StatusCode::FORBIDDEN, desc),
             ApiError::NotFound(desc) => (StatusCode::NOT_FOUND, desc),
             ApiError::UnsupportedType(desc) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, desc),
             ApiError::ImATeapot(desc) => (StatusCode::IM_A_TEAPOT, desc),
             ApiError::ProcessingError(desc) => (StatusCode::ACCEPTED, desc),
             ApiError::InvalidHeaderValue(desc) => (StatusCode::INTERNAL_SERVER_ERROR, desc),
+            ApiError::ServiceUnavailable(desc) => (StatusCode::SERVICE_UNAVAILABLE, desc),
+            ApiError::ServiceUnavailableRetryAfter(desc, _) => {
+                (StatusCode::SERVICE_UNAVAILABLE, desc)
+            }
+            ApiError::NotAcceptable(desc) => (StatusCode::NOT_ACCEPTABLE, desc),
         }
     }
 }
 
 impl Into<Response<Body>> for ApiError {
     fn into(self) -> Response<Body> {
+        let retry_after = match self {
+            ApiError::ServiceUnavailableRetryAfter(_, seconds) => Some(seconds),
+            _ => None,
+        };
         let (status_code, desc) = self.status_code();
-        Response::builder()
+
+        let mut builder = Response::builder()
             .status(status_code)
-            .header("content-type", "text/plain; charset=utf-8")
+            .header("content-type", "text/plain; charset=utf-8");
+        if let Some(seconds) = retry_after {
+            builder = builder.header("retry-after", seconds.to_string());
+        }
+        builder
             .body(Body::from(desc))
             .expect("Response should always be created.")
     }
@@ -3,8 +3,15 @@
 use bls::PublicKeyBytes;
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
+use state_processing::per_epoch_processing::{apply_rewards::Delta, AttestationComponentDeltas};
+use std::fmt;
+use std::str::FromStr;
 use types::beacon_state::EthSpec;
-use types::{BeaconState, CommitteeIndex, Hash256, SignedBeaconBlock, Slot, Validator};
+use types::utils::{fork_from_hex_str, fork_to_hex_str};
+use types::{
+    Attestation, BeaconState, Checkpoint, CommitteeIndex, Epoch, Hash256, SignedBeaconBlock, Slot,
+    Validator,
+};
 
 /// Information about a block that is at the head of a chain. May or may not represent the
 /// canonical head.
@@ -19,6 +26,32 @@ pub struct HeadBeaconBlock {
 pub struct BlockResponse<T: EthSpec> {
     pub root: Hash256,
     pub beacon_block: SignedBeaconBlock<T>,
+    /// Set to `true` if the block is finalized on the canonical chain.
+    pub finalized: bool,
+}
+
+/// The attestations included in a single block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(bound = "T: EthSpec")]
+pub struct BlockAttestations<T: EthSpec> {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    pub attestations: Vec<Attestation<T>>,
+}
+
+/// A block in the canonical chain, as returned by `/lighthouse/recent_blocks`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct RecentBlock {
+    pub root: Hash256,
+    pub slot: Slot,
+}
+
+/// The root of the block at some slot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct BlockRootResponse {
+    pub root: Hash256,
+    /// Set to `true` if the block is on the canonical chain.
+    pub canonical: bool,
 }
 
 /// Information about the block and state that are at head of the beacon chain.
@@ -27,6 +60,8 @@ pub struct CanonicalHeadResponse {
     pub slot: Slot,
     pub block_root: Hash256,
     pub state_root: Hash256,
+    /// The index of the validator which proposed the head block.
+    pub proposer_index: u64,
     pub finalized_slot: Slot,
     pub finalized_block_root: Hash256,
     pub justified_slot: Slot,
@@ -41,6 +76,120 @@ pub struct ValidatorResponse {
     pub validator_index: Option<usize>,
     pub balance: Option<u64>,
     pub validator: Option<Validator>,
+    /// Set to `true` if the state from which the validator was read is finalized on the canonical
+    /// chain.
+    pub finalized: bool,
+}
+
+/// The stage of its lifecycle which a validator is in at some epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
+impl ValidatorStatus {
+    /// Returns the status of the `validator` (with the given `balance`) at `epoch`.
+    pub fn from_validator(
+        validator: &Validator,
+        balance: u64,
+        epoch: Epoch,
+        far_future_epoch: Epoch,
+    ) -> Self {
+        if epoch < validator.activation_epoch {
+            if validator.activation_eligibility_epoch == far_future_epoch {
+                ValidatorStatus::PendingInitialized
+            } else {
+                ValidatorStatus::PendingQueued
+            }
+        } else if epoch < validator.exit_epoch {
+            if validator.slashed {
+                ValidatorStatus::ActiveSlashed
+            } else if validator.exit_epoch == far_future_epoch {
+                ValidatorStatus::ActiveOngoing
+            } else {
+                ValidatorStatus::ActiveExiting
+            }
+        } else if epoch < validator.withdrawable_epoch {
+            if validator.slashed {
+                ValidatorStatus::ExitedSlashed
+            } else {
+                ValidatorStatus::ExitedUnslashed
+            }
+        } else if balance == 0 {
+            ValidatorStatus::WithdrawalDone
+        } else {
+            ValidatorStatus::WithdrawalPossible
+        }
+    }
+}
+
+impl FromStr for ValidatorStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending_initialized" => Ok(ValidatorStatus::PendingInitialized),
+            "pending_queued" => Ok(ValidatorStatus::PendingQueued),
+            "active_ongoing" => Ok(ValidatorStatus::ActiveOngoing),
+            "active_exiting" => Ok(ValidatorStatus::ActiveExiting),
+            "active_slashed" => Ok(ValidatorStatus::ActiveSlashed),
+            "exited_unslashed" => Ok(ValidatorStatus::ExitedUnslashed),
+            "exited_slashed" => Ok(ValidatorStatus::ExitedSlashed),
+            "withdrawal_possible" => Ok(ValidatorStatus::WithdrawalPossible),
+            "withdrawal_done" => Ok(ValidatorStatus::WithdrawalDone),
+            other => Err(format!("Invalid validator status: {:?}", other)),
+        }
+    }
+}
+
+impl fmt::Display for ValidatorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ValidatorStatus::PendingInitialized => "pending_initialized",
+            ValidatorStatus::PendingQueued => "pending_queued",
+            ValidatorStatus::ActiveOngoing => "active_ongoing",
+            ValidatorStatus::ActiveExiting => "active_exiting",
+            ValidatorStatus::ActiveSlashed => "active_slashed",
+            ValidatorStatus::ExitedUnslashed => "exited_unslashed",
+            ValidatorStatus::ExitedSlashed => "exited_slashed",
+            ValidatorStatus::WithdrawalPossible => "withdrawal_possible",
+            ValidatorStatus::WithdrawalDone => "withdrawal_done",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Identifies a validator either by its index in the validator registry or by its public key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidatorId {
+    Index(u64),
+    PublicKey(PublicKeyBytes),
+}
+
+/// The balance of the validator at `index`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorBalance {
+    pub index: u64,
+    pub balance: u64,
+}
+
+/// A page of the validators in some `BeaconState`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorsPage {
+    /// The root of the state from which the page was read.
+    pub state_root: Hash256,
+    /// The number of validators across all pages.
+    pub total: u64,
+    pub validators: Vec<ValidatorResponse>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
@@ -50,6 +199,13 @@ pub struct ValidatorRequest {
     pub pubkeys: Vec<PublicKeyBytes>,
 }
 
+/// A request for a single validator across several states.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorStatesRequest {
+    pub state_roots: Vec<Hash256>,
+    pub pubkey: PublicKeyBytes,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct Committee {
     pub slot: Slot,
@@ -62,4 +218,231 @@ pub struct Committee {
 pub struct StateResponse<T: EthSpec> {
     pub root: Hash256,
     pub beacon_state: BeaconState<T>,
+    /// Set to `true` if the state is finalized on the canonical chain.
+    pub finalized: bool,
+}
+
+/// The current slot of the node, along with the genesis time it was computed from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct CurrentSlotResponse {
+    pub slot: Slot,
+    pub genesis_time: u64,
+}
+
+/// The properties of the genesis of the beacon chain, which never change.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenesisData {
+    pub genesis_time: u64,
+    pub genesis_validators_root: Hash256,
+    #[serde(
+        serialize_with = "fork_to_hex_str",
+        deserialize_with = "fork_from_hex_str"
+    )]
+    pub genesis_fork_version: [u8; 4],
+}
+
+/// The justification and finalization checkpoints of some `BeaconState`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct FinalityCheckpoints {
+    pub previous_justified: Checkpoint,
+    pub current_justified: Checkpoint,
+    pub finalized: Checkpoint,
+}
+
+impl<T: EthSpec> From<&BeaconState<T>> for FinalityCheckpoints {
+    fn from(state: &BeaconState<T>) -> Self {
+        Self {
+            previous_justified: state.previous_justified_checkpoint,
+            current_justified: state.current_justified_checkpoint,
+            finalized: state.finalized_checkpoint,
+        }
+    }
+}
+
+/// The outcome of importing a single block from a batch of blocks.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockImportResult {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    /// Set to `None` if the block was imported successfully.
+    pub error: Option<String>,
+}
+
+impl BlockImportResult {
+    /// Returns `true` if the block was imported.
+    pub fn is_imported(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// A request for the rewards of some validators for their attestations during `epoch`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct AttestationRewardsRequest {
+    pub epoch: Epoch,
+    /// The validators to return rewards for, or all validators if empty.
+    pub validator_indices: Vec<u64>,
+}
+
+/// The rewards of a validator for each component of its attestation during some epoch, in Gwei.
+///
+/// Penalties are given as negative rewards.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttestationRewards {
+    pub validator_index: u64,
+    pub source: i64,
+    pub target: i64,
+    pub head: i64,
+    pub inclusion_delay: i64,
+    pub inactivity_penalty: i64,
+}
+
+impl AttestationRewards {
+    pub fn from_deltas(validator_index: u64, deltas: &AttestationComponentDeltas) -> Self {
+        let net = |delta: &Delta| delta.rewards() as i64 - delta.penalties() as i64;
+
+        Self {
+            validator_index,
+            source: net(&deltas.source),
+            target: net(&deltas.target),
+            head: net(&deltas.head),
+            inclusion_delay: net(&deltas.inclusion_delay),
+            inactivity_penalty: net(&deltas.inactivity_penalty),
+        }
+    }
+}
+
+/// The reward of the proposer of a block for each kind of operation it included, in Gwei.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct BlockRewards {
+    pub block_root: Hash256,
+    pub proposer_index: u64,
+    /// The reward for newly included attesters, credited during epoch processing.
+    pub attestations: u64,
+    pub proposer_slashings: u64,
+    pub attester_slashings: u64,
+    pub total: u64,
+}
+
+/// The number of each type of operation in the operation pool.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct PoolSummary {
+    pub attestations: u64,
+    pub attester_slashings: u64,
+    pub proposer_slashings: u64,
+    pub voluntary_exits: u64,
+}
+
+/// The pool from which an exported attestation was read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationPoolKind {
+    /// The operation pool, holding attestations that may be included in a block.
+    OpPool,
+    /// The naive aggregation pool, holding unaggregated attestations seen on subnets.
+    Naive,
+}
+
+/// An attestation in one of the node's pools, as returned by
+/// `/lighthouse/op_pool/attestations/export`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+pub struct ExportedAttestation<T: EthSpec> {
+    pub pool: AttestationPoolKind,
+    pub slot: Slot,
+    pub committee_index: CommitteeIndex,
+    /// The number of set bits in the aggregation bitfield.
+    pub bit_count: u64,
+    /// The number of slots between the attestation slot and the current slot.
+    pub age: u64,
+    pub attestation: Attestation<T>,
+}
+
+impl<T: EthSpec> ExportedAttestation<T> {
+    pub fn new(pool: AttestationPoolKind, attestation: Attestation<T>, current_slot: Slot) -> Self {
+        Self {
+            pool,
+            slot: attestation.data.slot,
+            committee_index: attestation.data.index,
+            bit_count: attestation.aggregation_bits.num_set_bits() as u64,
+            age: current_slot.saturating_sub(attestation.data.slot).as_u64(),
+            attestation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validator_status_from_validator() {
+        let far_future_epoch = Epoch::new(u64::max_value());
+        let validator = Validator {
+            pubkey: PublicKeyBytes::empty(),
+            withdrawal_credentials: Hash256::zero(),
+            effective_balance: 32,
+            slashed: false,
+            activation_eligibility_epoch: Epoch::new(1),
+            activation_epoch: Epoch::new(2),
+            exit_epoch: Epoch::new(4),
+            withdrawable_epoch: Epoch::new(6),
+        };
+        let status = |validator: &Validator, balance: u64, epoch: u64| {
+            ValidatorStatus::from_validator(validator, balance, Epoch::new(epoch), far_future_epoch)
+        };
+
+        let initialized = Validator {
+            activation_eligibility_epoch: far_future_epoch,
+            activation_epoch: far_future_epoch,
+            ..validator.clone()
+        };
+        assert_eq!(
+            status(&initialized, 32, 0),
+            ValidatorStatus::PendingInitialized
+        );
+        assert_eq!(status(&validator, 32, 1), ValidatorStatus::PendingQueued);
+        assert_eq!(status(&validator, 32, 3), ValidatorStatus::ActiveExiting);
+        assert_eq!(status(&validator, 32, 4), ValidatorStatus::ExitedUnslashed);
+        assert_eq!(
+            status(&validator, 32, 6),
+            ValidatorStatus::WithdrawalPossible
+        );
+        assert_eq!(status(&validator, 0, 6), ValidatorStatus::WithdrawalDone);
+
+        let ongoing = Validator {
+            exit_epoch: far_future_epoch,
+            withdrawable_epoch: far_future_epoch,
+            ..validator.clone()
+        };
+        assert_eq!(status(&ongoing, 32, 3), ValidatorStatus::ActiveOngoing);
+
+        let slashed = Validator {
+            slashed: true,
+            ..validator
+        };
+        assert_eq!(status(&slashed, 32, 3), ValidatorStatus::ActiveSlashed);
+        assert_eq!(status(&slashed, 32, 5), ValidatorStatus::ExitedSlashed);
+    }
+
+    #[test]
+    fn validator_status_string_round_trip() {
+        for status in &[
+            ValidatorStatus::PendingInitialized,
+            ValidatorStatus::PendingQueued,
+            ValidatorStatus::ActiveOngoing,
+            ValidatorStatus::ActiveExiting,
+            ValidatorStatus::ActiveSlashed,
+            ValidatorStatus::ExitedUnslashed,
+            ValidatorStatus::ExitedSlashed,
+            ValidatorStatus::WithdrawalPossible,
+            ValidatorStatus::WithdrawalDone,
+        ] {
+            assert_eq!(status.to_string().parse(), Ok(*status));
+            assert_eq!(
+                serde_json::to_string(status).unwrap(),
+                format!("\"{}\"", status)
+            );
+        }
+        assert!("active".parse::<ValidatorStatus>().is_err());
+    }
 }
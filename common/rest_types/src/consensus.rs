@@ -64,3 +64,149 @@ pub struct IndividualVotesResponse {
     /// Voting statistics for the validator, if they voted in the given epoch.
     pub vote: Option<IndividualVote>,
 }
+
+/// The effectiveness of a validator's attestation during some epoch.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct EpochEffectiveness {
+    pub epoch: Epoch,
+    /// The distance between the attestation slot and the slot of the block which included it, if
+    /// it was included.
+    pub inclusion_delay: Option<u64>,
+    /// True if the attestation voted for the correct source checkpoint.
+    pub is_source_attester: bool,
+    /// True if the attestation voted for the correct target checkpoint.
+    pub is_target_attester: bool,
+    /// True if the attestation voted for the correct head block.
+    pub is_head_attester: bool,
+    /// The proportion of correct votes, divided by the inclusion delay. Ranges from `0` (no
+    /// attestation was included) to `1` (a fully correct attestation was included in the next
+    /// slot).
+    pub score: f64,
+}
+
+impl EpochEffectiveness {
+    /// Returns the effectiveness of the validator with `status` during the _previous_ epoch of
+    /// the state from which `status` was computed, which must be `epoch`.
+    ///
+    /// Returns `None` if the validator was not active during `epoch`.
+    pub fn from_validator_status(epoch: Epoch, status: &ValidatorStatus) -> Option<Self> {
+        if !status.is_active_in_previous_epoch {
+            return None;
+        }
+
+        let inclusion_delay = status
+            .inclusion_info
+            .filter(|_| status.is_previous_epoch_attester)
+            .map(|info| info.delay);
+        let is_source_attester = status.is_previous_epoch_attester;
+        let is_target_attester = status.is_previous_epoch_target_attester;
+        let is_head_attester = status.is_previous_epoch_head_attester;
+
+        let score = inclusion_delay.map_or(0.0, |delay| {
+            let correct_votes = [is_source_attester, is_target_attester, is_head_attester]
+                .iter()
+                .filter(|vote| **vote)
+                .count();
+            correct_votes as f64 / 3.0 / delay.max(1) as f64
+        });
+
+        Some(Self {
+            epoch,
+            inclusion_delay,
+            is_source_attester,
+            is_target_attester,
+            is_head_attester,
+            score,
+        })
+    }
+}
+
+/// The effectiveness of a validator's attestations over a range of epochs, as returned by the
+/// `/lighthouse/validator/effectiveness` endpoint.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct ValidatorEffectiveness {
+    pub validator_index: u64,
+    /// The mean score of `epochs`, or `None` if the validator was not active during any of them.
+    pub effectiveness: Option<f64>,
+    /// The effectiveness during each epoch of the range during which the validator was active.
+    pub epochs: Vec<EpochEffectiveness>,
+}
+
+impl ValidatorEffectiveness {
+    pub fn new(validator_index: u64, epochs: Vec<EpochEffectiveness>) -> Self {
+        let effectiveness = if epochs.is_empty() {
+            None
+        } else {
+            Some(epochs.iter().map(|epoch| epoch.score).sum::<f64>() / epochs.len() as f64)
+        };
+
+        Self {
+            validator_index,
+            effectiveness,
+            epochs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use state_processing::per_epoch_processing::validator_statuses::InclusionInfo;
+
+    fn attester(delay: u64, target: bool, head: bool) -> ValidatorStatus {
+        ValidatorStatus {
+            is_active_in_previous_epoch: true,
+            is_previous_epoch_attester: true,
+            is_previous_epoch_target_attester: target,
+            is_previous_epoch_head_attester: head,
+            inclusion_info: Some(InclusionInfo {
+                delay,
+                proposer_index: 0,
+            }),
+            ..ValidatorStatus::default()
+        }
+    }
+
+    #[test]
+    fn effectiveness_is_weighted_by_delay_and_correctness() {
+        let epoch = Epoch::new(3);
+        let score = |status: &ValidatorStatus| {
+            EpochEffectiveness::from_validator_status(epoch, status)
+                .expect("should be active")
+                .score
+        };
+
+        assert_eq!(score(&attester(1, true, true)), 1.0);
+        assert_eq!(score(&attester(2, true, true)), 0.5);
+        assert_eq!(score(&attester(1, true, false)), 2.0 / 3.0);
+        assert_eq!(score(&attester(4, false, false)), 1.0 / 12.0);
+
+        let absent = ValidatorStatus {
+            is_active_in_previous_epoch: true,
+            ..ValidatorStatus::default()
+        };
+        assert_eq!(score(&absent), 0.0);
+
+        assert_eq!(
+            EpochEffectiveness::from_validator_status(epoch, &ValidatorStatus::default()),
+            None,
+            "inactive validators should not be scored"
+        );
+    }
+
+    #[test]
+    fn effectiveness_is_mean_of_epochs() {
+        let epochs = vec![
+            EpochEffectiveness::from_validator_status(Epoch::new(1), &attester(1, true, true))
+                .unwrap(),
+            EpochEffectiveness::from_validator_status(Epoch::new(2), &attester(2, true, true))
+                .unwrap(),
+        ];
+
+        assert_eq!(
+            ValidatorEffectiveness::new(0, epochs).effectiveness,
+            Some(0.75)
+        );
+        assert_eq!(ValidatorEffectiveness::new(0, vec![]).effectiveness, None);
+    }
+}
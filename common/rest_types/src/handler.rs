@@ -28,12 +28,45 @@ impl From<&str> for ApiEncodingFormat {
     fn from(f: &str) -> ApiEncodingFormat {
         match f {
             "application/yaml" => ApiEncodingFormat::YAML,
-            "application/ssz" => ApiEncodingFormat::SSZ,
+            "application/ssz" | "application/octet-stream" => ApiEncodingFormat::SSZ,
             _ => ApiEncodingFormat::JSON,
         }
     }
 }
 
+/// Returns the encoding and content type of the response for the given `Accept` header.
+///
+/// The first supported media type in the header is used, ignoring any parameters (e.g.,
+/// `q=0.9`). An empty header, `*/*` or `application/*` results in JSON. Returns a
+/// `NotAcceptable` error if none of the media types are supported.
+fn negotiate_encoding(accept_header: &str) -> Result<(ApiEncodingFormat, &'static str), ApiError> {
+    if accept_header.trim().is_empty() {
+        return Ok((ApiEncodingFormat::JSON, "application/json"));
+    }
+
+    accept_header
+        .split(',')
+        .map(|media_range| media_range.split(';').next().unwrap_or("").trim())
+        .find_map(|media_type| match media_type {
+            // Event streams are always served as `text/event-stream` by `sse_stream`.
+            "application/json" | "application/*" | "*/*" | "text/event-stream" => {
+                Some((ApiEncodingFormat::JSON, "application/json"))
+            }
+            "application/yaml" => Some((ApiEncodingFormat::YAML, "application/yaml")),
+            "application/ssz" => Some((ApiEncodingFormat::SSZ, "application/ssz")),
+            "application/octet-stream" => {
+                Some((ApiEncodingFormat::SSZ, "application/octet-stream"))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            ApiError::NotAcceptable(format!(
+                "None of the media types in the Accept header are supported: {:?}",
+                accept_header
+            ))
+        })
+}
+
 /// Provides a HTTP request handler with Lighthouse-specific functionality.
 pub struct Handler<T> {
     executor: TaskExecutor,
@@ -41,6 +74,7 @@ pub struct Handler<T> {
     body: Body,
     ctx: T,
     encoding: ApiEncodingFormat,
+    content_type: &'static str,
     allow_body: bool,
 }
 
@@ -62,13 +96,16 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
             })
             .map(String::from)?;
 
+        let (encoding, content_type) = negotiate_encoding(&accept_header)?;
+
         Ok(Self {
             executor,
             req,
             body,
             ctx,
             allow_body: false,
-            encoding: ApiEncodingFormat::from(accept_header.as_str()),
+            encoding,
+            content_type,
         })
     }
 
@@ -89,6 +126,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            content_type: self.content_type,
         })
     }
 
@@ -109,6 +147,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            content_type: self.content_type,
         })
     }
 
@@ -141,6 +180,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            content_type: self.content_type,
         })
     }
 
@@ -181,6 +221,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
 /// returned.
 pub struct HandledRequest<V> {
     encoding: ApiEncodingFormat,
+    content_type: &'static str,
     value: V,
 }
 
@@ -195,13 +236,23 @@ impl HandledRequest<String> {
     }
 }
 
+impl HandledRequest<StatusCode> {
+    /// Respond with the status code in `value` and an empty body.
+    pub fn status_only(self) -> ApiResult {
+        Response::builder()
+            .status(self.value)
+            .body(Body::empty())
+            .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+    }
+}
+
 impl<V: Serialize + Encode> HandledRequest<V> {
     /// Suitable for all items which implement `serde` and `ssz`.
     pub fn all_encodings(self) -> ApiResult {
         match self.encoding {
             ApiEncodingFormat::SSZ => Response::builder()
                 .status(StatusCode::OK)
-                .header("content-type", "application/ssz")
+                .header("content-type", self.content_type)
                 .body(Body::from(self.value.as_ssz_bytes()))
                 .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e))),
             _ => self.serde_encodings(),
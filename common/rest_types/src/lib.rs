@@ -11,12 +11,22 @@ mod validator;
 
 pub use api_error::{ApiError, ApiResult};
 pub use beacon::{
-    BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
-    ValidatorRequest, ValidatorResponse,
+    AttestationPoolKind, AttestationRewards, AttestationRewardsRequest, BlockAttestations,
+    BlockImportResult, BlockResponse, BlockRewards, BlockRootResponse, CanonicalHeadResponse,
+    Committee, CurrentSlotResponse, ExportedAttestation, FinalityCheckpoints, GenesisData,
+    HeadBeaconBlock, PoolSummary, RecentBlock, StateResponse, ValidatorBalance, ValidatorId,
+    ValidatorRequest, ValidatorResponse, ValidatorStatesRequest, ValidatorStatus, ValidatorsPage,
+};
+pub use consensus::{
+    EpochEffectiveness, IndividualVote, IndividualVotesRequest, IndividualVotesResponse,
+    ValidatorEffectiveness,
 };
-pub use consensus::{IndividualVote, IndividualVotesRequest, IndividualVotesResponse};
 pub use handler::{ApiEncodingFormat, Handler};
-pub use node::{Health, SyncingResponse, SyncingStatus};
+pub use node::{
+    Health, NodeIdentity, NodeLiveness, PeerCount, PeerData, PeerDirection, PeerState,
+    SyncingResponse, SyncingStatus, SYNC_TOLERANCE,
+};
 pub use validator::{
-    ValidatorDutiesRequest, ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription,
+    EpochValidatorDuty, ValidatorDutiesRequest, ValidatorDuty, ValidatorDutyBytes,
+    ValidatorLiveness, ValidatorLivenessRequest, ValidatorSubscription,
 };
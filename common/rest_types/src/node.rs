@@ -1,11 +1,28 @@
 //! Collection of types for the /node HTTP
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
+use std::fmt;
+use std::str::FromStr;
 use types::Slot;
 
 #[cfg(target_os = "linux")]
 use {procinfo::pid, psutil::process::Process};
 
+/// The number of slots which the head of a node may lag behind the current slot whilst the node is
+/// still considered to be synced.
+pub const SYNC_TOLERANCE: u64 = 4;
+
+/// The readiness of a node to serve validators, as reported by the `/node/liveness` endpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NodeLiveness {
+    /// The head of the node is within `SYNC_TOLERANCE` slots of the current slot.
+    Synced,
+    /// The head of the node is more than `SYNC_TOLERANCE` slots behind the current slot.
+    Syncing,
+    /// The node is unable to read its chain (e.g., because genesis has not yet occurred).
+    Unavailable,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
 /// The current syncing status of the node.
 pub struct SyncingStatus {
@@ -34,6 +51,104 @@ pub struct SyncingResponse {
     pub sync_status: SyncingStatus,
 }
 
+/// The identity of the node on the network, as reported by the `/node/identity` endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeIdentity {
+    /// The libp2p peer ID of the node.
+    pub peer_id: String,
+    /// The base64 encoded ENR of the node.
+    pub enr: String,
+    /// The multiaddrs on which libp2p is listening.
+    pub p2p_addresses: Vec<String>,
+    /// The UDP multiaddrs advertised for discovery by the ENR. Empty if the ENR does not advertise
+    /// a discovery address (e.g., if discovery is disabled).
+    pub discovery_addresses: Vec<String>,
+}
+
+/// The number of peers known to the node in each connection state, as reported by the
+/// `/node/peer_count` endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerCount {
+    pub connected: u64,
+    pub connecting: u64,
+    pub disconnected: u64,
+}
+
+/// The state of the connection between the node and a peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerState {
+    Connected,
+    Connecting,
+    Disconnected,
+}
+
+impl FromStr for PeerState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "connected" => Ok(PeerState::Connected),
+            "connecting" => Ok(PeerState::Connecting),
+            "disconnected" => Ok(PeerState::Disconnected),
+            other => Err(format!("Invalid peer state: {:?}", other)),
+        }
+    }
+}
+
+impl fmt::Display for PeerState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            PeerState::Connected => "connected",
+            PeerState::Connecting => "connecting",
+            PeerState::Disconnected => "disconnected",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Indicates which party initiated the connection between the node and a peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerDirection {
+    Inbound,
+    Outbound,
+}
+
+impl FromStr for PeerDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inbound" => Ok(PeerDirection::Inbound),
+            "outbound" => Ok(PeerDirection::Outbound),
+            other => Err(format!("Invalid peer direction: {:?}", other)),
+        }
+    }
+}
+
+impl fmt::Display for PeerDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            PeerDirection::Inbound => "inbound",
+            PeerDirection::Outbound => "outbound",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A peer known to the node, as reported by the `/node/peers` endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerData {
+    pub peer_id: String,
+    /// The most recently learned listening address of the peer, if any.
+    pub last_seen_p2p_address: Option<String>,
+    pub state: PeerState,
+    /// The direction of the connection, if it is known. The direction of disconnected peers is
+    /// not known.
+    pub direction: Option<PeerDirection>,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 /// Reports on the health of the Lighthouse instance.
 pub struct Health {
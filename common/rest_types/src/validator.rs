@@ -1,7 +1,7 @@
 use bls::{PublicKey, PublicKeyBytes};
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use types::{CommitteeIndex, Epoch, Slot};
+use types::{CommitteeIndex, Epoch, Hash256, Slot, SubnetId};
 
 /// A Validator duty with the validator public key represented a `PublicKeyBytes`.
 pub type ValidatorDutyBytes = ValidatorDutyBase<PublicKeyBytes>;
@@ -27,6 +27,11 @@ pub struct ValidatorDutyBase<T> {
     ///
     /// Should be set to `None` when duties are not yet known (before the current epoch).
     pub block_proposal_slots: Option<Vec<Slot>>,
+    /// The root of the block on which the `block_proposal_slots` depend, being the latest block
+    /// prior to the epoch. The proposal slots may change if this block is re-orged out.
+    ///
+    /// Set to `None` whenever `block_proposal_slots` is `None`.
+    pub block_proposal_dependent_root: Option<Hash256>,
     /// This provides the modulo: `max(1, len(committee) // TARGET_AGGREGATORS_PER_COMMITTEE)`
     /// which allows the validator client to determine if this duty requires the validator to be
     /// aggregate attestations.
@@ -34,7 +39,8 @@ pub struct ValidatorDutyBase<T> {
 }
 
 impl<T> ValidatorDutyBase<T> {
-    /// Return `true` if these validator duties are equal, ignoring their `block_proposal_slots`.
+    /// Return `true` if these validator duties are equal, ignoring their `block_proposal_slots`
+    /// and `block_proposal_dependent_root`.
     pub fn eq_ignoring_proposal_slots(&self, other: &Self) -> bool
     where
         T: PartialEq,
@@ -49,12 +55,38 @@ impl<T> ValidatorDutyBase<T> {
     }
 }
 
+/// The duties of a validator during some epoch, as returned by
+/// `/lighthouse/validator/duty_history`.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct EpochValidatorDuty {
+    pub epoch: Epoch,
+    pub duty: ValidatorDutyBytes,
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
 pub struct ValidatorDutiesRequest {
     pub epoch: Epoch,
     pub pubkeys: Vec<PublicKeyBytes>,
 }
 
+/// A request for the liveness of a set of validators during an epoch.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
+pub struct ValidatorLivenessRequest {
+    pub epoch: Epoch,
+    pub indices: Vec<u64>,
+}
+
+/// Indicates whether or not a validator was seen to be active during an epoch.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
+pub struct ValidatorLiveness {
+    /// The validator's index in `state.validators`.
+    pub index: u64,
+    pub epoch: Epoch,
+    /// True if the beacon node observed an attestation or block from the validator during
+    /// `epoch`.
+    pub is_live: bool,
+}
+
 /// A validator subscription, created when a validator subscribes to a slot to perform optional aggregation
 /// duties.
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
@@ -71,6 +103,10 @@ pub struct ValidatorSubscription {
     /// If true, the validator is an aggregator and the beacon node should aggregate attestations
     /// for this slot.
     pub is_aggregator: bool,
+    /// The subnet of the committee, if already computed by the validator client. The beacon node
+    /// rejects the subscription if this is not the subnet of the committee.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subnet_id: Option<SubnetId>,
 }
 
 #[cfg(test)]
@@ -90,10 +126,12 @@ mod test {
             attestation_committee_position: Some(6),
             committee_count_at_slot: Some(4),
             block_proposal_slots: None,
+            block_proposal_dependent_root: None,
             aggregator_modulo: Some(99),
         };
         let duty2 = ValidatorDuty {
             block_proposal_slots: Some(vec![Slot::new(42), Slot::new(45)]),
+            block_proposal_dependent_root: Some(Hash256::repeat_byte(1)),
             ..duty1.clone()
         };
         assert_ne!(duty1, duty2);
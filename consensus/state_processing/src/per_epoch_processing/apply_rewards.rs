@@ -30,6 +30,26 @@ impl Delta {
         self.reward(other.rewards)?;
         self.penalize(other.penalties)
     }
+
+    /// The total reward of the validator.
+    pub fn rewards(&self) -> u64 {
+        self.rewards
+    }
+
+    /// The total penalty of the validator.
+    pub fn penalties(&self) -> u64 {
+        self.penalties
+    }
+}
+
+/// The changes to a validators balance for each component of its attestation.
+#[derive(Default, Clone)]
+pub struct AttestationComponentDeltas {
+    pub source: Delta,
+    pub target: Delta,
+    pub head: Delta,
+    pub inclusion_delay: Delta,
+    pub inactivity_penalty: Delta,
 }
 
 /// Apply attester and proposer rewards.
@@ -71,39 +91,16 @@ fn get_attestation_deltas<T: EthSpec>(
     validator_statuses: &ValidatorStatuses,
     spec: &ChainSpec,
 ) -> Result<Vec<Delta>, Error> {
-    let finality_delay = (state.previous_epoch() - state.finalized_checkpoint.epoch).as_u64();
-
     let mut deltas = vec![Delta::default(); state.validators.len()];
 
-    let total_balances = &validator_statuses.total_balances;
-
-    // Filter out ineligible validators. All sub-functions of the spec do this except for
-    // `get_inclusion_delay_deltas`. It's safe to do so here because any validator that is in the
-    // unslashed indices of the matching source attestations is active, and therefore eligible.
-    for (index, validator) in validator_statuses
-        .statuses
-        .iter()
-        .enumerate()
-        .filter(|(_, validator)| is_eligible_validator(validator))
+    for (index, components, proposer_delta) in
+        get_eligible_component_deltas(state, validator_statuses, spec)?
     {
-        let base_reward = get_base_reward(state, index, total_balances.current_epoch(), spec)?;
-
-        let source_delta =
-            get_source_delta(validator, base_reward, total_balances, finality_delay, spec)?;
-        let target_delta =
-            get_target_delta(validator, base_reward, total_balances, finality_delay, spec)?;
-        let head_delta =
-            get_head_delta(validator, base_reward, total_balances, finality_delay, spec)?;
-        let (inclusion_delay_delta, proposer_delta) =
-            get_inclusion_delay_delta(validator, base_reward, spec)?;
-        let inactivity_penalty_delta =
-            get_inactivity_penalty_delta(validator, base_reward, finality_delay, spec)?;
-
-        deltas[index].combine(source_delta)?;
-        deltas[index].combine(target_delta)?;
-        deltas[index].combine(head_delta)?;
-        deltas[index].combine(inclusion_delay_delta)?;
-        deltas[index].combine(inactivity_penalty_delta)?;
+        deltas[index].combine(components.source)?;
+        deltas[index].combine(components.target)?;
+        deltas[index].combine(components.head)?;
+        deltas[index].combine(components.inclusion_delay)?;
+        deltas[index].combine(components.inactivity_penalty)?;
 
         if let Some((proposer_index, proposer_delta)) = proposer_delta {
             if proposer_index >= deltas.len() {
@@ -117,6 +114,80 @@ fn get_attestation_deltas<T: EthSpec>(
     Ok(deltas)
 }
 
+/// Returns the changes to each validators balance for each component of its attestation during
+/// the previous epoch, as applied by `process_rewards_and_penalties`.
+///
+/// The rewards of proposers for including attestations are not included.
+pub fn get_attestation_component_deltas<T: EthSpec>(
+    state: &BeaconState<T>,
+    validator_statuses: &ValidatorStatuses,
+    spec: &ChainSpec,
+) -> Result<Vec<AttestationComponentDeltas>, Error> {
+    let mut deltas = vec![AttestationComponentDeltas::default(); state.validators.len()];
+
+    for (index, components, _) in get_eligible_component_deltas(state, validator_statuses, spec)? {
+        deltas[index] = components;
+    }
+
+    Ok(deltas)
+}
+
+/// Returns the index and `AttestationComponentDeltas` of each eligible validator, along with the
+/// index and delta of the proposer which included its attestation.
+#[allow(clippy::type_complexity)]
+fn get_eligible_component_deltas<T: EthSpec>(
+    state: &BeaconState<T>,
+    validator_statuses: &ValidatorStatuses,
+    spec: &ChainSpec,
+) -> Result<Vec<(usize, AttestationComponentDeltas, Option<(usize, Delta)>)>, Error> {
+    let finality_delay = (state.previous_epoch() - state.finalized_checkpoint.epoch).as_u64();
+
+    let total_balances = &validator_statuses.total_balances;
+
+    // Filter out ineligible validators. All sub-functions of the spec do this except for
+    // `get_inclusion_delay_deltas`. It's safe to do so here because any validator that is in the
+    // unslashed indices of the matching source attestations is active, and therefore eligible.
+    validator_statuses
+        .statuses
+        .iter()
+        .enumerate()
+        .filter(|(_, validator)| is_eligible_validator(validator))
+        .map(|(index, validator)| -> Result<_, Error> {
+            let base_reward = get_base_reward(state, index, total_balances.current_epoch(), spec)?;
+
+            let (inclusion_delay, proposer_delta) =
+                get_inclusion_delay_delta(validator, base_reward, spec)?;
+
+            let components = AttestationComponentDeltas {
+                source: get_source_delta(
+                    validator,
+                    base_reward,
+                    total_balances,
+                    finality_delay,
+                    spec,
+                )?,
+                target: get_target_delta(
+                    validator,
+                    base_reward,
+                    total_balances,
+                    finality_delay,
+                    spec,
+                )?,
+                head: get_head_delta(validator, base_reward, total_balances, finality_delay, spec)?,
+                inclusion_delay,
+                inactivity_penalty: get_inactivity_penalty_delta(
+                    validator,
+                    base_reward,
+                    finality_delay,
+                    spec,
+                )?,
+            };
+
+            Ok((index, components, proposer_delta))
+        })
+        .collect()
+}
+
 fn get_attestation_component_delta(
     index_in_unslashed_attesting_indices: bool,
     attesting_balance: u64,
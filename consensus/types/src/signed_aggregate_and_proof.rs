@@ -1,9 +1,11 @@
 use super::{
-    AggregateAndProof, Attestation, ChainSpec, Domain, EthSpec, Fork, Hash256, PublicKey,
-    SecretKey, SelectionProof, Signature, SignedRoot,
+    AggregateAndProof, AggregateSignature, Attestation, AttestationData, BitList, ChainSpec,
+    Domain, EthSpec, Fork, Hash256, PublicKey, SecretKey, SelectionProof, Signature, SignedRoot,
+    Unsigned,
 };
 use crate::test_utils::TestRandom;
 use serde_derive::{Deserialize, Serialize};
+use ssz::Encode;
 use ssz_derive::{Decode, Encode};
 use test_random_derive::TestRandom;
 use tree_hash_derive::TreeHash;
@@ -61,6 +63,37 @@ impl<T: EthSpec> SignedAggregateAndProof<T> {
         }
     }
 
+    /// Return an aggregate with every bit set for a committee of the max possible size.
+    pub fn full() -> Self {
+        let committee_size = T::MaxValidatorsPerCommittee::to_usize();
+        let mut aggregation_bits = BitList::with_capacity(committee_size)
+            .expect("max committee size should be within the bitlist capacity");
+        for i in 0..committee_size {
+            aggregation_bits
+                .set(i, true)
+                .expect("index should be within the max committee size");
+        }
+
+        SignedAggregateAndProof {
+            message: AggregateAndProof {
+                aggregator_index: 0,
+                aggregate: Attestation {
+                    aggregation_bits,
+                    data: AttestationData::default(),
+                    signature: AggregateSignature::empty(),
+                },
+                selection_proof: Signature::empty(),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    /// Returns the SSZ-encoded length of the largest possible `SignedAggregateAndProof`, for
+    /// comparison against the size limits of the network.
+    pub fn max_ssz_len() -> usize {
+        Self::full().ssz_bytes_len()
+    }
+
     /// Verifies the signature of the `AggregateAndProof`
     pub fn is_valid_signature(
         &self,
@@ -80,6 +113,19 @@ impl<T: EthSpec> SignedAggregateAndProof<T> {
         self.signature.verify(validator_pubkey, message)
     }
 
+    /// Verifies only the selection_proof in the contained `AggregateAndProof`, for callers which
+    /// have already verified the signature of the `AggregateAndProof`.
+    pub fn verify_selection_proof_only(
+        &self,
+        validator_pubkey: &PublicKey,
+        fork: &Fork,
+        genesis_validators_root: Hash256,
+        spec: &ChainSpec,
+    ) -> bool {
+        self.message
+            .is_valid_selection_proof(validator_pubkey, fork, genesis_validators_root, spec)
+    }
+
     /// Verifies the signature of the `AggregateAndProof` as well the underlying selection_proof in
     /// the contained `AggregateAndProof`.
     pub fn is_valid(
@@ -98,3 +144,126 @@ impl<T: EthSpec> SignedAggregateAndProof<T> {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generate_deterministic_keypair, SeedableRng, XorShiftRng};
+    use crate::{Epoch, Keypair, MainnetEthSpec, Slot};
+
+    type E = MainnetEthSpec;
+
+    /// The inputs shared by the tests, signing as validator `0` at genesis.
+    struct Fixture {
+        rng: XorShiftRng,
+        spec: ChainSpec,
+        keypair: Keypair,
+        genesis_validators_root: Hash256,
+        genesis_fork: Fork,
+    }
+
+    fn fixture() -> Fixture {
+        Fixture {
+            rng: XorShiftRng::from_seed([42; 16]),
+            spec: E::default_spec(),
+            keypair: generate_deterministic_keypair(0),
+            genesis_validators_root: Hash256::zero(),
+            genesis_fork: Fork {
+                previous_version: [0; 4],
+                current_version: [0; 4],
+                epoch: Epoch::new(0),
+            },
+        }
+    }
+
+    #[test]
+    fn signature_is_only_valid_for_the_signing_fork() {
+        let Fixture {
+            mut rng,
+            spec,
+            keypair,
+            genesis_validators_root,
+            genesis_fork: pre_fork,
+        } = fixture();
+
+        let post_fork = Fork {
+            previous_version: pre_fork.current_version,
+            current_version: [1; 4],
+            epoch: Epoch::new(1),
+        };
+
+        let mut aggregate = Attestation::<E>::random_for_test(&mut rng);
+        aggregate.data.slot = Slot::new(2 * E::slots_per_epoch());
+
+        let signed = SignedAggregateAndProof::from_aggregate(
+            0,
+            aggregate,
+            None,
+            &keypair.sk,
+            &post_fork,
+            genesis_validators_root,
+            &spec,
+        );
+
+        assert!(signed.is_valid_signature(&keypair.pk, &post_fork, genesis_validators_root, &spec));
+        assert!(!signed.is_valid_signature(&keypair.pk, &pre_fork, genesis_validators_root, &spec));
+        assert!(signed.is_valid(&keypair.pk, &post_fork, genesis_validators_root, &spec));
+        assert!(!signed.is_valid(&keypair.pk, &pre_fork, genesis_validators_root, &spec));
+    }
+
+    #[test]
+    fn selection_proof_is_verified_independently_of_the_signature() {
+        let Fixture {
+            mut rng,
+            spec,
+            keypair,
+            genesis_validators_root,
+            genesis_fork: fork,
+        } = fixture();
+        let other_keypair = generate_deterministic_keypair(1);
+
+        let aggregate = Attestation::<E>::random_for_test(&mut rng);
+
+        let mut signed = SignedAggregateAndProof::from_aggregate(
+            0,
+            aggregate,
+            None,
+            &keypair.sk,
+            &fork,
+            genesis_validators_root,
+            &spec,
+        );
+        assert!(signed.verify_selection_proof_only(
+            &keypair.pk,
+            &fork,
+            genesis_validators_root,
+            &spec
+        ));
+
+        // An invalid outer signature does not affect the selection proof.
+        signed.signature = Signature::empty();
+        assert!(!signed.is_valid(&keypair.pk, &fork, genesis_validators_root, &spec));
+        assert!(signed.verify_selection_proof_only(
+            &keypair.pk,
+            &fork,
+            genesis_validators_root,
+            &spec
+        ));
+
+        // A selection proof from another validator is invalid.
+        signed.message.selection_proof = SelectionProof::new::<E>(
+            signed.message.aggregate.data.slot,
+            &other_keypair.sk,
+            &fork,
+            genesis_validators_root,
+            &spec,
+        )
+        .into();
+        assert!(!signed.verify_selection_proof_only(
+            &keypair.pk,
+            &fork,
+            genesis_validators_root,
+            &spec
+        ));
+    }
+}
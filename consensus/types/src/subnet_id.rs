@@ -2,6 +2,7 @@
 use crate::{AttestationData, ChainSpec, CommitteeIndex, EthSpec, Slot};
 use safe_arith::{ArithError, SafeArith};
 use serde_derive::{Deserialize, Serialize};
+use ssz::{Decode, DecodeError, Encode};
 use std::ops::{Deref, DerefMut};
 
 #[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
@@ -74,3 +75,35 @@ impl Into<u64> for SubnetId {
         self.0
     }
 }
+
+impl Encode for SubnetId {
+    fn is_ssz_fixed_len() -> bool {
+        <u64 as Encode>::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        <u64 as Encode>::ssz_fixed_len()
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        self.0.ssz_bytes_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.0.ssz_append(buf)
+    }
+}
+
+impl Decode for SubnetId {
+    fn is_ssz_fixed_len() -> bool {
+        <u64 as Decode>::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        <u64 as Decode>::ssz_fixed_len()
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        u64::from_ssz_bytes(bytes).map(Self)
+    }
+}
@@ -387,3 +387,19 @@ fn invalid_prev_surrounds_new_multiple() {
     }
     .run()
 }
+
+#[test]
+fn invalid_genesis_validators_root_mismatch() {
+    let other_root = Hash256::from_low_u64_be(1);
+    StreamTest {
+        cases: vec![
+            Test::single(attestation_data_builder(0, 1)),
+            Test::single(attestation_data_builder(1, 2))
+                .with_genesis_validators_root(other_root)
+                .expect_genesis_validators_root_mismatch(DEFAULT_GENESIS_VALIDATORS_ROOT),
+            Test::single(attestation_data_builder(1, 2)),
+        ],
+        ..StreamTest::default()
+    }
+    .run()
+}
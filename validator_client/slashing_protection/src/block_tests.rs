@@ -122,3 +122,30 @@ fn invalid_unregistered_validator() {
     }
     .run()
 }
+
+#[test]
+fn invalid_genesis_validators_root_mismatch() {
+    let other_root = Hash256::from_low_u64_be(1);
+    StreamTest {
+        cases: vec![
+            Test::single(block(1)),
+            Test::single(block(2))
+                .with_genesis_validators_root(other_root)
+                .expect_genesis_validators_root_mismatch(DEFAULT_GENESIS_VALIDATORS_ROOT),
+            Test::single(block(2)),
+        ],
+        ..StreamTest::default()
+    }
+    .run()
+}
+
+#[test]
+fn double_block_proposal_display() {
+    let error = NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(SignedBlock::new(
+        Slot::new(12),
+        Hash256::zero(),
+    )));
+    assert!(error
+        .to_string()
+        .starts_with("slashable block: double block proposal at slot 12"));
+}
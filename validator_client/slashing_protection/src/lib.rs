@@ -10,8 +10,8 @@ pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::SlashingDatabase;
 use rusqlite::Error as SQLError;
+use std::fmt;
 use std::io::{Error as IOError, ErrorKind};
-use std::string::ToString;
 use types::{Hash256, PublicKey};
 
 /// The attestation or block is not safe to sign.
@@ -22,6 +22,11 @@ pub enum NotSafe {
     UnregisteredValidator(PublicKey),
     InvalidBlock(InvalidBlock),
     InvalidAttestation(InvalidAttestation),
+    /// The message was signed for a different network to the one recorded in the database.
+    GenesisValidatorsRootMismatch {
+        stored: Hash256,
+        provided: Hash256,
+    },
     IOError(ErrorKind),
     SQLError(String),
     SQLPoolError(String),
@@ -70,8 +75,20 @@ impl From<r2d2::Error> for NotSafe {
     }
 }
 
-impl ToString for NotSafe {
-    fn to_string(&self) -> String {
-        format!("{:?}", self)
+impl fmt::Display for NotSafe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotSafe::UnregisteredValidator(pk) => write!(f, "unregistered validator {:?}", pk),
+            NotSafe::InvalidBlock(e) => write!(f, "slashable block: {}", e),
+            NotSafe::InvalidAttestation(e) => write!(f, "slashable attestation: {}", e),
+            NotSafe::GenesisValidatorsRootMismatch { stored, provided } => write!(
+                f,
+                "genesis validators root {:?} does not match the stored root {:?}",
+                provided, stored
+            ),
+            NotSafe::IOError(kind) => write!(f, "IO error: {:?}", kind),
+            NotSafe::SQLError(e) => write!(f, "SQL error: {}", e),
+            NotSafe::SQLPoolError(e) => write!(f, "SQL pool error: {}", e),
+        }
     }
 }
@@ -22,7 +22,14 @@ fn block_same_slot() {
     let num_blocks = 10;
     let results = (0..num_blocks)
         .into_par_iter()
-        .map(|_| slashing_db.check_and_insert_block_proposal(&pk, &block(1), DEFAULT_DOMAIN))
+        .map(|_| {
+            slashing_db.check_and_insert_block_proposal(
+                &pk,
+                &block(1),
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT,
+            )
+        })
         .collect::<Vec<_>>();
 
     let num_successes = results.iter().filter(|res| res.is_ok()).count();
@@ -48,6 +55,7 @@ fn attestation_same_target() {
                 &pk,
                 &attestation_data_builder(i, num_attestations),
                 DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT,
             )
         })
         .collect::<Vec<_>>();
@@ -73,10 +81,96 @@ fn attestation_surround_fest() {
         .into_par_iter()
         .map(|i| {
             let att = attestation_data_builder(i, 2 * num_attestations - i);
-            slashing_db.check_and_insert_attestation(&pk, &att, DEFAULT_DOMAIN)
+            slashing_db.check_and_insert_attestation(
+                &pk,
+                &att,
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let num_successes = results.iter().filter(|res| res.is_ok()).count();
+    assert_eq!(num_successes, 1);
+}
+
+#[test]
+fn block_same_slot_in_memory() {
+    let slashing_db = SlashingDatabase::create_in_memory().unwrap();
+
+    let pk = pubkey(0);
+
+    slashing_db.register_validator(&pk).unwrap();
+
+    // A stream of blocks all with the same slot, signed via clones of the same database.
+    let num_blocks = 10;
+    let results = (0..num_blocks)
+        .into_par_iter()
+        .map(|_| {
+            slashing_db.clone().check_and_insert_block_proposal(
+                &pk,
+                &block(1),
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let num_successes = results.iter().filter(|res| res.is_ok()).count();
+    assert_eq!(num_successes, 1);
+}
+
+#[test]
+fn block_same_slot_stress() {
+    let slashing_db = SlashingDatabase::create_in_memory().unwrap();
+
+    let pk = pubkey(0);
+
+    slashing_db.register_validator(&pk).unwrap();
+
+    // Many distinct blocks at the same slot, racing for the lock of a single validator.
+    let num_blocks = 200;
+    let results = (0..num_blocks)
+        .into_par_iter()
+        .map(|_| {
+            slashing_db.check_and_insert_block_proposal(
+                &pk,
+                &block(1),
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT,
+            )
         })
         .collect::<Vec<_>>();
 
     let num_successes = results.iter().filter(|res| res.is_ok()).count();
     assert_eq!(num_successes, 1);
+    assert!(results.iter().all(|res| match res {
+        Ok(Safe::Valid) | Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(_))) => true,
+        _ => false,
+    }));
+}
+
+#[test]
+fn block_distinct_validators() {
+    let slashing_db = SlashingDatabase::create_in_memory().unwrap();
+
+    let num_validators = 10;
+    let pubkeys = (0..num_validators).map(pubkey).collect::<Vec<_>>();
+
+    slashing_db.register_validators(pubkeys.iter()).unwrap();
+
+    // Each validator signs a block at the same slot, which is safe for all of them.
+    let results = pubkeys
+        .par_iter()
+        .map(|pk| {
+            slashing_db.check_and_insert_block_proposal(
+                pk,
+                &block(1),
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert!(results.iter().all(|res| *res == Ok(Safe::Valid)));
 }
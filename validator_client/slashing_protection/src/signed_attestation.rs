@@ -1,4 +1,5 @@
 use crate::hash256_from_row;
+use std::fmt;
 use types::{AttestationData, Epoch, Hash256, SignedRoot};
 
 /// An attestation that has previously been signed.
@@ -20,6 +21,59 @@ pub enum InvalidAttestation {
     PrevSurroundsNew { prev: SignedAttestation },
     /// The attestation is invalid because its source epoch is greater than its target epoch.
     SourceExceedsTarget,
+    /// The attestation's source epoch is less than that of an attestation pruned from the
+    /// database.
+    SourceViolatesLowerBound {
+        source_epoch: Epoch,
+        bound_epoch: Epoch,
+    },
+    /// The attestation's target epoch is not greater than that of an attestation pruned from the
+    /// database.
+    TargetViolatesLowerBound {
+        target_epoch: Epoch,
+        bound_epoch: Epoch,
+    },
+}
+
+impl fmt::Display for InvalidAttestation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidAttestation::DoubleVote(prev) => write!(
+                f,
+                "double vote for target epoch {} (previous source epoch {})",
+                prev.target_epoch, prev.source_epoch
+            ),
+            InvalidAttestation::NewSurroundsPrev { prev } => write!(
+                f,
+                "surrounds the previous attestation from epoch {} to {}",
+                prev.source_epoch, prev.target_epoch
+            ),
+            InvalidAttestation::PrevSurroundsNew { prev } => write!(
+                f,
+                "surrounded by the previous attestation from epoch {} to {}",
+                prev.source_epoch, prev.target_epoch
+            ),
+            InvalidAttestation::SourceExceedsTarget => {
+                write!(f, "source epoch is greater than target epoch")
+            }
+            InvalidAttestation::SourceViolatesLowerBound {
+                source_epoch,
+                bound_epoch,
+            } => write!(
+                f,
+                "source epoch {} is less than the pruned source epoch {}",
+                source_epoch, bound_epoch
+            ),
+            InvalidAttestation::TargetViolatesLowerBound {
+                target_epoch,
+                bound_epoch,
+            } => write!(
+                f,
+                "target epoch {} is not greater than the pruned target epoch {}",
+                target_epoch, bound_epoch
+            ),
+        }
+    }
 }
 
 impl SignedAttestation {
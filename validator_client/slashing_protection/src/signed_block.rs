@@ -1,4 +1,5 @@
 use crate::hash256_from_row;
+use std::fmt;
 use types::{BeaconBlockHeader, Hash256, SignedRoot, Slot};
 
 /// A block that has previously been signed.
@@ -12,6 +13,31 @@ pub struct SignedBlock {
 #[derive(PartialEq, Debug)]
 pub enum InvalidBlock {
     DoubleBlockProposal(SignedBlock),
+    /// The block's slot is not greater than that of a block pruned from the database.
+    SlotViolatesLowerBound {
+        block_slot: Slot,
+        bound_slot: Slot,
+    },
+}
+
+impl fmt::Display for InvalidBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidBlock::DoubleBlockProposal(prev) => write!(
+                f,
+                "double block proposal at slot {} (previous signing root {:?})",
+                prev.slot, prev.signing_root
+            ),
+            InvalidBlock::SlotViolatesLowerBound {
+                block_slot,
+                bound_slot,
+            } => write!(
+                f,
+                "slot {} is not greater than the pruned slot {}",
+                block_slot, bound_slot
+            ),
+        }
+    }
 }
 
 impl SignedBlock {
@@ -1,14 +1,21 @@
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
-use crate::{NotSafe, Safe, SignedAttestation, SignedBlock};
+use crate::{hash256_from_row, NotSafe, Safe, SignedAttestation, SignedBlock};
+use parking_lot::Mutex;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::path::Path;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
-use types::{AttestationData, BeaconBlockHeader, Hash256, PublicKey, SignedRoot};
+use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKey, SignedRoot, Slot};
 
 type Pool = r2d2::Pool<SqliteConnectionManager>;
+/// A lock per validator, shared by all clones of a `SlashingDatabase`.
+type ValidatorLocks = Arc<Mutex<HashMap<PublicKey, Arc<Mutex<()>>>>>;
 
 /// We set the pool size to 1 for compatibility with locking_mode=EXCLUSIVE.
 ///
@@ -23,6 +30,11 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_millis(100);
 #[derive(Debug, Clone)]
 pub struct SlashingDatabase {
     conn_pool: Pool,
+    validator_locks: ValidatorLocks,
+    /// The number of exclusive transactions committed by `check_and_insert_*`, shared by all
+    /// clones.
+    #[cfg(test)]
+    write_count: Arc<AtomicUsize>,
 }
 
 impl SlashingDatabase {
@@ -47,8 +59,34 @@ impl SlashingDatabase {
 
         Self::set_db_file_permissions(&file)?;
         let conn_pool = Self::open_conn_pool(path)?;
-        let conn = conn_pool.get()?;
+        Self::create_tables(&conn_pool.get()?)?;
+
+        Ok(Self::from_conn_pool(conn_pool))
+    }
+
+    /// Create a slashing database held in memory rather than on disk.
+    ///
+    /// The database is shared by all clones of the returned value, and lost once they are all
+    /// dropped. Useful for tests which should not touch the filesystem.
+    pub fn create_in_memory() -> Result<Self, NotSafe> {
+        let manager = SqliteConnectionManager::memory().with_init(Self::apply_pragmas);
+        // Each connection to an in-memory database opens a distinct database, so the pool must
+        // hold exactly one connection and never close it.
+        let conn_pool = Pool::builder()
+            .max_size(1)
+            .min_idle(Some(1))
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connection_timeout(CONNECTION_TIMEOUT)
+            .build(manager)
+            .map_err(|e| NotSafe::SQLError(format!("Unable to open database: {:?}", e)))?;
+        Self::create_tables(&conn_pool.get()?)?;
 
+        Ok(Self::from_conn_pool(conn_pool))
+    }
+
+    /// Create the tables of a new slashing database.
+    fn create_tables(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
         conn.execute(
             "CREATE TABLE validators (
                 id INTEGER PRIMARY KEY,
@@ -80,13 +118,43 @@ impl SlashingDatabase {
             params![],
         )?;
 
-        Ok(Self { conn_pool })
+        Self::create_metadata_table(conn)?;
+        Self::create_lower_bound_tables(conn)
     }
 
-    /// Open an existing `SlashingDatabase` from disk.
+    /// Create any tables which were introduced after the given database was created.
+    fn migrate(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
+        Self::create_metadata_table(conn)?;
+        Self::create_lower_bound_tables(conn)
+    }
+
+    /// Open an existing `SlashingDatabase` from disk, migrating it to the latest schema.
+    ///
+    /// Error if the database is in use by another `SlashingDatabase`.
     pub fn open(path: &Path) -> Result<Self, NotSafe> {
         let conn_pool = Self::open_conn_pool(&path)?;
-        Ok(Self { conn_pool })
+        Self::migrate(&conn_pool.get()?)?;
+        Ok(Self::from_conn_pool(conn_pool))
+    }
+
+    fn from_conn_pool(conn_pool: Pool) -> Self {
+        Self {
+            conn_pool,
+            validator_locks: <_>::default(),
+            #[cfg(test)]
+            write_count: <_>::default(),
+        }
+    }
+
+    /// Get the lock which serialises the check-and-insert of messages signed by `validator_pubkey`.
+    ///
+    /// To avoid deadlock, the lock must be taken before a connection from the pool, never after.
+    fn validator_lock(&self, validator_pubkey: &PublicKey) -> Arc<Mutex<()>> {
+        self.validator_locks
+            .lock()
+            .entry(validator_pubkey.clone())
+            .or_default()
+            .clone()
     }
 
     /// Open a new connection pool with all of the necessary settings and tweaks.
@@ -156,6 +224,80 @@ impl SlashingDatabase {
         Ok(())
     }
 
+    /// Create the table which records the `genesis_validators_root` of the network that the
+    /// database's signatures belong to.
+    ///
+    /// Databases created prior to the introduction of this table have it created upon being opened.
+    fn create_metadata_table(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS metadata (
+                id INTEGER PRIMARY KEY CHECK (id = 0),
+                genesis_validators_root BLOB NOT NULL
+            )",
+            params![],
+        )?;
+        Ok(())
+    }
+
+    /// Create the tables which record, for each validator, the greatest block slot and attestation
+    /// epochs that have been pruned from the database.
+    ///
+    /// Databases created prior to the introduction of pruning have them created upon being opened.
+    fn create_lower_bound_tables(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS block_lower_bounds (
+                validator_id INTEGER PRIMARY KEY,
+                slot INTEGER NOT NULL,
+                FOREIGN KEY(validator_id) REFERENCES validators(id)
+            )",
+            params![],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS attestation_lower_bounds (
+                validator_id INTEGER PRIMARY KEY,
+                source_epoch INTEGER NOT NULL,
+                target_epoch INTEGER NOT NULL,
+                FOREIGN KEY(validator_id) REFERENCES validators(id)
+            )",
+            params![],
+        )?;
+        Ok(())
+    }
+
+    /// Check that `genesis_validators_root` matches the root stored in the database.
+    ///
+    /// If no root has been stored yet, `genesis_validators_root` is stored, such that all
+    /// subsequent messages must be signed for the same network.
+    fn check_genesis_validators_root(
+        txn: &Transaction,
+        genesis_validators_root: Hash256,
+    ) -> Result<(), NotSafe> {
+        let stored = txn
+            .query_row(
+                "SELECT genesis_validators_root FROM metadata WHERE id = 0",
+                params![],
+                |row| hash256_from_row(0, row),
+            )
+            .optional()?;
+
+        match stored {
+            Some(stored) if stored != genesis_validators_root => {
+                Err(NotSafe::GenesisValidatorsRootMismatch {
+                    stored,
+                    provided: genesis_validators_root,
+                })
+            }
+            Some(_) => Ok(()),
+            None => {
+                txn.execute(
+                    "INSERT INTO metadata (id, genesis_validators_root) VALUES (0, ?1)",
+                    params![genesis_validators_root.as_bytes()],
+                )?;
+                Ok(())
+            }
+        }
+    }
+
     /// Get the database-internal ID for a validator.
     ///
     /// This is NOT the same as a validator index, and depends on the ordering that validators
@@ -170,6 +312,87 @@ impl SlashingDatabase {
         .ok_or_else(|| NotSafe::UnregisteredValidator(public_key.clone()))
     }
 
+    /// Check whether `genesis_validators_root` matches the root stored in the database, without
+    /// storing it if none has been stored yet.
+    fn genesis_validators_root_matches(
+        txn: &Transaction,
+        genesis_validators_root: Hash256,
+    ) -> Result<bool, NotSafe> {
+        let stored = txn
+            .query_row(
+                "SELECT genesis_validators_root FROM metadata WHERE id = 0",
+                params![],
+                |row| hash256_from_row(0, row),
+            )
+            .optional()?;
+        Ok(stored == Some(genesis_validators_root))
+    }
+
+    /// Check whether `block_header` is identical to a block already signed by `validator_pubkey`
+    /// for the network with `genesis_validators_root`.
+    ///
+    /// This only reads from the database.
+    fn is_duplicate_block_proposal(
+        txn: &Transaction,
+        validator_pubkey: &PublicKey,
+        block_header: &BeaconBlockHeader,
+        domain: Hash256,
+        genesis_validators_root: Hash256,
+    ) -> Result<bool, NotSafe> {
+        if !Self::genesis_validators_root_matches(txn, genesis_validators_root)? {
+            return Ok(false);
+        }
+
+        let validator_id = Self::get_validator_id(txn, validator_pubkey)?;
+        let signing_root = txn
+            .prepare(
+                "SELECT signing_root
+                 FROM signed_blocks
+                 WHERE validator_id = ?1 AND slot = ?2",
+            )?
+            .query_row(params![validator_id, block_header.slot], |row| {
+                hash256_from_row(0, row)
+            })
+            .optional()?;
+
+        Ok(signing_root == Some(block_header.signing_root(domain)))
+    }
+
+    /// Check whether `attestation` is identical to an attestation already signed by
+    /// `validator_pubkey` for the network with `genesis_validators_root`.
+    ///
+    /// This only reads from the database.
+    fn is_duplicate_attestation(
+        txn: &Transaction,
+        validator_pubkey: &PublicKey,
+        attestation: &AttestationData,
+        domain: Hash256,
+        genesis_validators_root: Hash256,
+    ) -> Result<bool, NotSafe> {
+        if !Self::genesis_validators_root_matches(txn, genesis_validators_root)? {
+            return Ok(false);
+        }
+
+        let validator_id = Self::get_validator_id(txn, validator_pubkey)?;
+        let signing_root = txn
+            .prepare(
+                "SELECT signing_root
+                 FROM signed_attestations
+                 WHERE validator_id = ?1 AND source_epoch = ?2 AND target_epoch = ?3",
+            )?
+            .query_row(
+                params![
+                    validator_id,
+                    attestation.source.epoch,
+                    attestation.target.epoch
+                ],
+                |row| hash256_from_row(0, row),
+            )
+            .optional()?;
+
+        Ok(signing_root == Some(attestation.signing_root(domain)))
+    }
+
     /// Check a block proposal from `validator_pubkey` for slash safety.
     fn check_block_proposal(
         &self,
@@ -195,15 +418,29 @@ impl SlashingDatabase {
         if let Some(existing_block) = existing_block {
             if existing_block.signing_root == block_header.signing_root(domain) {
                 // Same slot and same hash -> we're re-broadcasting a previously signed block
-                Ok(Safe::SameData)
+                return Ok(Safe::SameData);
             } else {
                 // Same epoch but not the same hash -> it's a DoubleBlockProposal
-                Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(
+                return Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(
                     existing_block,
-                )))
+                )));
             }
-        } else {
-            Ok(Safe::Valid)
+        }
+
+        // Blocks at or prior to the slot of a pruned block can't be checked, so are refused.
+        let bound_slot = txn
+            .prepare("SELECT slot FROM block_lower_bounds WHERE validator_id = ?1")?
+            .query_row(params![validator_id], |row| row.get(0))
+            .optional()?;
+
+        match bound_slot {
+            Some(bound_slot) if block_header.slot <= bound_slot => Err(NotSafe::InvalidBlock(
+                InvalidBlock::SlotViolatesLowerBound {
+                    block_slot: block_header.slot,
+                    bound_slot,
+                },
+            )),
+            _ => Ok(Safe::Valid),
         }
     }
 
@@ -255,7 +492,38 @@ impl SlashingDatabase {
             }
         }
 
-        // 2. Check that no previous vote is surrounding `attestation`.
+        // 2. Check that `attestation` can't be slashable with respect to a pruned attestation.
+        let bound = txn
+            .prepare(
+                "SELECT source_epoch, target_epoch
+                 FROM attestation_lower_bounds
+                 WHERE validator_id = ?1",
+            )?
+            .query_row(params![validator_id], |row| {
+                Ok((row.get::<_, Epoch>(0)?, row.get::<_, Epoch>(1)?))
+            })
+            .optional()?;
+
+        if let Some((bound_source_epoch, bound_target_epoch)) = bound {
+            if att_source_epoch < bound_source_epoch {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::SourceViolatesLowerBound {
+                        source_epoch: att_source_epoch,
+                        bound_epoch: bound_source_epoch,
+                    },
+                ));
+            }
+            if att_target_epoch <= bound_target_epoch {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::TargetViolatesLowerBound {
+                        target_epoch: att_target_epoch,
+                        bound_epoch: bound_target_epoch,
+                    },
+                ));
+            }
+        }
+
+        // 3. Check that no previous vote is surrounding `attestation`.
         // If there is a surrounding attestation, we only return the most recent one.
         let surrounding_attestation = txn
             .prepare(
@@ -277,7 +545,7 @@ impl SlashingDatabase {
             ));
         }
 
-        // 3. Check that no previous vote is surrounded by `attestation`.
+        // 4. Check that no previous vote is surrounded by `attestation`.
         // If there is a surrounded attestation, we only return the most recent one.
         let surrounded_attestation = txn
             .prepare(
@@ -359,16 +627,47 @@ impl SlashingDatabase {
     /// The checking and inserting happen atomically and exclusively. We enforce exclusivity
     /// to prevent concurrent checks and inserts from resulting in slashable data being inserted.
     ///
+    /// Concurrent calls for the same `validator_pubkey` (via this database or any of its clones)
+    /// are serialised by a per-validator lock, so each call observes every message inserted by
+    /// the calls which were granted the lock before it. Calls for different validators do not
+    /// contend on this lock.
+    ///
+    /// The `genesis_validators_root` used to compute `domain` must match the root stored in the
+    /// database, so that signatures for one network are never checked against another's history.
+    ///
+    /// Re-signing a block identical to one already recorded returns `Safe::SameData` after only
+    /// reading from the database, without taking an exclusive transaction.
+    ///
     /// This is the safe, externally-callable interface for checking block proposals.
     pub fn check_and_insert_block_proposal(
         &self,
         validator_pubkey: &PublicKey,
         block_header: &BeaconBlockHeader,
         domain: Hash256,
+        genesis_validators_root: Hash256,
     ) -> Result<Safe, NotSafe> {
+        let validator_lock = self.validator_lock(validator_pubkey);
+        let _validator_guard = validator_lock.lock();
+
         let mut conn = self.conn_pool.get()?;
+
+        // Any error here (e.g. from an unregistered validator or a database predating some table)
+        // is reported by the full check below.
+        let duplicate = Self::is_duplicate_block_proposal(
+            &conn.transaction()?,
+            validator_pubkey,
+            block_header,
+            domain,
+            genesis_validators_root,
+        );
+        if let Ok(true) = duplicate {
+            return Ok(Safe::SameData);
+        }
+
         let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
 
+        Self::check_genesis_validators_root(&txn, genesis_validators_root)?;
+
         let safe = self.check_block_proposal(&txn, validator_pubkey, block_header, domain)?;
 
         if safe != Safe::SameData {
@@ -376,6 +675,8 @@ impl SlashingDatabase {
         }
 
         txn.commit()?;
+        #[cfg(test)]
+        self.write_count.fetch_add(1, Ordering::SeqCst);
         Ok(safe)
     }
 
@@ -384,16 +685,44 @@ impl SlashingDatabase {
     /// The checking and inserting happen atomically and exclusively. We enforce exclusivity
     /// to prevent concurrent checks and inserts from resulting in slashable data being inserted.
     ///
+    /// Concurrent calls for the same `validator_pubkey` are serialised, as per
+    /// `check_and_insert_block_proposal`.
+    ///
+    /// The `genesis_validators_root` used to compute `domain` must match the root stored in the
+    /// database (see `check_and_insert_block_proposal`).
+    ///
+    /// Re-signing an identical attestation returns `Safe::SameData` without a write, as per
+    /// `check_and_insert_block_proposal`.
+    ///
     /// This is the safe, externally-callable interface for checking attestations.
     pub fn check_and_insert_attestation(
         &self,
         validator_pubkey: &PublicKey,
         attestation: &AttestationData,
         domain: Hash256,
+        genesis_validators_root: Hash256,
     ) -> Result<Safe, NotSafe> {
+        let validator_lock = self.validator_lock(validator_pubkey);
+        let _validator_guard = validator_lock.lock();
+
         let mut conn = self.conn_pool.get()?;
+
+        // Errors are reported by the full check below, as per `check_and_insert_block_proposal`.
+        let duplicate = Self::is_duplicate_attestation(
+            &conn.transaction()?,
+            validator_pubkey,
+            attestation,
+            domain,
+            genesis_validators_root,
+        );
+        if let Ok(true) = duplicate {
+            return Ok(Safe::SameData);
+        }
+
         let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
 
+        Self::check_genesis_validators_root(&txn, genesis_validators_root)?;
+
         let safe = self.check_attestation(&txn, validator_pubkey, attestation, domain)?;
 
         if safe != Safe::SameData {
@@ -401,14 +730,182 @@ impl SlashingDatabase {
         }
 
         txn.commit()?;
+        #[cfg(test)]
+        self.write_count.fetch_add(1, Ordering::SeqCst);
         Ok(safe)
     }
+
+    /// Delete the blocks signed by `validator_pubkey` with slots prior to `up_to_slot`, except for
+    /// the block with the greatest slot.
+    ///
+    /// Blocks at or prior to the greatest deleted slot are refused from then on, so that pruning
+    /// never permits a slashable block to be signed.
+    pub fn prune_signed_blocks(
+        &self,
+        validator_pubkey: &PublicKey,
+        up_to_slot: Slot,
+    ) -> Result<(), NotSafe> {
+        let validator_lock = self.validator_lock(validator_pubkey);
+        let _validator_guard = validator_lock.lock();
+
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        let validator_id = Self::get_validator_id(&txn, validator_pubkey)?;
+        Self::prune_validator_blocks(&txn, validator_id, Some(up_to_slot))?;
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Delete the attestations signed by `validator_pubkey` with target epochs prior to
+    /// `up_to_epoch`, except for the attestation with the greatest target epoch.
+    ///
+    /// Attestations with a source epoch prior to the greatest deleted source epoch, or a target
+    /// epoch at or prior to the greatest deleted target epoch, are refused from then on, so that
+    /// pruning never permits a slashable attestation to be signed.
+    pub fn prune_signed_attestations(
+        &self,
+        validator_pubkey: &PublicKey,
+        up_to_epoch: Epoch,
+    ) -> Result<(), NotSafe> {
+        let validator_lock = self.validator_lock(validator_pubkey);
+        let _validator_guard = validator_lock.lock();
+
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        let validator_id = Self::get_validator_id(&txn, validator_pubkey)?;
+        Self::prune_validator_attestations(&txn, validator_id, Some(up_to_epoch))?;
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Prune the history of every validator down to its most recent block and attestation.
+    pub fn minify(&self) -> Result<(), NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        let validator_ids = txn
+            .prepare("SELECT id FROM validators")?
+            .query_map(params![], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+
+        for validator_id in validator_ids {
+            Self::prune_validator_blocks(&txn, validator_id, None)?;
+            Self::prune_validator_attestations(&txn, validator_id, None)?;
+        }
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Prune the blocks of the validator with `validator_id`, as per `prune_signed_blocks`.
+    ///
+    /// All blocks but the latest are pruned if `up_to_slot` is `None`.
+    fn prune_validator_blocks(
+        txn: &Transaction,
+        validator_id: i64,
+        up_to_slot: Option<Slot>,
+    ) -> Result<(), NotSafe> {
+        let pruned_slot: Option<Slot> = txn.query_row(
+            "SELECT MAX(slot) FROM signed_blocks
+             WHERE validator_id = ?1 AND (?2 IS NULL OR slot < ?2) AND slot < (
+                SELECT MAX(slot) FROM signed_blocks WHERE validator_id = ?1
+             )",
+            params![validator_id, up_to_slot],
+            |row| row.get(0),
+        )?;
+
+        if let Some(pruned_slot) = pruned_slot {
+            txn.execute(
+                "DELETE FROM signed_blocks WHERE validator_id = ?1 AND slot <= ?2",
+                params![validator_id, pruned_slot],
+            )?;
+
+            let bound_slot = txn
+                .query_row(
+                    "SELECT slot FROM block_lower_bounds WHERE validator_id = ?1",
+                    params![validator_id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .map_or(pruned_slot, |bound_slot: Slot| {
+                    std::cmp::max(bound_slot, pruned_slot)
+                });
+            txn.execute(
+                "INSERT OR REPLACE INTO block_lower_bounds (validator_id, slot) VALUES (?1, ?2)",
+                params![validator_id, bound_slot],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Prune the attestations of the validator with `validator_id`, as per
+    /// `prune_signed_attestations`.
+    ///
+    /// All attestations but the latest are pruned if `up_to_epoch` is `None`.
+    fn prune_validator_attestations(
+        txn: &Transaction,
+        validator_id: i64,
+        up_to_epoch: Option<Epoch>,
+    ) -> Result<(), NotSafe> {
+        let (pruned_source_epoch, pruned_target_epoch): (Option<Epoch>, Option<Epoch>) = txn
+            .query_row(
+                "SELECT MAX(source_epoch), MAX(target_epoch) FROM signed_attestations
+                 WHERE validator_id = ?1 AND (?2 IS NULL OR target_epoch < ?2) AND target_epoch < (
+                    SELECT MAX(target_epoch) FROM signed_attestations WHERE validator_id = ?1
+                 )",
+                params![validator_id, up_to_epoch],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+
+        if let (Some(pruned_source_epoch), Some(pruned_target_epoch)) =
+            (pruned_source_epoch, pruned_target_epoch)
+        {
+            txn.execute(
+                "DELETE FROM signed_attestations WHERE validator_id = ?1 AND target_epoch <= ?2",
+                params![validator_id, pruned_target_epoch],
+            )?;
+
+            let (bound_source_epoch, bound_target_epoch) = txn
+                .query_row(
+                    "SELECT source_epoch, target_epoch
+                     FROM attestation_lower_bounds
+                     WHERE validator_id = ?1",
+                    params![validator_id],
+                    |row| Ok((row.get::<_, Epoch>(0)?, row.get::<_, Epoch>(1)?)),
+                )
+                .optional()?
+                .map_or(
+                    (pruned_source_epoch, pruned_target_epoch),
+                    |(source, target)| {
+                        (
+                            std::cmp::max(source, pruned_source_epoch),
+                            std::cmp::max(target, pruned_target_epoch),
+                        )
+                    },
+                );
+            txn.execute(
+                "INSERT OR REPLACE INTO attestation_lower_bounds
+                 (validator_id, source_epoch, target_epoch)
+                 VALUES (?1, ?2, ?3)",
+                params![validator_id, bound_source_epoch, bound_target_epoch],
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::pubkey;
+    use crate::attestation_tests::attestation_data_builder;
+    use crate::block_tests::block;
+    use crate::test_utils::{pubkey, DEFAULT_DOMAIN, DEFAULT_GENESIS_VALIDATORS_ROOT};
     use tempfile::tempdir;
 
     #[test]
@@ -418,15 +915,59 @@ mod tests {
         assert!(SlashingDatabase::open(&file).is_err());
     }
 
-    // Due to the exclusive locking, trying to use an already open database should error.
+    // Due to the exclusive locking, trying to open an already open database should error, since
+    // opening migrates the database.
     #[test]
     fn double_open_error() {
         let dir = tempdir().unwrap();
         let file = dir.path().join("db.sqlite");
         let _db1 = SlashingDatabase::create(&file).unwrap();
 
-        let db2 = SlashingDatabase::open(&file).unwrap();
-        db2.register_validator(&pubkey(0)).unwrap_err();
+        SlashingDatabase::open(&file).unwrap_err();
+    }
+
+    // A database created before the metadata and lower bound tables were introduced should have
+    // them created upon being opened.
+    #[test]
+    fn open_migrates_old_schema() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+
+        let db = SlashingDatabase::create(&file).unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+        {
+            let conn = db.conn_pool.get().unwrap();
+            for table in &["metadata", "block_lower_bounds", "attestation_lower_bounds"] {
+                conn.execute(&format!("DROP TABLE {}", table), params![])
+                    .unwrap();
+            }
+        }
+        drop(db);
+
+        let db = SlashingDatabase::open(&file).unwrap();
+        {
+            let conn = db.conn_pool.get().unwrap();
+            for table in &["metadata", "block_lower_bounds", "attestation_lower_bounds"] {
+                let count: i64 = conn
+                    .query_row(
+                        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                        params![*table],
+                        |row| row.get(0),
+                    )
+                    .unwrap();
+                assert_eq!(count, 1, "the {} table should be created", table);
+            }
+        }
+
+        assert_eq!(
+            db.check_and_insert_block_proposal(
+                &pubkey(0),
+                &block(1),
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT
+            ),
+            Ok(Safe::Valid)
+        );
     }
 
     // Attempting to create the same database twice should error.
@@ -439,33 +980,272 @@ mod tests {
         SlashingDatabase::create(&file).unwrap_err();
     }
 
+    /// Check that `db` has the expected connection settings.
+    fn check_connection_settings(db: &SlashingDatabase) {
+        assert_eq!(db.conn_pool.max_size(), POOL_SIZE);
+        assert_eq!(db.conn_pool.connection_timeout(), CONNECTION_TIMEOUT);
+        let conn = db.conn_pool.get().unwrap();
+        assert_eq!(
+            conn.pragma_query_value(None, "foreign_keys", |row| { row.get::<_, bool>(0) })
+                .unwrap(),
+            true
+        );
+        assert_eq!(
+            conn.pragma_query_value(None, "locking_mode", |row| { row.get::<_, String>(0) })
+                .unwrap()
+                .to_uppercase(),
+            "EXCLUSIVE"
+        );
+    }
+
     // Check that both `open` and `create` apply the same connection settings.
     #[test]
     fn connection_settings_applied() {
         let dir = tempdir().unwrap();
         let file = dir.path().join("db.sqlite");
 
-        let check = |db: &SlashingDatabase| {
-            assert_eq!(db.conn_pool.max_size(), POOL_SIZE);
-            assert_eq!(db.conn_pool.connection_timeout(), CONNECTION_TIMEOUT);
-            let conn = db.conn_pool.get().unwrap();
+        let db1 = SlashingDatabase::create(&file).unwrap();
+        check_connection_settings(&db1);
+        drop(db1);
+        let db2 = SlashingDatabase::open(&file).unwrap();
+        check_connection_settings(&db2);
+    }
+
+    // Check that an in-memory database applies the same connection settings as one on disk.
+    #[test]
+    fn in_memory_connection_settings_applied() {
+        check_connection_settings(&SlashingDatabase::create_in_memory().unwrap());
+    }
+
+    // Clones of an in-memory database should share its contents.
+    #[test]
+    fn in_memory_clones_share_data() {
+        let db1 = SlashingDatabase::create_in_memory().unwrap();
+        let db2 = db1.clone();
+
+        db1.register_validator(&pubkey(0)).unwrap();
+
+        let header = block(1);
+        assert_eq!(
+            db2.check_and_insert_block_proposal(
+                &pubkey(0),
+                &header,
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT
+            ),
+            Ok(Safe::Valid)
+        );
+        assert_eq!(
+            db1.check_and_insert_block_proposal(
+                &pubkey(0),
+                &header,
+                DEFAULT_DOMAIN,
+                DEFAULT_GENESIS_VALIDATORS_ROOT
+            ),
+            Ok(Safe::SameData)
+        );
+    }
+
+    fn sign_block(db: &SlashingDatabase, header: &BeaconBlockHeader) -> Result<Safe, NotSafe> {
+        db.check_and_insert_block_proposal(
+            &pubkey(0),
+            header,
+            DEFAULT_DOMAIN,
+            DEFAULT_GENESIS_VALIDATORS_ROOT,
+        )
+    }
+
+    fn sign_attestation(db: &SlashingDatabase, data: &AttestationData) -> Result<Safe, NotSafe> {
+        db.check_and_insert_attestation(
+            &pubkey(0),
+            data,
+            DEFAULT_DOMAIN,
+            DEFAULT_GENESIS_VALIDATORS_ROOT,
+        )
+    }
+
+    #[test]
+    fn prune_signed_blocks_retains_lower_bound() {
+        let db = SlashingDatabase::create_in_memory().unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        let blocks = (1..=5).map(block).collect::<Vec<_>>();
+        for header in &blocks {
+            assert_eq!(sign_block(&db, header), Ok(Safe::Valid));
+        }
+
+        db.prune_signed_blocks(&pubkey(0), Slot::new(4)).unwrap();
+
+        for slot in 1..=3 {
+            assert_eq!(
+                sign_block(&db, &block(slot)),
+                Err(NotSafe::InvalidBlock(
+                    InvalidBlock::SlotViolatesLowerBound {
+                        block_slot: Slot::new(slot),
+                        bound_slot: Slot::new(3),
+                    }
+                ))
+            );
+        }
+        assert_eq!(sign_block(&db, &blocks[3]), Ok(Safe::SameData));
+        assert_eq!(sign_block(&db, &blocks[4]), Ok(Safe::SameData));
+        assert_eq!(sign_block(&db, &block(6)), Ok(Safe::Valid));
+
+        // Pruning up to a slot beyond the latest block retains the latest block.
+        db.prune_signed_blocks(&pubkey(0), Slot::new(100)).unwrap();
+        assert_eq!(
+            sign_block(&db, &blocks[4]),
+            Err(NotSafe::InvalidBlock(
+                InvalidBlock::SlotViolatesLowerBound {
+                    block_slot: Slot::new(5),
+                    bound_slot: Slot::new(5),
+                }
+            ))
+        );
+        assert!(matches!(
+            sign_block(&db, &block(6)),
+            Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(_)))
+        ));
+        assert_eq!(sign_block(&db, &block(7)), Ok(Safe::Valid));
+    }
+
+    #[test]
+    fn prune_signed_attestations_retains_lower_bound() {
+        let db = SlashingDatabase::create_in_memory().unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        for (source, target) in &[(0, 1), (1, 2), (2, 3), (3, 4)] {
             assert_eq!(
-                conn.pragma_query_value(None, "foreign_keys", |row| { row.get::<_, bool>(0) })
-                    .unwrap(),
-                true
+                sign_attestation(&db, &attestation_data_builder(*source, *target)),
+                Ok(Safe::Valid)
             );
+        }
+
+        db.prune_signed_attestations(&pubkey(0), Epoch::new(3))
+            .unwrap();
+
+        // A pruned attestation can't be signed again, even with the same data.
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(1, 2)),
+            Err(NotSafe::InvalidAttestation(
+                InvalidAttestation::TargetViolatesLowerBound {
+                    target_epoch: Epoch::new(2),
+                    bound_epoch: Epoch::new(2),
+                }
+            ))
+        );
+        // An attestation surrounding a pruned attestation is refused.
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(0, 5)),
+            Err(NotSafe::InvalidAttestation(
+                InvalidAttestation::SourceViolatesLowerBound {
+                    source_epoch: Epoch::new(0),
+                    bound_epoch: Epoch::new(1),
+                }
+            ))
+        );
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(2, 3)),
+            Ok(Safe::SameData)
+        );
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(4, 5)),
+            Ok(Safe::Valid)
+        );
+    }
+
+    #[test]
+    fn minify_retains_latest_messages() {
+        let db = SlashingDatabase::create_in_memory().unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        let blocks = (1..=3).map(block).collect::<Vec<_>>();
+        for header in &blocks {
+            assert_eq!(sign_block(&db, header), Ok(Safe::Valid));
+        }
+        for (source, target) in &[(0, 1), (1, 2), (2, 3)] {
             assert_eq!(
-                conn.pragma_query_value(None, "locking_mode", |row| { row.get::<_, String>(0) })
-                    .unwrap()
-                    .to_uppercase(),
-                "EXCLUSIVE"
+                sign_attestation(&db, &attestation_data_builder(*source, *target)),
+                Ok(Safe::Valid)
             );
-        };
+        }
 
-        let db1 = SlashingDatabase::create(&file).unwrap();
-        check(&db1);
-        drop(db1);
-        let db2 = SlashingDatabase::open(&file).unwrap();
-        check(&db2);
+        db.minify().unwrap();
+
+        assert_eq!(sign_block(&db, &blocks[2]), Ok(Safe::SameData));
+        assert!(sign_block(&db, &block(2)).is_err());
+        assert!(sign_block(&db, &block(1)).is_err());
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(2, 3)),
+            Ok(Safe::SameData)
+        );
+        assert!(sign_attestation(&db, &attestation_data_builder(1, 2)).is_err());
+        assert!(sign_attestation(&db, &attestation_data_builder(0, 4)).is_err());
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(3, 4)),
+            Ok(Safe::Valid)
+        );
+    }
+
+    fn write_count(db: &SlashingDatabase) -> usize {
+        db.write_count.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn duplicate_block_skips_write() {
+        let db = SlashingDatabase::create_in_memory().unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        let header = block(1);
+        assert_eq!(sign_block(&db, &header), Ok(Safe::Valid));
+        assert_eq!(write_count(&db), 1);
+
+        assert_eq!(sign_block(&db, &header), Ok(Safe::SameData));
+        assert_eq!(write_count(&db), 1, "duplicate should not be written");
+
+        // A conflicting block at the same slot is still refused.
+        assert!(sign_block(&db, &block(1)).is_err());
+
+        // As is a duplicate for another network.
+        assert!(matches!(
+            db.check_and_insert_block_proposal(
+                &pubkey(0),
+                &header,
+                DEFAULT_DOMAIN,
+                Hash256::from_low_u64_be(1)
+            ),
+            Err(NotSafe::GenesisValidatorsRootMismatch { .. })
+        ));
+
+        // The block retained as the lower bound by pruning is still a duplicate.
+        let header = block(2);
+        assert_eq!(sign_block(&db, &header), Ok(Safe::Valid));
+        db.prune_signed_blocks(&pubkey(0), Slot::new(3)).unwrap();
+        let writes = write_count(&db);
+        assert_eq!(sign_block(&db, &header), Ok(Safe::SameData));
+        assert_eq!(write_count(&db), writes, "duplicate should not be written");
+    }
+
+    #[test]
+    fn duplicate_attestation_skips_write() {
+        let db = SlashingDatabase::create_in_memory().unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(0, 1)),
+            Ok(Safe::Valid)
+        );
+        assert_eq!(write_count(&db), 1);
+
+        assert_eq!(
+            sign_attestation(&db, &attestation_data_builder(0, 1)),
+            Ok(Safe::SameData)
+        );
+        assert_eq!(write_count(&db), 1, "duplicate should not be written");
+
+        // A double vote is still refused.
+        let mut conflicting = attestation_data_builder(0, 1);
+        conflicting.index += 1;
+        assert!(sign_attestation(&db, &conflicting).is_err());
     }
 }
@@ -8,6 +8,7 @@ use types::{
 
 pub const DEFAULT_VALIDATOR_INDEX: usize = 0;
 pub const DEFAULT_DOMAIN: Hash256 = Hash256::zero();
+pub const DEFAULT_GENESIS_VALIDATORS_ROOT: Hash256 = Hash256::zero();
 
 pub fn pubkey(index: usize) -> PublicKey {
     generate_deterministic_keypair(index).pk
@@ -17,6 +18,7 @@ pub struct Test<T> {
     pubkey: PublicKey,
     data: T,
     domain: Hash256,
+    genesis_validators_root: Hash256,
     expected: Result<Safe, NotSafe>,
 }
 
@@ -30,6 +32,7 @@ impl<T> Test<T> {
             pubkey,
             data,
             domain: DEFAULT_DOMAIN,
+            genesis_validators_root: DEFAULT_GENESIS_VALIDATORS_ROOT,
             expected: Ok(Safe::Valid),
         }
     }
@@ -39,6 +42,11 @@ impl<T> Test<T> {
         self
     }
 
+    pub fn with_genesis_validators_root(mut self, genesis_validators_root: Hash256) -> Self {
+        self.genesis_validators_root = genesis_validators_root;
+        self
+    }
+
     pub fn expect_result(mut self, result: Result<Safe, NotSafe>) -> Self {
         self.expected = result;
         self
@@ -52,6 +60,14 @@ impl<T> Test<T> {
         self.expect_result(Err(NotSafe::InvalidBlock(error)))
     }
 
+    pub fn expect_genesis_validators_root_mismatch(self, stored: Hash256) -> Self {
+        let provided = self.genesis_validators_root;
+        self.expect_result(Err(NotSafe::GenesisValidatorsRootMismatch {
+            stored,
+            provided,
+        }))
+    }
+
     pub fn expect_same_data(self) -> Self {
         self.expect_result(Ok(Safe::SameData))
     }
@@ -85,7 +101,12 @@ impl StreamTest<AttestationData> {
 
         for (i, test) in self.cases.iter().enumerate() {
             assert_eq!(
-                slashing_db.check_and_insert_attestation(&test.pubkey, &test.data, test.domain),
+                slashing_db.check_and_insert_attestation(
+                    &test.pubkey,
+                    &test.data,
+                    test.domain,
+                    test.genesis_validators_root
+                ),
                 test.expected,
                 "attestation {} not processed as expected",
                 i
@@ -106,7 +127,12 @@ impl StreamTest<BeaconBlockHeader> {
 
         for (i, test) in self.cases.iter().enumerate() {
             assert_eq!(
-                slashing_db.check_and_insert_block_proposal(&test.pubkey, &test.data, test.domain),
+                slashing_db.check_and_insert_block_proposal(
+                    &test.pubkey,
+                    &test.data,
+                    test.domain,
+                    test.genesis_validators_root
+                ),
                 test.expected,
                 "attestation {} not processed as expected",
                 i
@@ -1,8 +1,11 @@
-use crate::validator_store::ValidatorStore;
+use crate::{
+    config::SyncTolerances, is_synced::is_synced_with_peers, validator_store::ValidatorStore,
+};
 use environment::RuntimeContext;
 use futures::channel::mpsc::Receiver;
 use futures::{StreamExt, TryFutureExt};
 use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
+use rest_types::SYNC_TOLERANCE;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
 use std::ops::Deref;
@@ -16,6 +19,9 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     beacon_node: Option<RemoteBeaconNode<E>>,
     context: Option<RuntimeContext<E>>,
     graffiti: Option<Graffiti>,
+    allow_unsynced_beacon_node: bool,
+    sync_tolerance: u64,
+    min_peer_count: usize,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
@@ -26,6 +32,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             beacon_node: None,
             context: None,
             graffiti: None,
+            allow_unsynced_beacon_node: false,
+            sync_tolerance: SYNC_TOLERANCE,
+            min_peer_count: 0,
         }
     }
 
@@ -54,6 +63,25 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    /// Set to `true` to allow producing blocks when the beacon node is not synced.
+    pub fn allow_unsynced_beacon_node(mut self, allow_unsynced_beacon_node: bool) -> Self {
+        self.allow_unsynced_beacon_node = allow_unsynced_beacon_node;
+        self
+    }
+
+    /// Set the number of slots a syncing beacon node may lag behind whilst still producing blocks
+    /// to the `block_proposal` tolerance.
+    pub fn sync_tolerances(mut self, sync_tolerances: SyncTolerances) -> Self {
+        self.sync_tolerance = sync_tolerances.block_proposal;
+        self
+    }
+
+    /// Set the minimum number of peers the beacon node must have to produce blocks.
+    pub fn min_peer_count(mut self, min_peer_count: usize) -> Self {
+        self.min_peer_count = min_peer_count;
+        self
+    }
+
     pub fn build(self) -> Result<BlockService<T, E>, String> {
         Ok(BlockService {
             inner: Arc::new(Inner {
@@ -70,6 +98,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                     .context
                     .ok_or_else(|| "Cannot build BlockService without runtime_context")?,
                 graffiti: self.graffiti,
+                allow_unsynced_beacon_node: self.allow_unsynced_beacon_node,
+                sync_tolerance: self.sync_tolerance,
+                min_peer_count: self.min_peer_count,
             }),
         })
     }
@@ -82,6 +113,12 @@ pub struct Inner<T, E: EthSpec> {
     beacon_node: RemoteBeaconNode<E>,
     context: RuntimeContext<E>,
     graffiti: Option<Graffiti>,
+    /// If true, blocks will be produced even if the beacon node is not synced.
+    allow_unsynced_beacon_node: bool,
+    /// The number of slots a syncing beacon node may lag behind whilst still producing blocks.
+    sync_tolerance: u64,
+    /// The minimum number of peers the beacon node must have to produce blocks.
+    min_peer_count: usize,
 }
 
 /// Attempts to produce attestations for any block producer(s) at the start of the epoch.
@@ -175,8 +212,31 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 log,
                 "No local block proposers for this slot";
                 "slot" => slot.as_u64()
-            )
-        } else if proposers.len() > 1 {
+            );
+            return Ok(());
+        }
+
+        let sync_status = is_synced_with_peers(
+            &self.beacon_node,
+            self.slot_clock.as_ref(),
+            self.sync_tolerance,
+            self.min_peer_count,
+            Some(log),
+        )
+        .await;
+
+        if !sync_status.is_ready() && !self.allow_unsynced_beacon_node {
+            warn!(
+                log,
+                "Skipping block production whilst beacon node is not ready";
+                "sync_status" => format!("{:?}", sync_status),
+                "proposers" => format!("{:?}", proposers),
+                "slot" => slot.as_u64(),
+            );
+            return Ok(());
+        }
+
+        if proposers.len() > 1 {
             error!(
                 log,
                 "Multiple block proposers for this slot";
@@ -260,3 +320,21 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slot_clock::TestingSlotClock;
+    use types::MinimalEthSpec;
+
+    #[test]
+    fn uses_the_block_proposal_sync_tolerance() {
+        let builder = BlockServiceBuilder::<TestingSlotClock, MinimalEthSpec>::new()
+            .sync_tolerances(SyncTolerances {
+                attestation: 4,
+                block_proposal: 1,
+            });
+
+        assert_eq!(builder.sync_tolerance, 1);
+    }
+}
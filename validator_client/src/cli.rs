@@ -60,6 +60,40 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                       node is not synced.",
                 ),
         )
+        .arg(
+            Arg::with_name("attestation-sync-tolerance")
+                .long("attestation-sync-tolerance")
+                .value_name("SLOTS")
+                .help(
+                    "The number of slots a syncing beacon node may be behind the current slot \
+                     whilst still being used to poll for duties and produce attestations. \
+                     Defaults to 4.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("block-proposal-sync-tolerance")
+                .long("block-proposal-sync-tolerance")
+                .value_name("SLOTS")
+                .help(
+                    "The number of slots a syncing beacon node may be behind the current slot \
+                     whilst still being used to produce blocks. Lower values avoid producing \
+                     blocks on a stale head, but skip proposals whilst the beacon node catches \
+                     up. Defaults to 4.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("min-peer-count")
+                .long("min-peer-count")
+                .value_name("COUNT")
+                .help(
+                    "The minimum number of peers the beacon node must be connected to before \
+                     it is used to poll for duties and produce blocks.",
+                )
+                .takes_value(true)
+                .default_value("0"),
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::with_name("graffiti")
@@ -1,5 +1,6 @@
 use clap::ArgMatches;
 use clap_utils::{parse_optional, parse_path_with_default_in_home_dir};
+use rest_types::SYNC_TOLERANCE;
 use serde_derive::{Deserialize, Serialize};
 use std::path::PathBuf;
 use types::{Graffiti, GRAFFITI_BYTES_LEN};
@@ -10,6 +11,26 @@ pub const DEFAULT_SECRETS_DIR: &str = ".lighthouse/secrets";
 /// Path to the slashing protection database within the datadir.
 pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.sqlite";
 
+/// The number of slots a syncing beacon node may lag behind the current slot whilst still being
+/// used for each activity of the validator client.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SyncTolerances {
+    /// Tolerance for polling duties and producing attestations.
+    pub attestation: u64,
+    /// Tolerance for producing blocks. Lowering it avoids producing blocks on a stale head, at the
+    /// cost of skipping proposals whilst the beacon node catches up.
+    pub block_proposal: u64,
+}
+
+impl Default for SyncTolerances {
+    fn default() -> Self {
+        Self {
+            attestation: SYNC_TOLERANCE,
+            block_proposal: SYNC_TOLERANCE,
+        }
+    }
+}
+
 /// Stores the core configuration for this validator instance.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -24,6 +45,10 @@ pub struct Config {
     /// If true, the validator client will still poll for duties and produce blocks even if the
     /// beacon node is not synced at startup.
     pub allow_unsynced_beacon_node: bool,
+    /// The number of slots the beacon node may lag behind before it is considered unsynced.
+    pub sync_tolerances: SyncTolerances,
+    /// The minimum number of peers the beacon node must be connected to before it is used.
+    pub min_peer_count: usize,
     /// If true, refuse to unlock a keypair that is guarded by a lockfile.
     pub strict_lockfiles: bool,
     /// If true, don't scan the validators dir for new keystores.
@@ -46,6 +71,8 @@ impl Default for Config {
             secrets_dir,
             http_server: DEFAULT_HTTP_SERVER.to_string(),
             allow_unsynced_beacon_node: false,
+            sync_tolerances: SyncTolerances::default(),
+            min_peer_count: 0,
             strict_lockfiles: false,
             disable_auto_discover: false,
             graffiti: None,
@@ -78,6 +105,18 @@ impl Config {
 
         config.allow_unsynced_beacon_node = cli_args.is_present("allow-unsynced");
         config.strict_lockfiles = cli_args.is_present("strict-lockfiles");
+
+        if let Some(tolerance) = parse_optional(cli_args, "attestation-sync-tolerance")? {
+            config.sync_tolerances.attestation = tolerance;
+        }
+
+        if let Some(tolerance) = parse_optional(cli_args, "block-proposal-sync-tolerance")? {
+            config.sync_tolerances.block_proposal = tolerance;
+        }
+
+        if let Some(min_peer_count) = parse_optional(cli_args, "min-peer-count")? {
+            config.min_peer_count = min_peer_count;
+        }
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
 
         if let Some(secrets_dir) = parse_optional(cli_args, "secrets-dir")? {
@@ -1,12 +1,15 @@
 use crate::{
-    block_service::BlockServiceNotification, is_synced::is_synced, validator_store::ValidatorStore,
+    block_service::BlockServiceNotification,
+    config::SyncTolerances,
+    is_synced::{is_synced_with_peers, SyncStatus},
+    validator_store::ValidatorStore,
 };
 use environment::RuntimeContext;
 use futures::channel::mpsc::Sender;
 use futures::{SinkExt, StreamExt};
 use parking_lot::RwLock;
 use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
-use rest_types::{ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription};
+use rest_types::{ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription, SYNC_TOLERANCE};
 use slog::{debug, error, trace, warn};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
@@ -14,7 +17,7 @@ use std::convert::TryInto;
 use std::ops::Deref;
 use std::sync::Arc;
 use tokio::time::{interval_at, Duration, Instant};
-use types::{ChainSpec, CommitteeIndex, Epoch, EthSpec, PublicKey, SelectionProof, Slot};
+use types::{ChainSpec, CommitteeIndex, Epoch, EthSpec, PublicKey, SelectionProof, Slot, SubnetId};
 
 /// Delay this period of time after the slot starts. This allows the node to process the new slot.
 const TIME_DELAY_FROM_SLOT: Duration = Duration::from_millis(100);
@@ -122,6 +125,7 @@ impl TryInto<DutyAndProof> for ValidatorDutyBytes {
             attestation_committee_position: self.attestation_committee_position,
             committee_count_at_slot: self.committee_count_at_slot,
             block_proposal_slots: self.block_proposal_slots,
+            block_proposal_dependent_root: self.block_proposal_dependent_root,
             aggregator_modulo: self.aggregator_modulo,
         };
         Ok(DutyAndProof {
@@ -276,6 +280,8 @@ impl DutiesStore {
                         Ok(InsertOutcome::Identical)
                     } else if duties.duty.block_proposal_slots.is_some() {
                         known_duties.duty.block_proposal_slots = duties.duty.block_proposal_slots;
+                        known_duties.duty.block_proposal_dependent_root =
+                            duties.duty.block_proposal_dependent_root;
                         Ok(InsertOutcome::NewProposalSlots)
                     } else {
                         Ok(InsertOutcome::Invalid)
@@ -331,6 +337,8 @@ pub struct DutiesServiceBuilder<T, E: EthSpec> {
     beacon_node: Option<RemoteBeaconNode<E>>,
     context: Option<RuntimeContext<E>>,
     allow_unsynced_beacon_node: bool,
+    sync_tolerance: u64,
+    min_peer_count: usize,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
@@ -341,6 +349,8 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
             beacon_node: None,
             context: None,
             allow_unsynced_beacon_node: false,
+            sync_tolerance: SYNC_TOLERANCE,
+            min_peer_count: 0,
         }
     }
 
@@ -370,6 +380,19 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
         self
     }
 
+    /// Set the number of slots a syncing beacon node may lag behind whilst still being polled to
+    /// the `attestation` tolerance.
+    pub fn sync_tolerances(mut self, sync_tolerances: SyncTolerances) -> Self {
+        self.sync_tolerance = sync_tolerances.attestation;
+        self
+    }
+
+    /// Set the minimum number of peers the beacon node must have to be polled.
+    pub fn min_peer_count(mut self, min_peer_count: usize) -> Self {
+        self.min_peer_count = min_peer_count;
+        self
+    }
+
     pub fn build(self) -> Result<DutiesService<T, E>, String> {
         Ok(DutiesService {
             inner: Arc::new(Inner {
@@ -387,6 +410,8 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
                     .context
                     .ok_or_else(|| "Cannot build DutiesService without runtime_context")?,
                 allow_unsynced_beacon_node: self.allow_unsynced_beacon_node,
+                sync_tolerance: self.sync_tolerance,
+                min_peer_count: self.min_peer_count,
             }),
         })
     }
@@ -402,6 +427,10 @@ pub struct Inner<T, E: EthSpec> {
     /// If true, the duties service will poll for duties from the beacon node even if it is not
     /// synced.
     allow_unsynced_beacon_node: bool,
+    /// The number of slots a syncing beacon node may lag behind whilst still being polled.
+    sync_tolerance: u64,
+    /// The minimum number of peers the beacon node must have to be polled.
+    min_peer_count: usize,
 }
 
 /// Maintains a store of the duties for all voting validators in the `validator_store`.
@@ -504,10 +533,35 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
     async fn do_update(self, block_service_tx: &mut Sender<BlockServiceNotification>) {
         let log = self.context.log();
 
-        if !is_synced(&self.beacon_node, &self.slot_clock, None).await
-            && !self.allow_unsynced_beacon_node
+        match is_synced_with_peers(
+            &self.beacon_node,
+            &self.slot_clock,
+            self.sync_tolerance,
+            self.min_peer_count,
+            None,
+        )
+        .await
         {
-            return;
+            SyncStatus::Synced | SyncStatus::SyncingWithinTolerance => {}
+            // Duties cannot be downloaded from an unreachable beacon node, even if it is allowed to
+            // be unsynced.
+            SyncStatus::Unreachable => {
+                debug!(
+                    log,
+                    "Skipping duties update whilst beacon node is unreachable"
+                );
+                return;
+            }
+            status @ SyncStatus::SyncingTooFarBehind | status @ SyncStatus::InsufficientPeers => {
+                if !self.allow_unsynced_beacon_node {
+                    debug!(
+                        log,
+                        "Skipping duties update whilst beacon node is not ready";
+                        "sync_status" => format!("{:?}", status),
+                    );
+                    return;
+                }
+            }
         }
 
         let slot = if let Some(slot) = self.slot_clock.now() {
@@ -643,12 +697,26 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
                 let is_aggregator = self.store.is_aggregator(&validator_pubkey, epoch)?;
 
                 if outcome.is_subscription_candidate() {
+                    let attestation_committee_index = remote_duties.attestation_committee_index?;
+                    let slot = remote_duties.attestation_slot?;
+                    let committee_count_at_slot = remote_duties.committee_count_at_slot?;
+
+                    // Compute the subnet here to save the beacon node from doing so.
+                    let subnet_id = SubnetId::compute_subnet::<E>(
+                        slot,
+                        attestation_committee_index,
+                        committee_count_at_slot,
+                        &self.context.eth2_config().spec,
+                    )
+                    .ok();
+
                     Some(ValidatorSubscription {
                         validator_index: remote_duties.validator_index?,
-                        attestation_committee_index: remote_duties.attestation_committee_index?,
-                        slot: remote_duties.attestation_slot?,
-                        committee_count_at_slot: remote_duties.committee_count_at_slot?,
+                        attestation_committee_index,
+                        slot,
+                        committee_count_at_slot,
                         is_aggregator,
+                        subnet_id,
                     })
                 } else {
                     None
@@ -732,3 +800,21 @@ fn duties_match_epoch(duties: &ValidatorDuty, epoch: Epoch, slots_per_epoch: u64
                 .all(|slot| slot.epoch(slots_per_epoch) == epoch)
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slot_clock::TestingSlotClock;
+    use types::MinimalEthSpec;
+
+    #[test]
+    fn uses_the_attestation_sync_tolerance() {
+        let builder = DutiesServiceBuilder::<TestingSlotClock, MinimalEthSpec>::new()
+            .sync_tolerances(SyncTolerances {
+                attestation: 4,
+                block_proposal: 1,
+            });
+
+        assert_eq!(builder.sync_tolerance, 4);
+    }
+}
@@ -30,6 +30,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ForkServiceBuilder<T, E> {
         }
     }
 
+    /// Starts the service with a known `fork`, rather than waiting for it to be downloaded.
+    #[cfg(test)]
+    pub fn fork(mut self, fork: Fork) -> Self {
+        self.fork = Some(fork);
+        self
+    }
+
     pub fn slot_clock(mut self, slot_clock: T) -> Self {
         self.slot_clock = Some(slot_clock);
         self
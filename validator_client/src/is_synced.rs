@@ -1,27 +1,55 @@
 use remote_beacon_node::RemoteBeaconNode;
 use rest_types::SyncingResponse;
-use slog::{debug, error, Logger};
+use slog::{debug, error, warn, Logger};
 use slot_clock::SlotClock;
-use types::EthSpec;
+use types::{EthSpec, Slot};
 
-/// A distance in slots.
-const SYNC_TOLERANCE: u64 = 4;
+/// The sync status of a beacon node, as observed by the validator client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncStatus {
+    /// The beacon node indicates that it is not syncing.
+    Synced,
+    /// The beacon node indicates that it is syncing, but it is close enough to the current slot to
+    /// be used.
+    SyncingWithinTolerance,
+    /// The beacon node indicates that it is syncing and is too far behind the current slot to be
+    /// used (or the current slot is unknown).
+    SyncingTooFarBehind,
+    /// The beacon node could not be reached.
+    Unreachable,
+    /// The beacon node is ready by its sync status, but is connected to too few peers to publish
+    /// messages.
+    InsufficientPeers,
+}
 
-/// Returns `true` if the beacon node is synced and ready for action.
+impl SyncStatus {
+    /// Returns `true` if the beacon node is ready for action.
+    pub fn is_ready(self) -> bool {
+        match self {
+            SyncStatus::Synced | SyncStatus::SyncingWithinTolerance => true,
+            SyncStatus::SyncingTooFarBehind
+            | SyncStatus::Unreachable
+            | SyncStatus::InsufficientPeers => false,
+        }
+    }
+}
+
+/// Returns the `SyncStatus` of the beacon node.
 ///
-/// Returns `false` if:
+/// The beacon node is only ready for action (see `SyncStatus::is_ready`) if it is reachable and
+/// either:
 ///
-///  - The beacon node is unreachable.
-///  - The beacon node indicates that it is syncing **AND** it is more than `SYNC_TOLERANCE` behind
-///  the highest known slot.
+///  - The beacon node indicates that it is not syncing, **OR**
+///  - The beacon node is no more than `sync_tolerance` slots behind the current slot.
 ///
 ///  The second condition means the even if the beacon node thinks that it's syncing, we'll still
 ///  try to use it if it's close enough to the head.
 pub async fn is_synced<T: SlotClock, E: EthSpec>(
     beacon_node: &RemoteBeaconNode<E>,
     slot_clock: &T,
+    sync_tolerance: u64,
     log_opt: Option<&Logger>,
-) -> bool {
+) -> SyncStatus {
     let resp = match beacon_node.http.node().syncing_status().await {
         Ok(resp) => resp,
         Err(e) => {
@@ -33,48 +61,178 @@ pub async fn is_synced<T: SlotClock, E: EthSpec>(
                 )
             }
 
-            return false;
+            return SyncStatus::Unreachable;
         }
     };
 
-    match &resp {
-        SyncingResponse {
-            is_syncing: false, ..
-        } => true,
-        SyncingResponse {
-            is_syncing: true,
-            sync_status,
-        } => {
+    if let Some(log) = log_opt.filter(|_| resp.is_syncing) {
+        debug!(
+            log,
+            "Beacon node sync status";
+            "status" => format!("{:?}", resp),
+        );
+    }
+
+    let status = sync_status(&resp, slot_clock.now(), sync_tolerance);
+
+    if let Some(log) = log_opt.filter(|_| status == SyncStatus::SyncingTooFarBehind) {
+        error!(
+            log,
+            "Beacon node is syncing";
+            "msg" => "not receiving new duties",
+            "target_slot" => resp.sync_status.highest_slot.as_u64(),
+            "current_slot" => resp.sync_status.current_slot.as_u64(),
+        );
+    }
+
+    status
+}
+
+/// Determines the `SyncStatus` of a reachable beacon node from its `SyncingResponse`, given the
+/// current slot `now`.
+fn sync_status(resp: &SyncingResponse, now: Option<Slot>, sync_tolerance: u64) -> SyncStatus {
+    if !resp.is_syncing {
+        return SyncStatus::Synced;
+    }
+
+    match now {
+        Some(now)
+            if is_within_sync_tolerance(resp.sync_status.current_slot, now, sync_tolerance) =>
+        {
+            SyncStatus::SyncingWithinTolerance
+        }
+        // There's no good reason why we shouldn't be able to read the slot clock, so we'll
+        // indicate we're not synced if that's the case.
+        _ => SyncStatus::SyncingTooFarBehind,
+    }
+}
+
+/// As per `is_synced`, but additionally returns `SyncStatus::InsufficientPeers` if an otherwise
+/// ready beacon node is connected to fewer than `min_peer_count` peers, since a node without peers
+/// cannot publish our messages.
+///
+/// The peer count is only requested when the beacon node is otherwise ready and
+/// `min_peer_count > 0`.
+pub async fn is_synced_with_peers<T: SlotClock, E: EthSpec>(
+    beacon_node: &RemoteBeaconNode<E>,
+    slot_clock: &T,
+    sync_tolerance: u64,
+    min_peer_count: usize,
+    log_opt: Option<&Logger>,
+) -> SyncStatus {
+    let status = is_synced(beacon_node, slot_clock, sync_tolerance, log_opt).await;
+
+    if !status.is_ready() || min_peer_count == 0 {
+        return status;
+    }
+
+    let peer_count = match beacon_node.http.network().get_peer_count().await {
+        Ok(peer_count) => peer_count,
+        Err(e) => {
             if let Some(log) = log_opt {
-                debug!(
+                error!(
                     log,
-                    "Beacon node sync status";
-                    "status" => format!("{:?}", resp),
-                );
+                    "Unable to read beacon node peer count";
+                    "error" => format!("{:?}", e)
+                )
             }
 
-            let now = if let Some(slot) = slot_clock.now() {
-                slot
-            } else {
-                // There's no good reason why we shouldn't be able to read the slot clock, so we'll
-                // indicate we're not synced if that's the case.
-                return false;
-            };
-
-            if sync_status.current_slot + SYNC_TOLERANCE >= now {
-                true
-            } else {
-                if let Some(log) = log_opt {
-                    error!(
-                        log,
-                        "Beacon node is syncing";
-                        "msg" => "not receiving new duties",
-                        "target_slot" => sync_status.highest_slot.as_u64(),
-                        "current_slot" => sync_status.current_slot.as_u64(),
-                    );
-                }
-                false
-            }
+            return SyncStatus::Unreachable;
+        }
+    };
+
+    let status = peer_status(status, peer_count, min_peer_count);
+
+    if let Some(log) = log_opt.filter(|_| status == SyncStatus::InsufficientPeers) {
+        warn!(
+            log,
+            "Beacon node has insufficient peers";
+            "msg" => "not receiving new duties",
+            "peer_count" => peer_count,
+            "min_peer_count" => min_peer_count,
+        );
+    }
+
+    status
+}
+
+/// Returns the `SyncStatus` of a beacon node with the given sync `status` which is connected to
+/// `peer_count` peers.
+fn peer_status(status: SyncStatus, peer_count: usize, min_peer_count: usize) -> SyncStatus {
+    if status.is_ready() && peer_count < min_peer_count {
+        SyncStatus::InsufficientPeers
+    } else {
+        status
+    }
+}
+
+/// Returns `true` if a beacon node with its head at `head_slot` is no more than `sync_tolerance`
+/// slots behind the slot `now`.
+fn is_within_sync_tolerance(head_slot: Slot, now: Slot, sync_tolerance: u64) -> bool {
+    head_slot + sync_tolerance >= now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rest_types::SyncingStatus;
+
+    fn syncing_response(is_syncing: bool, current_slot: Slot) -> SyncingResponse {
+        SyncingResponse {
+            is_syncing,
+            sync_status: SyncingStatus {
+                starting_slot: Slot::new(0),
+                current_slot,
+                highest_slot: current_slot,
+            },
         }
     }
+
+    #[test]
+    fn sync_status_distinguishes_distance() {
+        let now = Slot::new(10);
+        let sync_tolerance = 4;
+
+        assert_eq!(
+            sync_status(&syncing_response(false, now - 8), Some(now), sync_tolerance),
+            SyncStatus::Synced
+        );
+        assert_eq!(
+            sync_status(&syncing_response(true, now - 4), Some(now), sync_tolerance),
+            SyncStatus::SyncingWithinTolerance
+        );
+        assert_eq!(
+            sync_status(&syncing_response(true, now - 5), Some(now), sync_tolerance),
+            SyncStatus::SyncingTooFarBehind
+        );
+        assert_eq!(
+            sync_status(&syncing_response(true, now), None, sync_tolerance),
+            SyncStatus::SyncingTooFarBehind
+        );
+        assert!(SyncStatus::SyncingWithinTolerance.is_ready());
+        assert!(!SyncStatus::Unreachable.is_ready());
+    }
+
+    #[test]
+    fn synced_node_without_peers_is_not_ready() {
+        let min_peer_count = 1;
+
+        assert_eq!(
+            peer_status(SyncStatus::Synced, 0, min_peer_count),
+            SyncStatus::InsufficientPeers
+        );
+        assert_eq!(
+            peer_status(SyncStatus::SyncingWithinTolerance, 0, min_peer_count),
+            SyncStatus::InsufficientPeers
+        );
+        assert_eq!(
+            peer_status(SyncStatus::Synced, 3, min_peer_count),
+            SyncStatus::Synced
+        );
+        assert_eq!(
+            peer_status(SyncStatus::SyncingTooFarBehind, 3, min_peer_count),
+            SyncStatus::SyncingTooFarBehind
+        );
+        assert!(!SyncStatus::InsufficientPeers.is_ready());
+    }
 }
@@ -106,6 +106,12 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             RemoteBeaconNode::new_with_timeout(config.http_server.clone(), HTTP_TIMEOUT)
                 .map_err(|e| format!("Unable to init beacon node http client: {}", e))?;
 
+        info!(
+            log,
+            "Initialized beacon node http client";
+            "timeout_secs" => beacon_node.http.timeout().as_secs(),
+        );
+
         // TODO: check if all logs in wait_for_node are produed while awaiting
         let beacon_node = wait_for_node(beacon_node, &log).await?;
         let eth2_config = beacon_node
@@ -209,6 +215,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .beacon_node(beacon_node.clone())
             .runtime_context(context.service_context("duties".into()))
             .allow_unsynced_beacon_node(config.allow_unsynced_beacon_node)
+            .sync_tolerances(config.sync_tolerances)
+            .min_peer_count(config.min_peer_count)
             .build()?;
 
         let block_service = BlockServiceBuilder::new()
@@ -217,6 +225,9 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .beacon_node(beacon_node.clone())
             .runtime_context(context.service_context("block".into()))
             .graffiti(config.graffiti)
+            .allow_unsynced_beacon_node(config.allow_unsynced_beacon_node)
+            .sync_tolerances(config.sync_tolerances)
+            .min_peer_count(config.min_peer_count)
             .build()?;
 
         let attestation_service = AttestationServiceBuilder::new()
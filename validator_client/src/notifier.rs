@@ -1,4 +1,7 @@
-use crate::{is_synced::is_synced, ProductionValidatorClient};
+use crate::{
+    is_synced::{is_synced_with_peers, SyncStatus},
+    ProductionValidatorClient,
+};
 use futures::StreamExt;
 use slog::{error, info};
 use slot_clock::SlotClock;
@@ -11,6 +14,8 @@ pub fn spawn_notifier<T: EthSpec>(client: &ProductionValidatorClient<T>) -> Resu
     let executor = context.executor.clone();
     let duties_service = client.duties_service.clone();
     let allow_unsynced_beacon_node = client.config.allow_unsynced_beacon_node;
+    let sync_tolerance = client.config.sync_tolerances.attestation;
+    let min_peer_count = client.config.min_peer_count;
 
     let slot_duration = Duration::from_millis(context.eth2_config.spec.milliseconds_per_slot);
     let duration_to_next_slot = duties_service
@@ -26,15 +31,24 @@ pub fn spawn_notifier<T: EthSpec>(client: &ProductionValidatorClient<T>) -> Resu
         let log = context.log();
 
         while interval.next().await.is_some() {
-            if !is_synced(
+            match is_synced_with_peers(
                 &duties_service.beacon_node,
                 &duties_service.slot_clock,
+                sync_tolerance,
+                min_peer_count,
                 Some(&log),
             )
             .await
-                && !allow_unsynced_beacon_node
             {
-                continue;
+                SyncStatus::Synced | SyncStatus::SyncingWithinTolerance => {}
+                // The failure to reach the beacon node has already been logged and the duties are
+                // no longer being updated, so there is nothing useful to report.
+                SyncStatus::Unreachable => continue,
+                SyncStatus::SyncingTooFarBehind | SyncStatus::InsufficientPeers => {
+                    if !allow_unsynced_beacon_node {
+                        continue;
+                    }
+                }
             }
 
             if let Some(slot) = duties_service.slot_clock.now() {
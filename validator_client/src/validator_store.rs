@@ -138,6 +138,20 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         validator_pubkey: &PublicKey,
         block: BeaconBlock<E>,
         current_slot: Slot,
+    ) -> Option<SignedBeaconBlock<E>> {
+        self.sign_block_with_fork(validator_pubkey, block, current_slot, &self.fork()?)
+    }
+
+    /// As per `sign_block`, but signs with the given `fork` rather than the one obtained from the
+    /// beacon node.
+    ///
+    /// Useful for signing across a fork boundary.
+    pub fn sign_block_with_fork(
+        &self,
+        validator_pubkey: &PublicKey,
+        block: BeaconBlock<E>,
+        current_slot: Slot,
+        fork: &Fork,
     ) -> Option<SignedBeaconBlock<E>> {
         // Make sure the block slot is not higher than the current slot to avoid potential attacks.
         if block.slot > current_slot {
@@ -151,11 +165,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
 
         // Check for slashing conditions.
-        let fork = self.fork()?;
         let domain = self.spec.get_domain(
             block.epoch(),
             Domain::BeaconProposer,
-            &fork,
+            fork,
             self.genesis_validators_root,
         );
 
@@ -163,6 +176,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validator_pubkey,
             &block.block_header(),
             domain,
+            self.genesis_validators_root,
         );
 
         match slashing_status {
@@ -173,7 +187,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
                 Some(block.sign(
                     &voting_keypair.sk,
-                    &fork,
+                    fork,
                     self.genesis_validators_root,
                     &self.spec,
                 ))
@@ -194,11 +208,29 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 );
                 None
             }
-            Err(e) => {
+            Err(NotSafe::GenesisValidatorsRootMismatch { stored, provided }) => {
+                crit!(
+                    self.log,
+                    "Not signing block for a different network";
+                    "msg" => "the slashing protection database belongs to another network",
+                    "stored_genesis_validators_root" => format!("{:?}", stored),
+                    "genesis_validators_root" => format!("{:?}", provided),
+                );
+                None
+            }
+            Err(NotSafe::InvalidBlock(e)) => {
                 crit!(
                     self.log,
                     "Not signing slashable block";
-                    "error" => format!("{:?}", e)
+                    "violation" => e.to_string()
+                );
+                None
+            }
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Unable to check block for slashing protection";
+                    "error" => e.to_string()
                 );
                 None
             }
@@ -211,6 +243,25 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         validator_committee_position: usize,
         attestation: &mut Attestation<E>,
         current_epoch: Epoch,
+    ) -> Option<()> {
+        self.sign_attestation_with_fork(
+            validator_pubkey,
+            validator_committee_position,
+            attestation,
+            current_epoch,
+            &self.fork()?,
+        )
+    }
+
+    /// As per `sign_attestation`, but signs with the given `fork` rather than the one obtained
+    /// from the beacon node.
+    pub fn sign_attestation_with_fork(
+        &self,
+        validator_pubkey: &PublicKey,
+        validator_committee_position: usize,
+        attestation: &mut Attestation<E>,
+        current_epoch: Epoch,
+        fork: &Fork,
     ) -> Option<()> {
         // Make sure the target epoch is not higher than the current epoch to avoid potential attacks.
         if attestation.data.target.epoch > current_epoch {
@@ -218,18 +269,17 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
 
         // Checking for slashing conditions.
-        let fork = self.fork()?;
-
         let domain = self.spec.get_domain(
             attestation.data.target.epoch,
             Domain::BeaconAttester,
-            &fork,
+            fork,
             self.genesis_validators_root,
         );
         let slashing_status = self.slashing_protection.check_and_insert_attestation(
             validator_pubkey,
             &attestation.data,
             domain,
+            self.genesis_validators_root,
         );
 
         match slashing_status {
@@ -242,7 +292,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     .sign(
                         &voting_keypair.sk,
                         validator_committee_position,
-                        &fork,
+                        fork,
                         self.genesis_validators_root,
                         &self.spec,
                     )
@@ -273,12 +323,31 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 );
                 None
             }
-            Err(e) => {
+            Err(NotSafe::GenesisValidatorsRootMismatch { stored, provided }) => {
+                crit!(
+                    self.log,
+                    "Not signing attestation for a different network";
+                    "msg" => "the slashing protection database belongs to another network",
+                    "stored_genesis_validators_root" => format!("{:?}", stored),
+                    "genesis_validators_root" => format!("{:?}", provided),
+                );
+                None
+            }
+            Err(NotSafe::InvalidAttestation(e)) => {
                 crit!(
                     self.log,
                     "Not signing slashable attestation";
                     "attestation" => format!("{:?}", attestation.data),
-                    "error" => format!("{:?}", e)
+                    "violation" => e.to_string()
+                );
+                None
+            }
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Unable to check attestation for slashing protection";
+                    "attestation" => format!("{:?}", attestation.data),
+                    "error" => e.to_string()
                 );
                 None
             }
@@ -295,6 +364,25 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         validator_index: u64,
         aggregate: Attestation<E>,
         selection_proof: SelectionProof,
+    ) -> Option<SignedAggregateAndProof<E>> {
+        self.produce_signed_aggregate_and_proof_with_fork(
+            validator_pubkey,
+            validator_index,
+            aggregate,
+            selection_proof,
+            &self.fork()?,
+        )
+    }
+
+    /// As per `produce_signed_aggregate_and_proof`, but signs with the given `fork` rather than
+    /// the one obtained from the beacon node.
+    pub fn produce_signed_aggregate_and_proof_with_fork(
+        &self,
+        validator_pubkey: &PublicKey,
+        validator_index: u64,
+        aggregate: Attestation<E>,
+        selection_proof: SelectionProof,
+        fork: &Fork,
     ) -> Option<SignedAggregateAndProof<E>> {
         let validators = self.validators.read();
         let voting_keypair = &validators.voting_keypair(validator_pubkey)?;
@@ -304,7 +392,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             aggregate,
             Some(selection_proof),
             &voting_keypair.sk,
-            &self.fork()?,
+            fork,
             self.genesis_validators_root,
             &self.spec,
         ))
@@ -329,3 +417,181 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fork_service::ForkServiceBuilder;
+    use account_utils::validator_definitions::ValidatorDefinitions;
+    use environment::{null_logger, EnvironmentBuilder};
+    use remote_beacon_node::RemoteBeaconNode;
+    use slot_clock::TestingSlotClock;
+    use std::fs::create_dir_all;
+    use std::time::Duration;
+    use types::{
+        test_utils::generate_deterministic_keypair, AggregateSignature, AttestationData, BitList,
+        Checkpoint, MinimalEthSpec,
+    };
+    use validator_dir::insecure_keys::build_deterministic_validator_dirs;
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn signatures_verify_against_the_given_fork() {
+        let mut env = EnvironmentBuilder::minimal()
+            .single_thread_tokio_runtime()
+            .expect("should start tokio runtime")
+            .null_logger()
+            .expect("should start null logger")
+            .build()
+            .expect("should build env");
+        let log = null_logger().expect("should start null logger");
+        let spec = E::default_spec();
+        let genesis_validators_root = Hash256::repeat_byte(42);
+
+        let dir = TempDir::new("validator_store").expect("should create temp dir");
+        let validators_dir = dir.path().join("validators");
+        let secrets_dir = dir.path().join("secrets");
+        create_dir_all(&validators_dir).expect("should create validators dir");
+        create_dir_all(&secrets_dir).expect("should create secrets dir");
+        build_deterministic_validator_dirs(validators_dir.clone(), secrets_dir.clone(), &[0])
+            .expect("should build validator dir");
+
+        let mut definitions = ValidatorDefinitions::default();
+        definitions
+            .discover_local_keystores(&validators_dir, &secrets_dir, &log)
+            .expect("should discover keystore");
+        let validators = InitializedValidators::from_definitions(
+            definitions,
+            validators_dir,
+            false,
+            log.clone(),
+        )
+        .expect("should initialize validators");
+
+        // The fork obtained from the beacon node, which must not be used for signing.
+        let head_fork = Fork {
+            previous_version: [0; 4],
+            current_version: [0; 4],
+            epoch: Epoch::new(0),
+        };
+        let fork = Fork {
+            previous_version: head_fork.current_version,
+            current_version: [1; 4],
+            epoch: Epoch::new(0),
+        };
+
+        let fork_service = ForkServiceBuilder::new()
+            .fork(head_fork)
+            .slot_clock(TestingSlotClock::new(
+                Slot::new(0),
+                Duration::from_secs(0),
+                Duration::from_secs(1),
+            ))
+            .beacon_node(
+                RemoteBeaconNode::new("http://localhost:5052".to_string())
+                    .expect("should create beacon node client"),
+            )
+            .runtime_context(env.core_context())
+            .build()
+            .expect("should build fork service");
+
+        let mut config = Config::default();
+        config.data_dir = dir.path().to_path_buf();
+        let store = ValidatorStore::new(
+            validators,
+            &config,
+            genesis_validators_root,
+            spec.clone(),
+            fork_service,
+            log,
+        )
+        .expect("should build validator store");
+        store
+            .register_all_validators_for_slashing_protection()
+            .expect("should register validator");
+
+        let keypair = generate_deterministic_keypair(0);
+        let slot = Slot::new(1);
+
+        let mut block = BeaconBlock::<E>::empty(&spec);
+        block.slot = slot;
+        let signed_block = store
+            .sign_block_with_fork(&keypair.pk, block, slot, &fork)
+            .expect("should sign block");
+        assert!(signed_block.verify_signature(
+            None,
+            &keypair.pk,
+            &fork,
+            genesis_validators_root,
+            &spec
+        ));
+        assert!(!signed_block.verify_signature(
+            None,
+            &keypair.pk,
+            &head_fork,
+            genesis_validators_root,
+            &spec
+        ));
+
+        let mut attestation = Attestation::<E> {
+            aggregation_bits: BitList::with_capacity(1).expect("should create bitlist"),
+            data: AttestationData {
+                slot,
+                index: 0,
+                beacon_block_root: Hash256::zero(),
+                source: Checkpoint {
+                    epoch: Epoch::new(0),
+                    root: Hash256::zero(),
+                },
+                target: Checkpoint {
+                    epoch: Epoch::new(0),
+                    root: Hash256::zero(),
+                },
+            },
+            signature: AggregateSignature::infinity(),
+        };
+        store
+            .sign_attestation_with_fork(&keypair.pk, 0, &mut attestation, Epoch::new(0), &fork)
+            .expect("should sign attestation");
+        let attestation_root = |fork: &Fork| {
+            let domain = spec.get_domain(
+                attestation.data.target.epoch,
+                Domain::BeaconAttester,
+                fork,
+                genesis_validators_root,
+            );
+            attestation.data.signing_root(domain)
+        };
+        assert!(attestation
+            .signature
+            .fast_aggregate_verify(attestation_root(&fork), &[&keypair.pk]));
+        assert!(!attestation
+            .signature
+            .fast_aggregate_verify(attestation_root(&head_fork), &[&keypair.pk]));
+
+        let selection_proof =
+            SelectionProof::new::<E>(slot, &keypair.sk, &fork, genesis_validators_root, &spec);
+        let signed_aggregate = store
+            .produce_signed_aggregate_and_proof_with_fork(
+                &keypair.pk,
+                0,
+                attestation,
+                selection_proof,
+                &fork,
+            )
+            .expect("should sign aggregate");
+        assert!(signed_aggregate.is_valid_signature(
+            &keypair.pk,
+            &fork,
+            genesis_validators_root,
+            &spec
+        ));
+        assert!(!signed_aggregate.is_valid_signature(
+            &keypair.pk,
+            &head_fork,
+            genesis_validators_root,
+            &spec
+        ));
+    }
+}